@@ -1,5 +1,9 @@
 use glam::Vec2;
-use raylib::{drawing::RaylibDraw, math::Vector2, prelude::Color as RaylibColor};
+use raylib::{
+    drawing::RaylibDraw,
+    math::{Rectangle, Vector2},
+    prelude::Color as RaylibColor,
+};
 use rshigg::{Color, DrawBackend, Gui, Rect, Theme};
 
 pub fn draw_gui<T: Clone + Copy, D: RaylibDraw>(gui: &Gui<T>, draw: &mut D) {
@@ -23,6 +27,18 @@ impl<D: RaylibDraw> DrawBackend for RaylibBackend<'_, D> {
         );
     }
 
+    fn fill_rect_gradient(&mut self, rect: Rect, corners: [Color; 4]) {
+        let [top_left, top_right, bottom_right, bottom_left] = corners.map(to_raylib_color);
+        // raylib takes the corners counter-clockwise from the top-left
+        self.draw.draw_rectangle_gradient_ex(
+            Rectangle::new(rect.position.x, rect.position.y, rect.size.x, rect.size.y),
+            top_left,
+            bottom_left,
+            bottom_right,
+            top_right,
+        );
+    }
+
     fn draw_line(&mut self, start: Vec2, end: Vec2, color: Color, thickness: f32) {
         if thickness <= 1.0 {
             self.draw.draw_line(
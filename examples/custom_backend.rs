@@ -1,9 +1,21 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use glam::Vec2;
 use rshigg::{
-    transform_mouse_to_subsurface_coords, Button, ButtonToggle, Color, DrawBackend, Gui,
-    ImageStyle, LeftRightSelector, MoveAndResizeThumbs, Rect, Slider, Theme, VerticalSlider,
+    draw_list_row_text, transform_mouse_to_subsurface_coords, Atlas, Button, ButtonToggle,
+    Checkbox, Color, ColorPicker, ContextMenu, DrawBackend, Dropdown, Easing, Event, FontId,
+    FontStyle, Gui, ImageRotation, ImageStyle, Key, Label, LeftRightSelector, ListSelectionMode,
+    ListView, Menu, MenuBar, MenuItem, MessageBox, MoveAndResizeThumbs, NumberField, ProgressBar,
+    ProgressText, RadioGroup, RadioLayout, RangeSlider, Rect, Slider, StyleOverride, Tab,
+    TabContainer, Theme, Tooltip, TooltipPlacement, TreeNode, TreeView, Tween, TweenTarget,
+    VerticalSlider, XYPad,
 };
 
+const FONT_BODY: FontId = FontId(1);
+const FONT_PIXEL_TITLE: FontId = FontId(2);
+const IMG_PANEL_FRAME: u64 = 3001;
+
 #[derive(Clone, Copy, Debug)]
 enum Tag {
     ToggleMute,
@@ -12,6 +24,21 @@ enum Tag {
     SetQuality,
     SetVsync,
     ResizePanel,
+    SetResolution,
+    SetSubtitles,
+    SetDifficulty,
+    PickSave,
+    PickAsset,
+    SwitchTab,
+    ConfirmQuit,
+    ConfirmDiscard,
+    SaveMenu,
+    MainMenu,
+    SetSpawnRate,
+    SetHairColor,
+    SetDeadZone,
+    FilterLevels,
+    SetBand,
 }
 
 fn main() {
@@ -19,10 +46,21 @@ fn main() {
     let render_size = Vec2::new(640.0, 360.0);
     let p = |x: f32, y: f32| Vec2::new(x * render_size.x, y * render_size.y);
 
+    let mut panel = Label::new(p(0.02, 0.02), p(0.96, 0.8), None);
+    panel.set_background_image(ImageStyle::nine_slice(IMG_PANEL_FRAME, 8.0, 8.0, 8.0, 8.0));
+    gui.add_label(panel);
+
     let mut mute_button = Button::new(p(0.05, 0.05), p(0.25, 0.15), Some("Mute".to_string()));
     let mut icon = ImageStyle::stretched(1001);
     icon.draw_over_content = true;
     mute_button.set_background_image(icon);
+    mute_button.set_font(FONT_PIXEL_TITLE);
+    mute_button.set_style_class("danger");
+    mute_button.set_style(StyleOverride {
+        bevel_size_px: Some(3.0),
+        ..StyleOverride::default()
+    });
+    let mute_button_id = mute_button.id;
     gui.add_button(mute_button, Tag::ToggleMute);
 
     let mut volume_slider = Slider::new(
@@ -37,7 +75,15 @@ fn main() {
         Some("Volume".to_string()),
     );
     volume_slider.set_track_image(ImageStyle::tiled(2001));
-    volume_slider.set_thumb_image(ImageStyle::centered(2002));
+    let mut thumb = ImageStyle::centered(2002);
+    thumb.rotation = ImageRotation::Cw90;
+    thumb.opacity = 0.75;
+    volume_slider.set_thumb_image(thumb);
+    gui.set_tooltip(
+        volume_slider.id,
+        Tooltip::rich("Master volume, [b]0[/b] to [b]100[/b]")
+            .with_placement(TooltipPlacement::Above),
+    );
     gui.add_slider(volume_slider, Tag::SetVolume);
     gui.add_vertical_slider(
         VerticalSlider::new(
@@ -83,6 +129,292 @@ fn main() {
         Tag::ResizePanel,
     );
 
+    let resolutions: Vec<String> = (0..40)
+        .map(|i| format!("{}x{}", 640 + i * 64, 360 + i * 36))
+        .collect();
+    let resolution_dropdown = Dropdown::new(p(0.5, 0.64), p(0.3, 0.1), resolutions, 0);
+    let resolution_id = resolution_dropdown.id;
+    gui.add_dropdown(resolution_dropdown, Tag::SetResolution);
+
+    let mut subtitles = Checkbox::new(
+        p(0.68, 0.48),
+        p(0.25, 0.08),
+        Some("Subtitles".to_string()),
+        true,
+    );
+    subtitles.set_tri_state(true);
+    let subtitles_id = subtitles.id;
+    gui.add_checkbox(subtitles, Tag::SetSubtitles);
+    let difficulty = RadioGroup::new(
+        p(0.05, 0.84),
+        p(0.6, 0.08),
+        vec!["Easy".to_string(), "Normal".to_string(), "Hard".to_string()],
+        1,
+        RadioLayout::Horizontal,
+    );
+    let difficulty_id = difficulty.id;
+    gui.add_radio_group(difficulty, Tag::SetDifficulty);
+    let mut health = ProgressBar::new(p(0.68, 0.58), p(0.25, 0.04), 0.0, 100.0, 80.0);
+    health.set_trail(true);
+    health.trail_delay = 0.1;
+    health.set_segments(4);
+    health.set_text(ProgressText::Percent);
+    let health_id = health.id;
+    gui.add_progress_bar(health);
+    let help = Label::new(p(0.68, 0.5), p(0.25, 0.08), Some("Press F1".to_string()));
+    let help_id = help.id;
+    gui.add_label(help);
+
+    // four tabs don't fit, the strip scrolls; only the selected page is visible
+    let mut settings_tabs = TabContainer::new(
+        p(0.66, 0.42),
+        p(0.3, 0.22),
+        18.0,
+        56.0,
+        vec![
+            Tab::new("Game").with_page_ids(vec![subtitles_id, health_id]),
+            Tab::new("Help").with_page_ids(vec![help_id]),
+            Tab::new("Audio"),
+            Tab::new("Input"),
+        ],
+    );
+    settings_tabs.set_font_size(12.0);
+    let settings_tabs_top = settings_tabs.position;
+    let settings_tabs_id = settings_tabs.id;
+    gui.add_tab_container(settings_tabs, Tag::SwitchTab);
+
+    // thousands of save slots, only the visible rows are drawn
+    let saves: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(
+        (1..=5000).map(|i| format!("Save {i:04}")).collect(),
+    ));
+    let mut save_list = ListView::new(p(0.35, 0.04), p(0.55, 0.22), 5000, 16.0);
+    save_list.set_selection_mode(ListSelectionMode::Multiple);
+    save_list.set_reorderable(true);
+    save_list.set_font_size(12.0);
+    let row_saves = saves.clone();
+    save_list.set_draw_row(move |backend, theme, row| {
+        if let Some(name) = row_saves.borrow().get(row.index) {
+            draw_list_row_text(backend, theme, row, name);
+        }
+    });
+    let save_list_top = save_list.position;
+    let save_list_id = save_list.id;
+    gui.add_list_view(save_list, Tag::PickSave);
+
+    // right-click menu on the save list, with a sort submenu
+    const MENU_SAVE: u32 = 1;
+    const MENU_SORT: u32 = 2;
+    let mut save_menu = ContextMenu::new(
+        Menu::new(
+            MENU_SAVE,
+            vec![
+                MenuItem::new("Load"),
+                MenuItem::new("Rename"),
+                MenuItem::new("Delete").with_enabled(false),
+                MenuItem::separator(),
+                MenuItem::submenu(
+                    "Sort by",
+                    Menu::new(
+                        MENU_SORT,
+                        vec![MenuItem::new("Name"), MenuItem::new("Date")],
+                    ),
+                ),
+                MenuItem::checkable("Show autosaves", true),
+            ],
+        ),
+        vec![save_list_id],
+        120.0,
+        18.0,
+    );
+    save_menu.set_font_size(12.0);
+    let save_menu_id = save_menu.id;
+    gui.add_context_menu(save_menu, Tag::SaveMenu);
+
+    // folders load their children the first time they open
+    let mut assets = TreeView::new(
+        p(0.68, 0.84),
+        p(0.3, 0.15),
+        vec![
+            TreeNode::lazy(1, "textures"),
+            TreeNode::new(2, "sounds").with_children(vec![TreeNode::new(20, "click.wav")]),
+        ],
+        16.0,
+    );
+    assets.set_font_size(12.0);
+    assets.set_load_children(|key| {
+        (0..3)
+            .map(|i| TreeNode::new(key * 10 + i, &format!("tile_{i}.png")))
+            .collect()
+    });
+    let assets_top = assets.position;
+    let assets_id = assets.id;
+    gui.add_tree_view(assets, Tag::PickAsset);
+
+    // editor-style menu bar across the top
+    const MENU_FILE: u32 = 3;
+    const MENU_EDIT: u32 = 4;
+    let mut main_menu = MenuBar::new(
+        Vec2::ZERO,
+        Vec2::new(render_size.x, 14.0),
+        vec![
+            MenuItem::submenu(
+                "File",
+                Menu::new(
+                    MENU_FILE,
+                    vec![
+                        MenuItem::new("Save").with_shortcut("Ctrl+S"),
+                        MenuItem::separator(),
+                        MenuItem::new("Quit").with_shortcut("Ctrl+Q"),
+                    ],
+                ),
+            ),
+            MenuItem::submenu(
+                "Edit",
+                Menu::new(
+                    MENU_EDIT,
+                    vec![
+                        MenuItem::new("Undo").with_shortcut("Ctrl+Z"),
+                        MenuItem::new("Redo").with_enabled(false),
+                        MenuItem::checkable("Snap to grid", false).with_shortcut("Ctrl+G"),
+                    ],
+                ),
+            ),
+        ],
+        40.0,
+        130.0,
+    );
+    main_menu.set_font_size(10.0);
+    let main_menu_id = main_menu.id;
+    gui.add_menu_bar(main_menu, Tag::MainMenu);
+
+    let mut spawn_rate = NumberField::new(p(0.5, 0.755), p(0.16, 0.06), 0.0, 10.0, 0.001, 1.0);
+    spawn_rate.set_precision(3);
+    spawn_rate.set_units(" s");
+    spawn_rate.set_drag_speed(0.01);
+    spawn_rate.set_font_size(12.0);
+    let spawn_rate_up =
+        spawn_rate.increment_button.position + spawn_rate.increment_button.size * 0.5;
+    let spawn_rate_text = spawn_rate.field_rect().position + Vec2::new(10.0, 10.0);
+    gui.add_number_field(spawn_rate, Tag::SetSpawnRate);
+
+    gui.bounds = Some(Rect::new(Vec2::ZERO, render_size));
+    gui.tooltip_delay_seconds = 0.1;
+
+    // click the second save, Shift+Down extends the selection, then drag the first
+    // save below the third
+    let row_center = |index: f32| save_list_top + Vec2::new(20.0, index * 16.0 + 8.0);
+    let mut list_events = Vec::new();
+    list_events.extend(gui.step(row_center(1.0), true));
+    list_events.extend(gui.step(row_center(1.0), false));
+    gui.modifiers.shift = true;
+    list_events.extend(gui.key_pressed(Key::Down));
+    gui.modifiers.shift = false;
+    list_events.extend(gui.step(row_center(0.0), true));
+    list_events.extend(gui.step(save_list_top + Vec2::new(20.0, 48.0), true));
+    list_events.extend(gui.step(save_list_top + Vec2::new(20.0, 48.0), false));
+    for tagged in &list_events {
+        if let Event::ListReordered { from, to } = tagged.event {
+            let mut saves = saves.borrow_mut();
+            let save = saves.remove(from);
+            saves.insert(to, save);
+        }
+    }
+    println!("list events: {:?}", list_events);
+    if let Some(list) = gui.get_list_view(save_list_id) {
+        println!("selected saves: {:?}", list.selected_rows());
+    }
+
+    // open the lazy folder, pick its second file, then walk back up and close it
+    let mut tree_events = Vec::new();
+    for (offset, pressed) in [(Vec2::new(6.0, 8.0), true), (Vec2::new(6.0, 8.0), false)] {
+        tree_events.extend(gui.step(assets_top + offset, pressed));
+    }
+    for pressed in [true, false] {
+        tree_events.extend(gui.step(assets_top + Vec2::new(40.0, 40.0), pressed));
+    }
+    tree_events.extend(gui.key_pressed(Key::Left));
+    tree_events.extend(gui.key_pressed(Key::Left));
+    println!("tree events: {:?}", tree_events);
+    if let Some(tree) = gui.get_tree_view(assets_id) {
+        println!("selected asset: {:?}", tree.selected_text());
+    }
+
+    // open the help page, then walk the strip with the keyboard and come back
+    let mut tab_events = Vec::new();
+    for pressed in [true, false] {
+        tab_events.extend(gui.step(settings_tabs_top + Vec2::new(80.0, 9.0), pressed));
+    }
+    println!(
+        "subtitles visible on help page: {}",
+        gui.is_visible(subtitles_id)
+    );
+    for key in [Key::End, Key::Home] {
+        tab_events.extend(gui.key_pressed(key));
+    }
+    println!("tab events: {:?}", tab_events);
+    if let Some(tabs) = gui.get_tab_container(settings_tabs_id) {
+        println!("help visible on game page: {}", gui.is_visible(help_id));
+        println!("tab strip scroll: {}", tabs.scroll_offset);
+    }
+
+    // right-click a save, hover "Sort by" and click "Date" in the submenu; then reopen
+    // the menu and untick "Show autosaves" from the keyboard
+    let mut menu_events = Vec::new();
+    let click_at = save_list_top + Vec2::new(40.0, 30.0);
+    gui.secondary_click(click_at);
+    let item_center = |gui: &Gui<Tag>, level: usize, index: usize| {
+        let context_menu = gui.get_context_menu(save_menu_id)?;
+        let panel = *context_menu.panel_rects().get(level)?;
+        let menu = context_menu.popup.open_menus(&context_menu.menu)[level];
+        let item = menu.item_rects(panel.position, panel.size.x, 18.0)[index];
+        Some(item.position + item.size * 0.5)
+    };
+    if let Some(sort_by) = item_center(&gui, 0, 4) {
+        menu_events.extend(gui.step(sort_by, false));
+    }
+    if let Some(date) = item_center(&gui, 1, 1) {
+        menu_events.extend(gui.step(date, true));
+        menu_events.extend(gui.step(date, false));
+    }
+    gui.secondary_click(click_at);
+    for key in [Key::Up, Key::Enter] {
+        menu_events.extend(gui.key_pressed(key));
+    }
+    println!("menu events: {:?}", menu_events);
+
+    // open File, slide over to Edit and click Undo; then press Ctrl+G
+    let mut menu_bar_events = Vec::new();
+    menu_bar_events.extend(gui.step(Vec2::new(20.0, 7.0), true));
+    menu_bar_events.extend(gui.step(Vec2::new(20.0, 7.0), false));
+    menu_bar_events.extend(gui.step(Vec2::new(60.0, 7.0), false));
+    if let Some(menu_bar) = gui.get_menu_bar(main_menu_id) {
+        println!("open menu: {:?}", menu_bar.open_index);
+    }
+    for pressed in [true, false] {
+        menu_bar_events.extend(gui.step(Vec2::new(60.0, 21.0), pressed));
+    }
+    menu_bar_events.extend(gui.trigger_shortcut("Ctrl+G"));
+    println!("menu bar events: {:?}", menu_bar_events);
+
+    // click the up arrow, hold it until it repeats, Shift-drag the value down, then
+    // click the field and type an expression
+    let mut number_events = Vec::new();
+    number_events.extend(gui.step(spawn_rate_up, true));
+    number_events.extend(gui.step(spawn_rate_up, false));
+    number_events.extend(gui.step(spawn_rate_up, true));
+    number_events.extend(gui.update(0.5));
+    number_events.extend(gui.step(spawn_rate_up, false));
+    gui.modifiers.shift = true;
+    number_events.extend(gui.step(spawn_rate_text, true));
+    number_events.extend(gui.step(spawn_rate_text - Vec2::new(40.0, 0.0), true));
+    number_events.extend(gui.step(spawn_rate_text - Vec2::new(40.0, 0.0), false));
+    gui.modifiers.shift = false;
+    number_events.extend(gui.step(spawn_rate_text, true));
+    number_events.extend(gui.step(spawn_rate_text, false));
+    gui.text_input("0.5 / 4");
+    number_events.extend(gui.key_pressed(Key::Enter));
+    println!("number events: {:?}", number_events);
+
     let window_size = Vec2::new(1280.0, 720.0);
     let mouse_in_window = Vec2::new(512.0, 252.0);
     let transformed_mouse =
@@ -94,14 +426,206 @@ fn main() {
     println!("pressed events: {:?}", pressed_events);
     println!("released events: {:?}", released_events);
 
+    // keyboard: pick the third resolution from the list, cycle the tri-state checkbox
+    // and move the radio selection
+    gui.set_focus(Some(resolution_id));
+    let mut key_events = Vec::new();
+    for key in [Key::Enter, Key::Down, Key::Down, Key::Enter] {
+        key_events.extend(gui.key_pressed(key));
+    }
+    gui.set_focus(Some(subtitles_id));
+    key_events.extend(gui.key_pressed(Key::Space));
+    gui.set_focus(Some(difficulty_id));
+    key_events.extend(gui.key_pressed(Key::Right));
+    println!("key events: {:?}", key_events);
+
+    // fade the mute button in, driven by fixed 0.1s frames; it is drawn mid-fade.
+    // the mouse rests on the volume slider long enough for its tooltip to show
+    gui.set_opacity(mute_button_id, 0.0);
+    gui.add_tween(
+        Tween::new(mute_button_id, TweenTarget::Opacity(1.0), 0.25).with_easing(Easing::QuadOut),
+    );
+    // take damage, the trail starts catching up on the second frame
+    if let Some(health) = gui.get_progress_bar_mut(health_id) {
+        health.set_value(35.0);
+    }
+    for _ in 0..2 {
+        let update_events = gui.update(0.1);
+        println!("update events: {:?}", update_events);
+    }
+
+    // a quit prompt with a second prompt stacked on top. Escape answers the top one,
+    // then clicking "No" answers the first; the widgets beneath get neither
+    let screen = Rect::new(Vec2::ZERO, render_size);
+    let quit = MessageBox::centered(
+        screen,
+        p(0.5, 0.4),
+        "Quit",
+        "Quit without saving? Progress since the last save is lost.",
+        &["Yes", "No"],
+    );
+    let quit_no = &quit.buttons[1];
+    let quit_no_center = quit_no.position + quit_no.size * 0.5;
+    gui.add_message_box(quit, Tag::ConfirmQuit);
+    let discard = MessageBox::centered(
+        screen,
+        p(0.4, 0.3),
+        "Replay",
+        "Discard the unsaved replay too?",
+        &["Discard", "Keep", "Cancel"],
+    );
+    gui.add_message_box(discard, Tag::ConfirmDiscard);
+    let mut dialog_events = gui.key_pressed(Key::Escape);
+    for pressed in [true, false] {
+        dialog_events.extend(gui.step(quit_no_center, pressed));
+    }
+    println!("dialog events: {:?}", dialog_events);
+    // left open, so the render below shows the dimmed gui
+    gui.add_message_box(
+        MessageBox::centered(screen, p(0.4, 0.3), "Saved", "Settings saved.", &["OK"]),
+        Tag::ConfirmQuit,
+    );
+
+    let theme = Theme {
+        body_font: FontStyle {
+            font: FONT_BODY,
+            size_px: None,
+        },
+        button_font: FontStyle {
+            font: FONT_BODY,
+            size_px: Some(18.0),
+        },
+        ..Theme::default()
+    };
+    gui.add_style_class(
+        "danger",
+        StyleOverride {
+            control_color: Some(Color::rgb(200, 40, 40)),
+            text_color: Some(Color::rgb(255, 255, 255)),
+            ..StyleOverride::default()
+        },
+    );
     let mut backend = CommandBufferBackend::default();
-    rshigg::draw_gui(&gui, &mut backend, &Theme::default());
+    backend.atlas.add_packed_texture(
+        "ui_packed.png",
+        &[
+            (IMG_PANEL_FRAME, "panel_frame", Vec2::new(32.0, 32.0)),
+            (1001, "mute_icon", Vec2::new(24.0, 24.0)),
+            (2001, "volume_track", Vec2::new(64.0, 16.0)),
+            (2002, "volume_thumb", Vec2::new(16.0, 24.0)),
+        ],
+        128.0,
+        1.0,
+    );
+    rshigg::draw_gui(&gui, &mut backend, &theme);
     backend.dump();
+
+    // the character editor is a separate gui: drag the hair color to the square's
+    // top-right, pick a swatch, then type a hex color
+    let mut editor = Gui::new();
+    let mut hair = ColorPicker::new(Vec2::ZERO, Vec2::new(200.0, 160.0), Color::rgb(90, 60, 40));
+    hair.set_show_alpha(true);
+    hair.set_swatches(vec![
+        Color::rgb(20, 20, 20),
+        Color::rgb(120, 80, 40),
+        Color::rgb(230, 200, 120),
+        Color::rgb(180, 50, 30),
+    ]);
+    let hair_square = hair.saturation_value_rect();
+    let hair_hex = hair.hex_rect().position + Vec2::splat(5.0);
+    let hair_swatch = hair.swatch_rects()[2].position + Vec2::splat(5.0);
+    let hair_id = hair.id;
+    editor.add_color_picker(hair, Tag::SetHairColor);
+    let mut color_events = Vec::new();
+    color_events.extend(editor.step(hair_square.position + hair_square.size * 0.5, true));
+    color_events.extend(editor.step(hair_square.position + Vec2::new(500.0, -20.0), true));
+    color_events.extend(editor.step(hair_square.position, false));
+    for pressed in [true, false] {
+        color_events.extend(editor.step(hair_swatch, pressed));
+    }
+    for pressed in [true, false] {
+        color_events.extend(editor.step(hair_hex, pressed));
+    }
+    editor.text_input("#3a7");
+    color_events.extend(editor.key_pressed(Key::Enter));
+    println!("color events: {:?}", color_events);
+    if let Some(hair) = editor.get_color_picker(hair_id) {
+        println!("hair color: {}", hair.color().to_hex());
+    }
+
+    // stick dead-zone tuning: y points up and the value stays inside the circle, so
+    // dragging past the corner stops on the rim
+    let mut dead_zone = XYPad::new(
+        Vec2::new(0.0, 170.0),
+        Vec2::new(100.0, 100.0),
+        Vec2::new(-1.0, 1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::splat(0.05),
+        Vec2::ZERO,
+    );
+    dead_zone.set_circular(true);
+    let dead_zone_center = dead_zone.value_to_point(Vec2::ZERO);
+    editor.add_xy_pad(dead_zone, Tag::SetDeadZone);
+    let mut pad_events = Vec::new();
+    pad_events.extend(editor.step(dead_zone_center + Vec2::new(20.0, -10.0), true));
+    pad_events.extend(editor.step(dead_zone_center + Vec2::new(200.0, -200.0), true));
+    pad_events.extend(editor.step(dead_zone_center + Vec2::new(200.0, -200.0), false));
+    println!("pad events: {:?}", pad_events);
+
+    // "level 10 to 25": drag the high thumb up, slide the whole range down, then click
+    // the track left of the range to pull the low thumb there
+    let levels = RangeSlider::new(
+        Vec2::new(110.0, 170.0),
+        Vec2::new(196.0, 16.0),
+        8.0,
+        1.0,
+        50.0,
+        1.0,
+        10.0,
+        25.0,
+        0.0,
+    );
+    let level_x = |level: f32| levels.value_to_axis(level);
+    let (high_x, middle_x, track_x) = (level_x(25.0), level_x(18.0), level_x(3.0));
+    editor.add_range_slider(levels, Tag::FilterLevels);
+    let level_point = |x: f32| Vec2::new(x, 178.0);
+    let mut range_events = Vec::new();
+    for (x, pressed) in [
+        (high_x, true),
+        (high_x + 40.0, true),
+        (high_x + 40.0, false),
+        (middle_x, true),
+        (middle_x - 20.0, true),
+        (middle_x - 20.0, false),
+        (track_x, true),
+        (track_x, false),
+    ] {
+        range_events.extend(editor.step(level_point(x), pressed));
+    }
+    println!("range events: {:?}", range_events);
+    let mut band = RangeSlider::new(
+        Vec2::new(320.0, 170.0),
+        Vec2::new(16.0, 100.0),
+        8.0,
+        20.0,
+        20000.0,
+        10.0,
+        200.0,
+        4000.0,
+        0.0,
+    );
+    band.set_vertical(true);
+    editor.add_range_slider(band, Tag::SetBand);
+
+    let mut editor_backend = CommandBufferBackend::default();
+    rshigg::draw_gui(&editor, &mut editor_backend, &theme);
+    println!("editor commands: {}", editor_backend.commands.len());
 }
 
 #[derive(Default)]
 struct CommandBufferBackend {
     commands: Vec<String>,
+    atlas: Atlas,
 }
 
 impl CommandBufferBackend {
@@ -110,6 +634,15 @@ impl CommandBufferBackend {
         for command in self.commands.iter().take(24) {
             println!("{command}");
         }
+        println!("...");
+        // the last commands are the overlay pass (tooltip)
+        for command in self
+            .commands
+            .iter()
+            .skip(self.commands.len().saturating_sub(6))
+        {
+            println!("{command}");
+        }
         println!("total commands: {}", self.commands.len());
     }
 }
@@ -143,6 +676,28 @@ impl DrawBackend for CommandBufferBackend {
         ));
     }
 
+    fn draw_text_with_font(
+        &mut self,
+        text: &str,
+        position: Vec2,
+        font: FontId,
+        font_size: f32,
+        color: Color,
+    ) {
+        self.commands.push(format!(
+            "draw_text '{text}' at ({:.1},{:.1}) font={} size={:.1} color=({}, {}, {}, {})",
+            position.x, position.y, font.0, font_size, color.r, color.g, color.b, color.a
+        ));
+    }
+
+    fn fill_rect_gradient(&mut self, rect: Rect, corners: [Color; 4]) {
+        let corners = corners.map(|color| color.to_hex()).join(" ");
+        self.commands.push(format!(
+            "fill_rect_gradient pos=({:.1},{:.1}) size=({:.1},{:.1}) corners={corners}",
+            rect.position.x, rect.position.y, rect.size.x, rect.size.y
+        ));
+    }
+
     fn push_clip_rect(&mut self, rect: Rect) {
         self.commands.push(format!(
             "push_clip_rect pos=({:.1},{:.1}) size=({:.1},{:.1})",
@@ -154,19 +709,28 @@ impl DrawBackend for CommandBufferBackend {
         self.commands.push("pop_clip_rect".to_string());
     }
 
+    fn image_size(&self, image_id: u64) -> Option<Vec2> {
+        self.atlas.image_size(image_id)
+    }
+
     fn draw_image(&mut self, image: ImageStyle, rect: Rect) {
+        let tint = image.effective_tint();
         self.commands.push(format!(
-            "draw_image id={} layout={:?} pos=({:.1},{:.1}) size=({:.1},{:.1}) tint=({}, {}, {}, {}) over={}",
+            "draw_image id={} layout={:?} texture_region={:?} flip=({}, {}) rotation={:?} pos=({:.1},{:.1}) size=({:.1},{:.1}) tint=({}, {}, {}, {}) over={}",
             image.image_id,
             image.layout,
+            self.atlas.resolve(&image),
+            image.flip_x,
+            image.flip_y,
+            image.rotation,
             rect.position.x,
             rect.position.y,
             rect.size.x,
             rect.size.y,
-            image.tint.r,
-            image.tint.g,
-            image.tint.b,
-            image.tint.a,
+            tint.r,
+            tint.g,
+            tint.b,
+            tint.a,
             image.draw_over_content,
         ));
     }
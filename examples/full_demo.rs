@@ -1,5 +1,5 @@
 use raylib::prelude::*;
-use rshigg::Event;
+use rshigg::{Event, Key, Modifiers};
 
 #[path = "full_demo/draw.rs"]
 mod draw;
@@ -21,6 +21,8 @@ fn main() {
     rl.set_window_size(WINDOW_DIMS.x as i32, WINDOW_DIMS.y as i32);
     center_window(&mut rl);
     rl.set_target_fps(144);
+    // escape is handled in process_input, so it can close popups first
+    rl.set_exit_key(None);
 
     let mut render_texture = rl
         .load_render_texture(&rlt, DIMS.x, DIMS.y)
@@ -45,7 +47,37 @@ fn main() {
 }
 
 fn process_input(rl: &mut RaylibHandle, state: &mut DemoState) {
-    if rl.is_key_pressed(KeyboardKey::KEY_ESCAPE) {
+    let popup_was_open = state.main_gui.has_open_popup();
+    state.main_gui.modifiers = Modifiers {
+        shift: rl.is_key_down(KeyboardKey::KEY_LEFT_SHIFT)
+            || rl.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT),
+        ctrl: rl.is_key_down(KeyboardKey::KEY_LEFT_CONTROL)
+            || rl.is_key_down(KeyboardKey::KEY_RIGHT_CONTROL),
+    };
+    for (raylib_key, key) in [
+        (KeyboardKey::KEY_UP, Key::Up),
+        (KeyboardKey::KEY_DOWN, Key::Down),
+        (KeyboardKey::KEY_LEFT, Key::Left),
+        (KeyboardKey::KEY_RIGHT, Key::Right),
+        (KeyboardKey::KEY_PAGE_UP, Key::PageUp),
+        (KeyboardKey::KEY_PAGE_DOWN, Key::PageDown),
+        (KeyboardKey::KEY_HOME, Key::Home),
+        (KeyboardKey::KEY_END, Key::End),
+        (KeyboardKey::KEY_ENTER, Key::Enter),
+        (KeyboardKey::KEY_SPACE, Key::Space),
+        (KeyboardKey::KEY_ESCAPE, Key::Escape),
+        (KeyboardKey::KEY_BACKSPACE, Key::Backspace),
+    ] {
+        if rl.is_key_pressed(raylib_key) {
+            let events = state.main_gui.key_pressed(key);
+            handle_main_events(state, events);
+        }
+    }
+    while let Some(ch) = rl.get_char_pressed() {
+        state.main_gui.text_input(&ch.to_string());
+    }
+    // escape closes an open popup before it quits
+    if rl.is_key_pressed(KeyboardKey::KEY_ESCAPE) && !popup_was_open {
         state.running = false;
     }
 }
@@ -57,10 +89,13 @@ fn step(rl: &mut RaylibHandle, state: &mut DemoState) {
 
     let main_events = state.main_gui.step(mp, mouse_pressed);
     handle_main_events(state, main_events);
+    state.main_gui.update(rl.get_frame_time());
+
+    let wheel = rl.get_mouse_wheel_move();
+    let wheel_used = wheel != 0.0 && state.main_gui.mouse_wheel(wheel);
 
     if state.settings_open {
-        let wheel = rl.get_mouse_wheel_move();
-        if wheel != 0.0 {
+        if wheel != 0.0 && !wheel_used {
             if let Some(scroll) = state
                 .settings_gui
                 .get_vertical_slider_mut(state.scroll_slider_id)
@@ -79,5 +114,6 @@ fn step(rl: &mut RaylibHandle, state: &mut DemoState) {
 
         let settings_events = state.settings_gui.step(mp, mouse_pressed);
         handle_settings_events(state, settings_events);
+        state.settings_gui.update(rl.get_frame_time());
     }
 }
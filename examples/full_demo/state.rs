@@ -1,7 +1,12 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use glam::{UVec2, Vec2};
 use rshigg::{
-    Button, ButtonToggle, Draggable, Event, Gui, Label, LeftRightSelector, MoveAndResizeThumbs,
-    Slider, TaggedEvent, VerticalSlider,
+    draw_list_row_text, Button, ButtonToggle, Checkbox, Color, Draggable, Dropdown, Event, Gui,
+    ImageStyle, Label, LeftRightSelector, ListSelectionMode, ListView, MoveAndResizeThumbs,
+    RadioGroup, RadioLayout, Rect, Slider, StateImages, TaggedEvent, Tooltip, TooltipPlacement,
+    VerticalSlider,
 };
 
 use crate::raylib_skin::{
@@ -15,6 +20,21 @@ fn px(x: f32, y: f32) -> Vec2 {
     Vec2::new(x * DIMS.x as f32, y * DIMS.y as f32)
 }
 
+/// Option button sprite, brightened on hover and darkened while pressed.
+fn option_button_skin() -> StateImages {
+    let normal = ImageStyle::stretched(IMG_OPTION_BUTTON);
+    let mut skin = StateImages::new(normal);
+    skin.hovered = Some(ImageStyle {
+        tint: Color::rgb(255, 236, 190),
+        ..normal
+    });
+    skin.pressed = Some(ImageStyle {
+        tint: Color::rgb(170, 160, 140),
+        ..normal
+    });
+    skin
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Tag {
     OpenSettings,
@@ -22,6 +42,10 @@ pub enum Tag {
     MoveWindow,
     ScrollMenu,
     MoveAndResizePreview,
+    SetResolution,
+    SetVsync,
+    SetWindowMode,
+    PickSave,
     RowButton(usize),
     RowSlider(usize),
     RowSelector(usize),
@@ -56,6 +80,8 @@ pub struct DemoState {
     pub close_window_id: u32,
     pub preview_rect_pos: Vec2,
     pub preview_rect_size: Vec2,
+    /// Save names in list order, shared with the save list's row drawing.
+    pub saves: Rc<RefCell<Vec<String>>>,
 }
 
 impl DemoState {
@@ -65,9 +91,14 @@ impl DemoState {
 
         let open_button = Button::new(px(0.02, 0.02), px(0.14, 0.07), Some("Settings".to_string()));
         let mut open_button = open_button;
-        open_button.set_background_image(rshigg::ImageStyle::stretched(IMG_OPTION_BUTTON));
+        open_button.set_state_images(option_button_skin());
         let open_settings_button_id = open_button.id;
         main_gui.add_button(open_button, Tag::OpenSettings);
+        main_gui.set_tooltip(
+            open_settings_button_id,
+            Tooltip::text("Open the settings window").with_placement(TooltipPlacement::Right),
+        );
+        main_gui.bounds = Some(Rect::new(Vec2::ZERO, px(1.0, 1.0)));
 
         let preview_pos = px(0.58, 0.18);
         let preview_size = px(0.34, 0.24);
@@ -75,6 +106,52 @@ impl DemoState {
             MoveAndResizeThumbs::new(Vec2::new(18.0, 18.0), preview_pos, preview_size);
         main_gui.add_move_and_resize_thumbs(preview_thumbs, Tag::MoveAndResizePreview);
 
+        let resolutions = (0..40)
+            .map(|i| format!("{} x {}", 640 + i * 64, 360 + i * 36))
+            .collect();
+        let resolution_dropdown = Dropdown::new(px(0.66, 0.5), px(0.2, 0.06), resolutions, 10);
+        main_gui.add_dropdown(resolution_dropdown, Tag::SetResolution);
+        let vsync = Checkbox::new(
+            px(0.66, 0.58),
+            px(0.2, 0.05),
+            Some("VSync".to_string()),
+            true,
+        );
+        main_gui.add_checkbox(vsync, Tag::SetVsync);
+        let window_mode = RadioGroup::new(
+            px(0.66, 0.65),
+            px(0.2, 0.15),
+            vec![
+                "Windowed".to_string(),
+                "Borderless".to_string(),
+                "Fullscreen".to_string(),
+            ],
+            0,
+            RadioLayout::Vertical,
+        );
+        main_gui.add_radio_group(window_mode, Tag::SetWindowMode);
+
+        let saves: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(
+            (0..2000)
+                .map(|i| format!("Slot {:04}  Ch.{}", i + 1, i % 12 + 1))
+                .collect(),
+        ));
+        let mut save_list = ListView::new(px(0.66, 0.82), px(0.3, 0.15), 2000, 24.0);
+        save_list.set_selection_mode(ListSelectionMode::Multiple);
+        save_list.set_reorderable(true);
+        save_list.set_font_size(18.0);
+        let row_saves = saves.clone();
+        save_list.set_draw_row(move |backend, theme, row| {
+            if let Some(name) = row_saves.borrow().get(row.index) {
+                draw_list_row_text(backend, theme, row, name);
+            }
+        });
+        main_gui.set_tooltip(
+            save_list.id,
+            Tooltip::text("Shift/Ctrl-click to select several saves, drag to reorder"),
+        );
+        main_gui.add_list_view(save_list, Tag::PickSave);
+
         let settings_pos = px(0.08, 0.08);
         let settings_size = px(0.55, 0.82);
 
@@ -94,7 +171,7 @@ impl DemoState {
             Some("X".to_string()),
         );
         let mut close_window = close_window;
-        close_window.set_background_image(rshigg::ImageStyle::stretched(IMG_OPTION_BUTTON));
+        close_window.set_state_images(option_button_skin());
         let close_window_id = close_window.id;
         settings_gui.add_button(close_window, Tag::CloseSettings);
 
@@ -123,6 +200,16 @@ impl DemoState {
             label.set_background_image(rshigg::ImageStyle::stretched(IMG_ROW_STRIP));
             let label_id = label.id;
             settings_gui.add_label(label);
+            let hint = match i % 4 {
+                0 => "Applies this option right away.",
+                1 => "Drag to set a value from [b]0[/b] to [b]100[/b].",
+                2 => "Pick a quality preset with the arrows.",
+                _ => "Switches this option [color=#2a2]On[/color] or [color=#a22]Off[/color].",
+            };
+            settings_gui.set_tooltip(
+                label_id,
+                Tooltip::rich(&format!("[b]Option {}[/b]\n{hint}", i + 1)),
+            );
 
             let control = match i % 4 {
                 0 => {
@@ -163,12 +250,15 @@ impl DemoState {
                         ],
                         1,
                     );
-                    selector
-                        .left_button
-                        .set_background_image(rshigg::ImageStyle::stretched(IMG_OPTION_BUTTON));
-                    selector
-                        .right_button
-                        .set_background_image(rshigg::ImageStyle::stretched(IMG_OPTION_BUTTON));
+                    // one arrow sprite, mirrored for the left button
+                    let mut right_arrow = rshigg::ImageStyle::centered(IMG_GOLD_ARROW);
+                    right_arrow.draw_over_content = true;
+                    let left_arrow = rshigg::ImageStyle {
+                        flip_x: true,
+                        ..right_arrow
+                    };
+                    selector.left_button.set_background_image(left_arrow);
+                    selector.right_button.set_background_image(right_arrow);
                     let id = selector.id;
                     settings_gui.add_left_right_selector(selector, Tag::RowSelector(i));
                     RowControl::LeftRightSelector(id)
@@ -211,6 +301,7 @@ impl DemoState {
             close_window_id,
             preview_rect_pos: preview_pos,
             preview_rect_size: preview_size,
+            saves,
         };
         sync_main_visibility(&mut state);
         layout_settings(&mut state);
@@ -235,6 +326,42 @@ pub fn handle_main_events(state: &mut DemoState, events: Vec<TaggedEvent<Tag>>)
                 state.preview_rect_pos = target_position;
                 state.preview_rect_size = target_size;
             }
+            (Tag::SetVsync, Event::CheckboxChanged { checked, .. }) => {
+                println!("vsync => {checked}")
+            }
+            (Tag::SetWindowMode, Event::RadioSelected { index }) => {
+                if let Some(group) = state.main_gui.get_radio_group(tagged.element_id) {
+                    println!(
+                        "window mode => {} ({index})",
+                        group.selected_option().unwrap_or("n/a")
+                    );
+                }
+            }
+            (Tag::PickSave, Event::ListSelectionChanged { .. }) => {
+                if let Some(list) = state.main_gui.get_list_view(tagged.element_id) {
+                    let saves = state.saves.borrow();
+                    let names: Vec<&str> = list
+                        .selected_rows()
+                        .into_iter()
+                        .map(|index| saves[index].as_str())
+                        .collect();
+                    println!("saves => {names:?}");
+                }
+            }
+            (Tag::PickSave, Event::ListReordered { from, to }) => {
+                let mut saves = state.saves.borrow_mut();
+                let save = saves.remove(from);
+                println!("moved {save} to position {}", to + 1);
+                saves.insert(to, save);
+            }
+            (Tag::SetResolution, Event::SelectionChanged { .. }) => {
+                if let Some(dropdown) = state.main_gui.get_dropdown(tagged.element_id) {
+                    println!(
+                        "resolution => {}",
+                        dropdown.selected_option().unwrap_or("n/a")
+                    );
+                }
+            }
             _ => {}
         }
     }
@@ -308,6 +435,9 @@ pub fn layout_settings(state: &mut DemoState) {
         close.size = Vec2::new(scroll_w, top_h);
     }
 
+    // keep tooltips inside the clipped window
+    state.settings_gui.bounds = Some(settings_scroll_clip_rect(state));
+
     let viewport_top = pos.y + top_h + padding;
     let viewport_bottom = pos.y + size.y - padding;
     let viewport_h = (viewport_bottom - viewport_top).max(0.01);
@@ -1,8 +1,9 @@
 use glam::{IVec2, UVec2, Vec2};
 use raylib::prelude::{Color as RayColor, *};
 use rshigg::{
-    Button, ButtonToggle, Color as UiColor, Event, Gui, ImageStyle, Label, LeftRightSelector,
-    Slider, TaggedEvent, Theme,
+    Button, ButtonToggle, Color as UiColor, Easing, Event, Gui, ImageStyle, Label,
+    LeftRightSelector, ProgressBar, ProgressText, Slider, TaggedEvent, Theme, ThemeFileError,
+    ThemeWatcher, Tween, TweenTarget,
 };
 
 #[path = "shared/raylib_skin.rs"]
@@ -13,6 +14,7 @@ use raylib_skin::{
     IMG_SLIDER_KNOB, IMG_SLIDER_TRACK, IMG_SOFT_NOISE,
 };
 
+const THEME_PATH: &str = "assets/rpg_status_theme.txt";
 const WINDOW_DIMS: UVec2 = UVec2::new(1280, 720);
 const DIMS: UVec2 = WINDOW_DIMS;
 
@@ -27,17 +29,23 @@ enum Tag {
     AutoBattle,
     DisplayAbilities,
     Back,
+    Help,
 }
 
 struct State {
     running: bool,
     gui: Gui<Tag>,
     overdrive_slider_id: u32,
+    display_button_id: u32,
+    back_button_id: u32,
+    hp_bar_id: u32,
+    mp_bar_id: u32,
 }
 
 impl State {
     fn new() -> Self {
         let mut gui = Gui::new();
+        gui.hover_fade_seconds = 0.12;
 
         // Main panel background.
         let mut panel_bg = Label::new(p(0.13, 0.14), p(0.74, 0.72), None);
@@ -54,17 +62,35 @@ impl State {
         frame_overlay.set_background_image(frame_img);
         gui.add_label(frame_overlay);
 
-        let help = Label::new(
-            p(0.16, 0.18),
-            p(0.52, 0.05),
-            Some("Choose an ability set and adjust overdrive.".to_string()),
+        gui.register_icon("meat", ImageStyle::centered(IMG_ICON_MEAT));
+        let mut help = Label::new(p(0.16, 0.18), p(0.52, 0.05), None);
+        help.set_rich_text(
+            "Choose an [color=#ffd866]ability set[/color] and adjust {icon:meat} overdrive. \
+             [link=help]More[/link]",
         );
-        gui.add_label(help);
+        help.set_auto_height(true);
+        gui.add_tagged_label(help, Tag::Help);
 
         let mut portrait = Label::new(p(0.16, 0.30), p(0.11, 0.15), None);
         portrait.set_background_image(ImageStyle::stretched(IMG_PORTRAIT));
         gui.add_label(portrait);
 
+        // HP lags a trail behind when it drops, MP is split into charges
+        let mut hp_bar = ProgressBar::new(p(0.16, 0.465), p(0.11, 0.035), 0.0, 420.0, 420.0);
+        hp_bar.set_fill_color(UiColor::rgb(196, 60, 72));
+        hp_bar.set_trail_color(UiColor::rgb(255, 214, 120));
+        hp_bar.set_trail(true);
+        hp_bar.set_text(ProgressText::Fraction);
+        hp_bar.set_font_size(18.0);
+        let hp_bar_id = hp_bar.id;
+        gui.add_progress_bar(hp_bar);
+
+        let mut mp_bar = ProgressBar::new(p(0.16, 0.51), p(0.11, 0.025), 0.0, 5.0, 5.0);
+        mp_bar.set_fill_color(UiColor::rgb(70, 120, 230));
+        mp_bar.set_segments(5);
+        let mp_bar_id = mp_bar.id;
+        gui.add_progress_bar(mp_bar);
+
         let mut ability_selector = LeftRightSelector::new(
             p(0.30, 0.30),
             p(0.50, 0.07),
@@ -143,22 +169,40 @@ impl State {
             );
         }
 
+        // the bottom buttons start below the screen and slide in
         let mut display_button = Button::new(
-            p(0.46, 0.82),
+            p(0.46, 1.05),
             p(0.24, 0.065),
             Some("Display Abilities".to_string()),
         );
         style_button_with_texture(&mut display_button);
+        let display_button_id = display_button.id;
         gui.add_button(display_button, Tag::DisplayAbilities);
 
-        let mut back_button = Button::new(p(0.72, 0.82), p(0.11, 0.065), Some("Back".to_string()));
+        let mut back_button = Button::new(p(0.72, 1.05), p(0.11, 0.065), Some("Back".to_string()));
         style_button_with_texture(&mut back_button);
+        let back_button_id = back_button.id;
         gui.add_button(back_button, Tag::Back);
 
+        gui.add_tween(
+            Tween::new(display_button_id, TweenTarget::Position(p(0.46, 0.82)), 0.5)
+                .with_easing(Easing::BackOut)
+                .with_delay(0.2),
+        );
+        gui.add_tween(
+            Tween::new(back_button_id, TweenTarget::Position(p(0.72, 0.82)), 0.5)
+                .with_easing(Easing::BackOut)
+                .with_delay(0.3),
+        );
+
         Self {
             running: true,
             gui,
             overdrive_slider_id,
+            display_button_id,
+            back_button_id,
+            hp_bar_id,
+            mp_bar_id,
         }
     }
 }
@@ -209,40 +253,58 @@ fn main() {
             std::process::exit(1);
         });
     let skins = SkinTextures::load(&mut rl, &rlt);
+    let mut theme = rpg_theme();
+    let mut theme_watcher = ThemeWatcher::new(THEME_PATH);
 
     while state.running && !rl.window_should_close() {
         if rl.is_key_pressed(KeyboardKey::KEY_ESCAPE) {
             state.running = false;
         }
 
+        // edit the theme file while the demo runs to restyle it
+        match theme_watcher.poll(&mut theme, &mut state.gui.style_classes) {
+            Ok(true) => println!("Reloaded {}", THEME_PATH),
+            Ok(false) | Err(ThemeFileError::Io(_)) => {}
+            Err(err) => eprintln!("warning: {} (keeping previous theme)", err),
+        }
+
         let mouse = rl.get_mouse_position();
         let mouse_pressed = rl.is_mouse_button_down(MouseButton::MOUSE_LEFT_BUTTON);
         let events = state.gui.step(Vec2::new(mouse.x, mouse.y), mouse_pressed);
         handle_events(&mut state, events);
+        let tween_events = state.gui.update(rl.get_frame_time());
+        handle_events(&mut state, tween_events);
 
         let mut d = rl.begin_drawing(&rlt);
         {
             let low_res_d = &mut d.begin_texture_mode(&rlt, &mut render_texture);
             low_res_d.clear_background(RayColor::new(14, 21, 44, 255));
 
-            let theme = Theme {
-                control_color: UiColor::rgba(82, 98, 148, 190),
-                text_color: UiColor::rgb(236, 244, 255),
-                shadow_color: UiColor::rgb(10, 14, 24),
-                highlight_color: UiColor::rgb(166, 184, 232),
-                track_color: UiColor::rgb(72, 84, 132),
-                hover_shade: 0.92,
-                pressed_shade: 0.78,
-                bevel_size_px: 1.0,
-                font_size_px: 24.0,
-            };
             let mut backend = SkinRaylibBackend::new(low_res_d, &skins);
             rshigg::draw_gui(&state.gui, &mut backend, &theme);
+            // link hit areas for the next step
+            state.gui.layout_text(&backend, &theme);
         }
         scale_and_blit_render_texture_to_window(&mut d, &mut render_texture);
     }
 }
 
+fn rpg_theme() -> Theme {
+    Theme {
+        control_color: UiColor::rgba(82, 98, 148, 190),
+        text_color: UiColor::rgb(236, 244, 255),
+        shadow_color: UiColor::rgb(10, 14, 24),
+        highlight_color: UiColor::rgb(166, 184, 232),
+        track_color: UiColor::rgb(72, 84, 132),
+        hover_shade: 0.92,
+        pressed_shade: 0.78,
+        bevel_size_px: 1.0,
+        font_size_px: 24.0,
+        link_color: UiColor::rgb(255, 216, 102),
+        ..Theme::default()
+    }
+}
+
 fn handle_events(state: &mut State, events: Vec<TaggedEvent<Tag>>) {
     for tagged in events {
         match (tagged.tag, tagged.event) {
@@ -258,14 +320,58 @@ fn handle_events(state: &mut State, events: Vec<TaggedEvent<Tag>>) {
             }
             (Tag::AutoBattle, Event::ButtonToggleChanged { toggled_left }) => {
                 println!("Mode => {}", if toggled_left { "Manual" } else { "Auto" });
+                // abilities are picked automatically in auto mode
+                state.gui.set_enabled(state.display_button_id, toggled_left);
+            }
+            (Tag::DisplayAbilities, Event::DisabledClicked) => {
+                println!("Display abilities is unavailable in auto mode")
+            }
+            (Tag::DisplayAbilities, Event::ButtonReleased) => {
+                println!("Display abilities");
+                // casting costs a charge of MP and the recoil costs HP, both refill when empty
+                if let Some(bar) = state.gui.get_progress_bar_mut(state.hp_bar_id) {
+                    let hp = if bar.value <= bar.minimum {
+                        bar.maximum
+                    } else {
+                        bar.value - 85.0
+                    };
+                    bar.set_value(hp);
+                }
+                if let Some(bar) = state.gui.get_progress_bar_mut(state.mp_bar_id) {
+                    let mp = if bar.value <= bar.minimum {
+                        bar.maximum
+                    } else {
+                        bar.value - 1.0
+                    };
+                    bar.set_value(mp);
+                }
+            }
+            (Tag::Back, Event::ButtonReleased) => {
+                println!("Back");
+                bounce(&mut state.gui, state.back_button_id);
+            }
+            (Tag::Help, Event::LinkClicked { link_index }) => {
+                if let Some(label) = state.gui.get_label(tagged.element_id) {
+                    println!("Link => {}", label.link_target(link_index).unwrap_or("n/a"));
+                }
             }
-            (Tag::DisplayAbilities, Event::ButtonReleased) => println!("Display abilities"),
-            (Tag::Back, Event::ButtonReleased) => println!("Back"),
             _ => {}
         }
     }
 }
 
+/// Pop an element up in size and let it spring back.
+fn bounce(gui: &mut Gui<Tag>, id: u32) {
+    gui.cancel_tweens_for(id);
+    let grow =
+        gui.add_tween(Tween::new(id, TweenTarget::Scale(1.15), 0.08).with_easing(Easing::QuadOut));
+    gui.add_tween(
+        Tween::new(id, TweenTarget::Scale(1.0), 0.35)
+            .with_easing(Easing::ElasticOut)
+            .after(grow),
+    );
+}
+
 fn center_window(rl: &mut RaylibHandle) {
     let _ = IVec2::new(rl.get_screen_width(), rl.get_screen_height());
     rl.set_window_position(40, 40);
@@ -1,12 +1,10 @@
-use std::collections::HashMap;
-
 use glam::Vec2;
 use raylib::{
     drawing::RaylibDraw,
     math::Vector2,
-    prelude::{Color as RayColor, RaylibHandle, RaylibThread, Rectangle, Texture2D},
+    prelude::{measure_text, Color as RayColor, RaylibHandle, RaylibThread, Rectangle, Texture2D},
 };
-use rshigg::{Color, DrawBackend, ImageLayout, ImageStyle, Rect};
+use rshigg::{load_atlas, Atlas, Color, DrawBackend, ImageLayout, ImageStyle, Rect};
 
 pub const IMG_STATUS_BG: u64 = 1;
 pub const IMG_HELP_BAR: u64 = 2;
@@ -23,92 +21,47 @@ pub const IMG_SOFT_NOISE: u64 = 101;
 pub const IMG_ICON_MEAT: u64 = 102;
 pub const IMG_ICON_MOUSE: u64 = 103;
 
-#[derive(Clone, Copy)]
-struct SpriteRegion {
-    texture_index: usize,
-    source: Rectangle,
-}
+pub const SKIN_ATLAS_PATH: &str = "assets/skin_atlas.txt";
 
 pub struct SkinTextures {
-    textures: Vec<Texture2D>,
-    regions: HashMap<u64, SpriteRegion>,
+    atlas: Atlas,
+    /// Loaded textures, indexed like `Atlas::textures`. `None` when loading failed.
+    textures: Vec<Option<Texture2D>>,
 }
 
 impl SkinTextures {
     pub fn load(rl: &mut RaylibHandle, thread: &RaylibThread) -> Self {
-        let mut skins = Self {
-            textures: Vec::new(),
-            regions: HashMap::new(),
-        };
-
-        if let Some(status_tex) = skins.try_load_texture(rl, thread, "refs/image copy 2.png") {
-            skins.add_region(IMG_STATUS_BG, status_tex, 0.0, 0.0, 500.0, 281.0);
-            skins.add_region(IMG_HELP_BAR, status_tex, 34.0, 14.0, 188.0, 21.0);
-            skins.add_region(IMG_ROW_STRIP, status_tex, 122.0, 84.0, 318.0, 22.0);
-            skins.add_region(IMG_SLIDER_TRACK, status_tex, 121.0, 184.0, 320.0, 16.0);
-            skins.add_region(IMG_SLIDER_KNOB, status_tex, 215.0, 184.0, 28.0, 16.0);
-            skins.add_region(IMG_PORTRAIT, status_tex, 60.0, 121.0, 56.0, 56.0);
-            skins.add_region(IMG_SOFT_NOISE, status_tex, 438.0, 2.0, 56.0, 56.0);
-        }
-
-        if let Some(menu_tex) = skins.try_load_texture(rl, thread, "refs/image copy 5.png") {
-            skins.add_region(IMG_OPTION_BUTTON, menu_tex, 196.0, 99.0, 508.0, 34.0);
-            skins.add_region(IMG_GOLD_ARROW, menu_tex, 168.0, 107.0, 28.0, 17.0);
-        }
-
-        if let Some(title_tex) = skins.try_load_texture(rl, thread, "refs/image copy.png") {
-            skins.add_region(IMG_AURORA_TILE, title_tex, 460.0, 4.0, 280.0, 70.0);
-        }
-
-        if let Some(gear_tex) = skins.try_load_texture(rl, thread, "assets/gear.png") {
-            skins.add_region(IMG_ICON_GEAR, gear_tex, 0.0, 0.0, 24.0, 24.0);
-        }
-        if let Some(potato_tex) = skins.try_load_texture(rl, thread, "assets/potato.png") {
-            skins.add_region(IMG_ICON_POTATO, potato_tex, 0.0, 0.0, 24.0, 24.0);
-        }
-        if let Some(meat_tex) = skins.try_load_texture(rl, thread, "assets/meat.png") {
-            skins.add_region(IMG_ICON_MEAT, meat_tex, 0.0, 0.0, 24.0, 24.0);
-        }
-        if let Some(mouse_tex) = skins.try_load_texture(rl, thread, "assets/mouse.png") {
-            skins.add_region(IMG_ICON_MOUSE, mouse_tex, 0.0, 0.0, 23.0, 32.0);
-        }
-
-        skins
-    }
-
-    fn try_load_texture(
-        &mut self,
-        rl: &mut RaylibHandle,
-        thread: &RaylibThread,
-        path: &str,
-    ) -> Option<usize> {
-        match rl.load_texture(thread, path) {
-            Ok(texture) => {
-                let next_index = self.textures.len();
-                self.textures.push(texture);
-                Some(next_index)
-            }
+        let atlas = match load_atlas(SKIN_ATLAS_PATH) {
+            Ok(atlas) => atlas,
             Err(err) => {
-                eprintln!("warning: failed to load texture '{}': {}", path, err);
-                None
+                eprintln!(
+                    "warning: failed to load atlas '{}': {}",
+                    SKIN_ATLAS_PATH, err
+                );
+                Atlas::new()
             }
-        }
-    }
-
-    fn add_region(&mut self, image_id: u64, texture_index: usize, x: f32, y: f32, w: f32, h: f32) {
-        self.regions.insert(
-            image_id,
-            SpriteRegion {
-                texture_index,
-                source: Rectangle::new(x, y, w, h),
-            },
-        );
+        };
+        let textures = atlas
+            .textures
+            .iter()
+            .map(|path| match rl.load_texture(thread, path) {
+                Ok(texture) => Some(texture),
+                Err(err) => {
+                    eprintln!("warning: failed to load texture '{}': {}", path, err);
+                    None
+                }
+            })
+            .collect();
+        Self { atlas, textures }
     }
 
-    fn lookup(&self, image_id: u64) -> Option<(&Texture2D, Rectangle)> {
-        let region = self.regions.get(&image_id)?;
-        let texture = self.textures.get(region.texture_index)?;
-        Some((texture, region.source))
+    fn lookup(&self, image: &ImageStyle) -> Option<(&Texture2D, Rectangle)> {
+        let (texture_index, rect) = self.atlas.resolve(image)?;
+        let texture = self.textures.get(texture_index)?.as_ref()?;
+        Some((
+            texture,
+            Rectangle::new(rect.position.x, rect.position.y, rect.size.x, rect.size.y),
+        ))
     }
 }
 
@@ -134,28 +87,24 @@ impl<'a, D: RaylibDraw> SkinRaylibBackend<'a, D> {
         image: ImageStyle,
         rect: Rect,
     ) {
+        let dst_w = rect.size.x.max(0.0);
+        let dst_h = rect.size.y.max(0.0);
+        // on-screen size of the source after rotation
+        let (src_w, src_h) = if image.rotation.swaps_axes() {
+            (source.height.abs().max(1.0), source.width.abs().max(1.0))
+        } else {
+            (source.width.abs().max(1.0), source.height.abs().max(1.0))
+        };
+
         match image.layout {
-            ImageLayout::Stretch => {
-                let destination = Rectangle::new(
-                    rect.position.x,
-                    rect.position.y,
-                    rect.size.x.max(0.0),
-                    rect.size.y.max(0.0),
-                );
-                self.draw.draw_texture_pro(
-                    texture,
-                    source,
-                    destination,
-                    Vector2::new(0.0, 0.0),
-                    0.0,
-                    to_ray_color(image.tint),
-                );
+            // nine-slice images arrive here already split into stretched/tiled slices
+            ImageLayout::Stretch
+            | ImageLayout::NineSlice { .. }
+            | ImageLayout::NineSliceTiledCenter { .. } => {
+                let destination = Rectangle::new(rect.position.x, rect.position.y, dst_w, dst_h);
+                self.draw_quad(texture, source, destination, image);
             }
             ImageLayout::Center => {
-                let src_w = source.width.abs().max(1.0);
-                let src_h = source.height.abs().max(1.0);
-                let dst_w = rect.size.x.max(0.0);
-                let dst_h = rect.size.y.max(0.0);
                 let scale = (dst_w / src_w).min(dst_h / src_h).min(1.0);
                 let draw_w = src_w * scale;
                 let draw_h = src_h * scale;
@@ -165,39 +114,26 @@ impl<'a, D: RaylibDraw> SkinRaylibBackend<'a, D> {
                     draw_w,
                     draw_h,
                 );
-                self.draw.draw_texture_pro(
-                    texture,
-                    source,
-                    destination,
-                    Vector2::new(0.0, 0.0),
-                    0.0,
-                    to_ray_color(image.tint),
-                );
+                self.draw_quad(texture, source, destination, image);
             }
             ImageLayout::Tile => {
-                let tile_w = source.width.abs().max(1.0);
-                let tile_h = source.height.abs().max(1.0);
-                let x_end = rect.position.x + rect.size.x.max(0.0);
-                let y_end = rect.position.y + rect.size.y.max(0.0);
-                let x_dir = if source.width >= 0.0 { 1.0 } else { -1.0 };
-                let y_dir = if source.height >= 0.0 { 1.0 } else { -1.0 };
+                let x_end = rect.position.x + dst_w;
+                let y_end = rect.position.y + dst_h;
 
                 let mut y = rect.position.y;
                 while y < y_end {
-                    let h = (y_end - y).min(tile_h);
+                    let h = (y_end - y).min(src_h);
                     let mut x = rect.position.x;
                     while x < x_end {
-                        let w = (x_end - x).min(tile_w);
-                        let src = Rectangle::new(source.x, source.y, w * x_dir, h * y_dir);
-                        let destination = Rectangle::new(x, y, w, h);
-                        self.draw.draw_texture_pro(
-                            texture,
-                            src,
-                            destination,
-                            Vector2::new(0.0, 0.0),
-                            0.0,
-                            to_ray_color(image.tint),
-                        );
+                        let w = (x_end - x).min(src_w);
+                        // crop partial tiles in unrotated source space
+                        let (crop_w, crop_h) = if image.rotation.swaps_axes() {
+                            (h, w)
+                        } else {
+                            (w, h)
+                        };
+                        let src = Rectangle::new(source.x, source.y, crop_w, crop_h);
+                        self.draw_quad(texture, src, Rectangle::new(x, y, w, h), image);
                         x += w;
                     }
                     y += h;
@@ -205,6 +141,43 @@ impl<'a, D: RaylibDraw> SkinRaylibBackend<'a, D> {
             }
         }
     }
+
+    /// Draw `source` into `destination`, applying the image's flip, rotation and opacity.
+    fn draw_quad(
+        &mut self,
+        texture: &Texture2D,
+        mut source: Rectangle,
+        destination: Rectangle,
+        image: ImageStyle,
+    ) {
+        if image.flip_x {
+            source.width = -source.width;
+        }
+        if image.flip_y {
+            source.height = -source.height;
+        }
+        // raylib rotates around `origin`, so draw the unrotated quad centered on the
+        // destination and rotate it into place
+        let (quad_w, quad_h) = if image.rotation.swaps_axes() {
+            (destination.height, destination.width)
+        } else {
+            (destination.width, destination.height)
+        };
+        let centered = Rectangle::new(
+            destination.x + destination.width * 0.5,
+            destination.y + destination.height * 0.5,
+            quad_w,
+            quad_h,
+        );
+        self.draw.draw_texture_pro(
+            texture,
+            source,
+            centered,
+            Vector2::new(quad_w * 0.5, quad_h * 0.5),
+            image.rotation.degrees(),
+            to_ray_color(image.effective_tint()),
+        );
+    }
 }
 
 impl<D: RaylibDraw> DrawBackend for SkinRaylibBackend<'_, D> {
@@ -218,6 +191,18 @@ impl<D: RaylibDraw> DrawBackend for SkinRaylibBackend<'_, D> {
         );
     }
 
+    fn fill_rect_gradient(&mut self, rect: Rect, corners: [Color; 4]) {
+        let [top_left, top_right, bottom_right, bottom_left] = corners.map(to_ray_color);
+        // raylib takes the corners counter-clockwise from the top-left
+        self.draw.draw_rectangle_gradient_ex(
+            Rectangle::new(rect.position.x, rect.position.y, rect.size.x, rect.size.y),
+            top_left,
+            bottom_left,
+            bottom_right,
+            top_right,
+        );
+    }
+
     fn draw_line(&mut self, start: Vec2, end: Vec2, color: Color, thickness: f32) {
         if thickness <= 1.0 {
             self.draw.draw_line(
@@ -281,12 +266,20 @@ impl<D: RaylibDraw> DrawBackend for SkinRaylibBackend<'_, D> {
     }
 
     fn draw_image(&mut self, image: ImageStyle, rect: Rect) {
-        if let Some((texture, source)) = self.skins.lookup(image.image_id) {
+        if let Some((texture, source)) = self.skins.lookup(&image) {
             self.draw_sprite(texture, source, image, rect);
             return;
         }
         self.fill_rect(rect, Color::rgba(255, 0, 255, 140));
     }
+
+    fn image_size(&self, image_id: u64) -> Option<Vec2> {
+        self.skins.atlas.image_size(image_id)
+    }
+
+    fn measure_text(&self, text: &str, font_size: f32) -> Option<f32> {
+        Some(measure_text(text, font_size.max(1.0) as i32) as f32)
+    }
 }
 
 fn to_ray_color(color: Color) -> RayColor {
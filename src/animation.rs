@@ -0,0 +1,328 @@
+use std::f32::consts::PI;
+
+use glam::Vec2;
+
+use crate::{Color, DrawBackend, FontId, ImageStyle, Rect};
+
+/// Easing curve mapping linear progress `0..=1` to eased progress.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    /// Overshoots the target slightly, then settles.
+    BackOut,
+    /// Springs past the target and oscillates into place.
+    ElasticOut,
+    BounceOut,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::BackOut => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+            Easing::ElasticOut => {
+                if t <= 0.0 || t >= 1.0 {
+                    t
+                } else {
+                    2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            }
+            Easing::BounceOut => {
+                let n1 = 7.5625;
+                let d1 = 2.75;
+                if t < 1.0 / d1 {
+                    n1 * t * t
+                } else if t < 2.0 / d1 {
+                    let t = t - 1.5 / d1;
+                    n1 * t * t + 0.75
+                } else if t < 2.5 / d1 {
+                    let t = t - 2.25 / d1;
+                    n1 * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / d1;
+                    n1 * t * t + 0.984375
+                }
+            }
+        }
+    }
+}
+
+/// Widget property a tween animates, with the value it ends at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TweenTarget {
+    Position(Vec2),
+    Size(Vec2),
+    Tint(Color),
+    Opacity(f32),
+    Scale(f32),
+}
+
+impl TweenTarget {
+    fn lerp(self, end: TweenTarget, t: f32) -> TweenTarget {
+        match (self, end) {
+            (TweenTarget::Position(a), TweenTarget::Position(b)) => {
+                TweenTarget::Position(a.lerp(b, t))
+            }
+            (TweenTarget::Size(a), TweenTarget::Size(b)) => TweenTarget::Size(a.lerp(b, t)),
+            (TweenTarget::Tint(a), TweenTarget::Tint(b)) => TweenTarget::Tint(lerp_color(a, b, t)),
+            (TweenTarget::Opacity(a), TweenTarget::Opacity(b)) => {
+                TweenTarget::Opacity(a + (b - a) * t)
+            }
+            (TweenTarget::Scale(a), TweenTarget::Scale(b)) => TweenTarget::Scale(a + (b - a) * t),
+            _ => end,
+        }
+    }
+}
+
+/// An animation of one property of one element, driven by `Gui::update`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tween {
+    pub element_id: u32,
+    pub target: TweenTarget,
+    /// Seconds from start to target.
+    pub duration: f32,
+    /// Seconds to wait before starting.
+    pub delay: f32,
+    pub easing: Easing,
+    /// Wait for the tween with this id to finish before starting, for sequencing.
+    pub after: Option<u32>,
+}
+
+impl Tween {
+    pub fn new(element_id: u32, target: TweenTarget, duration: f32) -> Self {
+        Self {
+            element_id,
+            target,
+            duration,
+            delay: 0.0,
+            easing: Easing::Linear,
+            after: None,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn after(mut self, tween_id: u32) -> Self {
+        self.after = Some(tween_id);
+        self
+    }
+}
+
+/// A tween registered with a `Gui`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ActiveTween {
+    pub id: u32,
+    pub tween: Tween,
+    /// Value when the tween started, captured once the delay has passed.
+    pub start: Option<TweenTarget>,
+    pub elapsed: f32,
+}
+
+impl ActiveTween {
+    /// Advance by `dt` seconds. Returns the value to apply, if started, and whether the
+    /// tween finished. `current` reads the property's present value.
+    pub(crate) fn advance(
+        &mut self,
+        dt: f32,
+        current: impl FnOnce(TweenTarget) -> Option<TweenTarget>,
+    ) -> (Option<TweenTarget>, bool) {
+        self.elapsed += dt;
+        let running = self.elapsed - self.tween.delay;
+        if running < 0.0 {
+            return (None, false);
+        }
+        if self.start.is_none() {
+            self.start = current(self.tween.target);
+        }
+        let Some(start) = self.start else {
+            // element is gone
+            return (None, true);
+        };
+        let t = if self.tween.duration <= 0.0 {
+            1.0
+        } else {
+            (running / self.tween.duration).min(1.0)
+        };
+        let value = start.lerp(self.tween.target, self.tween.easing.apply(t));
+        (Some(value), t >= 1.0)
+    }
+}
+
+/// Tint, opacity and scale applied to everything an element draws.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ElementVisual {
+    /// Multiplies every color the element draws.
+    pub tint: Color,
+    pub opacity: f32,
+    /// Scale around the element's center.
+    pub scale: f32,
+}
+
+impl Default for ElementVisual {
+    fn default() -> Self {
+        Self {
+            tint: Color::rgb(255, 255, 255),
+            opacity: 1.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl ElementVisual {
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn modulate(&self, color: Color) -> Color {
+        let channel = |value: u8, tint: u8| (value as u16 * tint as u16 / 255) as u8;
+        Color {
+            r: channel(color.r, self.tint.r),
+            g: channel(color.g, self.tint.g),
+            b: channel(color.b, self.tint.b),
+            a: (channel(color.a, self.tint.a) as f32 * self.opacity.clamp(0.0, 1.0)).round() as u8,
+        }
+    }
+}
+
+/// Backend wrapper that applies an `ElementVisual` to every primitive.
+pub(crate) struct VisualBackend<'a> {
+    pub inner: &'a mut dyn DrawBackend,
+    pub visual: ElementVisual,
+    /// Center of the element, the origin for scaling.
+    pub origin: Vec2,
+}
+
+impl VisualBackend<'_> {
+    fn point(&self, point: Vec2) -> Vec2 {
+        self.origin + (point - self.origin) * self.visual.scale
+    }
+
+    fn rect(&self, rect: Rect) -> Rect {
+        Rect::new(self.point(rect.position), rect.size * self.visual.scale)
+    }
+
+    fn image(&self, image: ImageStyle) -> ImageStyle {
+        ImageStyle {
+            tint: self.visual.modulate(image.tint),
+            ..image
+        }
+    }
+}
+
+impl DrawBackend for VisualBackend<'_> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let (rect, color) = (self.rect(rect), self.visual.modulate(color));
+        self.inner.fill_rect(rect, color);
+    }
+
+    fn draw_line(&mut self, start: Vec2, end: Vec2, color: Color, thickness: f32) {
+        let (start, end) = (self.point(start), self.point(end));
+        let color = self.visual.modulate(color);
+        self.inner
+            .draw_line(start, end, color, thickness * self.visual.scale);
+    }
+
+    fn draw_text(&mut self, text: &str, position: Vec2, font_size: f32, color: Color) {
+        let (position, color) = (self.point(position), self.visual.modulate(color));
+        self.inner
+            .draw_text(text, position, font_size * self.visual.scale, color);
+    }
+
+    fn push_clip_rect(&mut self, rect: Rect) {
+        let rect = self.rect(rect);
+        self.inner.push_clip_rect(rect);
+    }
+
+    fn pop_clip_rect(&mut self) {
+        self.inner.pop_clip_rect();
+    }
+
+    fn draw_image(&mut self, image: ImageStyle, rect: Rect) {
+        let (image, rect) = (self.image(image), self.rect(rect));
+        self.inner.draw_image(image, rect);
+    }
+
+    fn image_size(&self, image_id: u64) -> Option<Vec2> {
+        self.inner.image_size(image_id)
+    }
+
+    fn draw_nine_slice(&mut self, image: ImageStyle, rect: Rect) {
+        let (image, rect) = (self.image(image), self.rect(rect));
+        self.inner.draw_nine_slice(image, rect);
+    }
+
+    fn fill_rect_gradient(&mut self, rect: Rect, corners: [Color; 4]) {
+        let (rect, corners) = (self.rect(rect), corners.map(|c| self.visual.modulate(c)));
+        self.inner.fill_rect_gradient(rect, corners);
+    }
+
+    // layout happens in unscaled element space, so measurements pass through
+    fn measure_text(&self, text: &str, font_size: f32) -> Option<f32> {
+        self.inner.measure_text(text, font_size)
+    }
+
+    fn draw_text_with_font(
+        &mut self,
+        text: &str,
+        position: Vec2,
+        font: FontId,
+        font_size: f32,
+        color: Color,
+    ) {
+        let (position, color) = (self.point(position), self.visual.modulate(color));
+        self.inner
+            .draw_text_with_font(text, position, font, font_size * self.visual.scale, color);
+    }
+
+    fn measure_text_with_font(&self, text: &str, font: FontId, font_size: f32) -> Option<f32> {
+        self.inner.measure_text_with_font(text, font, font_size)
+    }
+}
+
+pub(crate) fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color {
+        r: channel(a.r, b.r),
+        g: channel(a.g, b.g),
+        b: channel(a.b, b.b),
+        a: channel(a.a, b.a),
+    }
+}
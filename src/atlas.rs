@@ -0,0 +1,513 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use glam::Vec2;
+
+use crate::{ImageStyle, Rect};
+
+/// A named region of one atlas texture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasRegion {
+    pub name: String,
+    /// Index into `Atlas::textures`.
+    pub texture_index: usize,
+    /// Region in texture pixels.
+    pub rect: Rect,
+}
+
+/// Maps `ImageStyle::image_id`s to regions of textures.
+///
+/// The atlas only stores texture paths. Backends load `textures[i]` however they like
+/// and use `resolve` to turn an `ImageStyle` into a texture index and pixel region, so
+/// every backend reads the same sprites from the same places.
+#[derive(Clone, Debug, Default)]
+pub struct Atlas {
+    pub textures: Vec<String>,
+    regions: HashMap<u64, AtlasRegion>,
+    names: HashMap<String, u64>,
+}
+
+impl Atlas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a texture path and return its index.
+    pub fn add_texture(&mut self, path: &str) -> usize {
+        self.textures.push(path.to_string());
+        self.textures.len() - 1
+    }
+
+    /// Add or replace the region for `image_id`. Names are unique, so a different image
+    /// already registered under `name` loses its region. Returns `false` and adds nothing
+    /// if `name` couldn't be read back from an atlas file, see `is_valid_region_name`.
+    pub fn add_region(
+        &mut self,
+        image_id: u64,
+        name: &str,
+        texture_index: usize,
+        rect: Rect,
+    ) -> bool {
+        if !is_valid_region_name(name) {
+            return false;
+        }
+        if let Some(other_id) = self.image_id(name).filter(|other_id| *other_id != image_id) {
+            self.remove_region(other_id);
+        }
+        if let Some(old) = self.regions.insert(
+            image_id,
+            AtlasRegion {
+                name: name.to_string(),
+                texture_index,
+                rect,
+            },
+        ) {
+            self.names.remove(&old.name);
+        }
+        self.names.insert(name.to_string(), image_id);
+        true
+    }
+
+    pub fn remove_region(&mut self, image_id: u64) -> Option<AtlasRegion> {
+        let region = self.regions.remove(&image_id)?;
+        self.names.remove(&region.name);
+        Some(region)
+    }
+
+    pub fn region(&self, image_id: u64) -> Option<&AtlasRegion> {
+        self.regions.get(&image_id)
+    }
+
+    /// Image id registered under `name`.
+    pub fn image_id(&self, name: &str) -> Option<u64> {
+        self.names.get(name).copied()
+    }
+
+    /// All `(image_id, region)` pairs, sorted by image id.
+    pub fn regions(&self) -> Vec<(u64, &AtlasRegion)> {
+        let mut regions: Vec<(u64, &AtlasRegion)> = self
+            .regions
+            .iter()
+            .map(|(id, region)| (*id, region))
+            .collect();
+        regions.sort_by_key(|(id, _)| *id);
+        regions
+    }
+
+    /// Pixel size of an image's region, for `DrawBackend::image_size`.
+    pub fn image_size(&self, image_id: u64) -> Option<Vec2> {
+        self.region(image_id).map(|region| region.rect.size)
+    }
+
+    /// Texture index and texture-pixel rect to draw for `image`. `ImageStyle::source`
+    /// is relative to the image's region.
+    pub fn resolve(&self, image: &ImageStyle) -> Option<(usize, Rect)> {
+        let region = self.region(image.image_id)?;
+        let rect = match image
+            .source
+            .and_then(|source| source.to_pixels(Some(region.rect.size)))
+        {
+            Some(source) => Rect::new(region.rect.position + source.position, source.size),
+            None => region.rect,
+        };
+        Some((region.texture_index, rect))
+    }
+
+    /// Pack `sprites` (image id, name, size) into a new texture at `path` and register
+    /// their regions. Returns the packed texture size, or `None` if a sprite is wider
+    /// than `max_width` or a name is invalid.
+    pub fn add_packed_texture(
+        &mut self,
+        path: &str,
+        sprites: &[(u64, &str, Vec2)],
+        max_width: f32,
+        padding: f32,
+    ) -> Option<Vec2> {
+        if !sprites
+            .iter()
+            .all(|(_, name, _)| is_valid_region_name(name))
+        {
+            return None;
+        }
+        let sizes: Vec<Vec2> = sprites.iter().map(|(_, _, size)| *size).collect();
+        let packing = pack_rects(&sizes, max_width, padding)?;
+        let texture_index = self.add_texture(path);
+        for ((image_id, name, _), rect) in sprites.iter().zip(packing.rects) {
+            self.add_region(*image_id, name, texture_index, rect);
+        }
+        Some(packing.size)
+    }
+}
+
+/// Whether `name` survives a trip through an atlas file: not empty, no surrounding
+/// whitespace, no leading `[`, and no `=`, `#` or line breaks.
+pub fn is_valid_region_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.trim() == name
+        && !name.starts_with('[')
+        && !name.contains(['=', '#', '\n', '\r'])
+}
+
+/// Result of `pack_rects`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasPacking {
+    /// One rect per input size, in input order.
+    pub rects: Vec<Rect>,
+    /// Smallest texture size containing every rect.
+    pub size: Vec2,
+}
+
+/// Shelf-pack `sizes` into a texture at most `max_width` wide, leaving `padding` pixels
+/// between rects. Taller rects are placed first. Returns `None` if a rect doesn't fit
+/// the width.
+pub fn pack_rects(sizes: &[Vec2], max_width: f32, padding: f32) -> Option<AtlasPacking> {
+    if sizes.iter().any(|size| size.x > max_width) {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|a, b| sizes[*b].y.total_cmp(&sizes[*a].y));
+
+    let mut rects = vec![Rect::new(Vec2::ZERO, Vec2::ZERO); sizes.len()];
+    let mut cursor = Vec2::ZERO;
+    let mut shelf_height = 0.0_f32;
+    let mut packed_size = Vec2::ZERO;
+    for index in order {
+        let size = sizes[index];
+        if cursor.x > 0.0 && cursor.x + size.x > max_width {
+            cursor.x = 0.0;
+            cursor.y += shelf_height + padding;
+            shelf_height = 0.0;
+        }
+        rects[index] = Rect::new(cursor, size);
+        packed_size = packed_size.max(cursor + size);
+        shelf_height = shelf_height.max(size.y);
+        cursor.x += size.x + padding;
+    }
+
+    Some(AtlasPacking {
+        rects,
+        size: packed_size,
+    })
+}
+
+/// Error in atlas file text, with the 1-based line it was found on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AtlasParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for AtlasParseError {}
+
+#[derive(Debug)]
+pub enum AtlasFileError {
+    Io(io::Error),
+    Parse(AtlasParseError),
+}
+
+impl fmt::Display for AtlasFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AtlasFileError::Io(err) => write!(f, "atlas file io error: {err}"),
+            AtlasFileError::Parse(err) => write!(f, "atlas file parse error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AtlasFileError {}
+
+impl From<io::Error> for AtlasFileError {
+    fn from(err: io::Error) -> Self {
+        AtlasFileError::Io(err)
+    }
+}
+
+impl From<AtlasParseError> for AtlasFileError {
+    fn from(err: AtlasParseError) -> Self {
+        AtlasFileError::Parse(err)
+    }
+}
+
+/// Parse atlas file text.
+///
+/// `[texture path]` starts a texture section. Each `name = image_id x y width height`
+/// line in it adds a region of that texture, in pixels. `#` starts a comment at the
+/// beginning of a line or after whitespace, so texture paths like `ui#2.png` work.
+///
+/// ```text
+/// [texture assets/ui.png]
+/// button_normal = 8 0 0 64 24
+/// gold_arrow = 6 64 0 28 17   # pointing right
+/// ```
+pub fn parse_atlas(text: &str) -> Result<Atlas, AtlasParseError> {
+    let mut atlas = Atlas::new();
+    let mut texture_index = None;
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |message: String| AtlasParseError {
+            line: line_number,
+            message,
+        };
+
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header
+                .strip_suffix(']')
+                .ok_or_else(|| error("section header is missing ']'".to_string()))?;
+            let path = header
+                .trim()
+                .split_once(char::is_whitespace)
+                .filter(|(kind, _)| *kind == "texture")
+                .map(|(_, path)| path.trim())
+                .filter(|path| !path.is_empty())
+                .ok_or_else(|| error(format!("unknown section '[{header}]'")))?;
+            texture_index = Some(atlas.add_texture(path));
+            continue;
+        }
+
+        let texture_index = texture_index
+            .ok_or_else(|| error("region before any [texture ...] section".to_string()))?;
+        let (name, value) = line
+            .split_once('=')
+            .ok_or_else(|| error(format!("expected 'name = id x y w h', found '{line}'")))?;
+        let name = name.trim();
+        let parts: Vec<&str> = value.split_whitespace().collect();
+        let [id, x, y, w, h] = parts[..] else {
+            return Err(error(format!(
+                "expected 'id x y w h' for '{name}', found '{}'",
+                value.trim()
+            )));
+        };
+        let image_id: u64 = id
+            .parse()
+            .map_err(|_| error(format!("invalid image id '{id}'")))?;
+        let number = |value: &str| {
+            value
+                .parse::<f32>()
+                .map_err(|_| error(format!("invalid number '{value}'")))
+        };
+        let rect = Rect::new(
+            Vec2::new(number(x)?, number(y)?),
+            Vec2::new(number(w)?, number(h)?),
+        );
+        if atlas.region(image_id).is_some() {
+            return Err(error(format!("image id {image_id} is defined twice")));
+        }
+        if atlas.image_id(name).is_some() {
+            return Err(error(format!("name '{name}' is defined twice")));
+        }
+        if !atlas.add_region(image_id, name, texture_index, rect) {
+            return Err(error(format!("invalid region name '{name}'")));
+        }
+    }
+
+    Ok(atlas)
+}
+
+/// Drop a `#` comment. Like theme files, a `#` only starts one at the beginning of the
+/// line or after whitespace.
+fn strip_comment(line: &str) -> &str {
+    for (i, ch) in line.char_indices() {
+        if ch == '#' && (i == 0 || line[..i].ends_with(char::is_whitespace)) {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+/// Format an atlas in the `parse_atlas` format.
+pub fn atlas_to_string(atlas: &Atlas) -> String {
+    let mut out = String::new();
+    for (texture_index, path) in atlas.textures.iter().enumerate() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("[texture {path}]\n"));
+        for (image_id, region) in atlas.regions() {
+            if region.texture_index != texture_index {
+                continue;
+            }
+            let rect = region.rect;
+            out.push_str(&format!(
+                "{} = {} {} {} {} {}\n",
+                region.name, image_id, rect.position.x, rect.position.y, rect.size.x, rect.size.y
+            ));
+        }
+    }
+    out
+}
+
+pub fn load_atlas(path: impl AsRef<Path>) -> Result<Atlas, AtlasFileError> {
+    let text = fs::read_to_string(path)?;
+    Ok(parse_atlas(&text)?)
+}
+
+pub fn save_atlas(path: impl AsRef<Path>, atlas: &Atlas) -> io::Result<()> {
+    fs::write(path, atlas_to_string(atlas))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> Rect {
+        Rect::new(Vec2::new(x, y), Vec2::new(w, h))
+    }
+
+    #[test]
+    fn parses_textures_regions_and_comments() {
+        let atlas = parse_atlas(
+            "# skins\n[texture ui.png]\nbutton = 8 0 0 64 24\narrow = 6 64 0 28 17 # right\n\n[texture icons.png]\nheart = 100 0 0 16 16\n",
+        )
+        .unwrap();
+        assert_eq!(atlas.textures, ["ui.png", "icons.png"]);
+        assert_eq!(atlas.image_id("arrow"), Some(6));
+        let heart = atlas.region(100).unwrap();
+        assert_eq!(heart.texture_index, 1);
+        assert_eq!(heart.rect, rect(0.0, 0.0, 16.0, 16.0));
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let text =
+            "[texture a.png]\nb = 1 0 0 8 8\nc = 2 8 0 4.5 8\n\n[texture d.png]\ne = 3 0 0 1 1\n";
+        let atlas = parse_atlas(text).unwrap();
+        assert_eq!(atlas_to_string(&atlas), text);
+        assert_eq!(
+            parse_atlas(&atlas_to_string(&atlas)).unwrap().regions(),
+            atlas.regions()
+        );
+    }
+
+    #[test]
+    fn errors_report_their_line() {
+        let cases = [
+            ("a = 1 0 0 8 8", 1),
+            ("[texture a.png]\na = 1 0 0 8", 2),
+            ("[texture a.png]\na 1 0 0 8 8", 2),
+            ("[texture a.png]\na = x 0 0 8 8", 2),
+            ("[texture a.png]\na = 1 0 0 8 tall", 2),
+            ("[texture a.png\n", 1),
+            ("[sprites a.png]", 1),
+            ("[texture]", 1),
+            ("[textures a.png]", 1),
+            ("[texturea.png]", 1),
+            ("[texture a.png]\na = 1 0 0 8 8\nb = 1 8 0 8 8", 3),
+            ("[texture a.png]\na = 1 0 0 8 8\na = 2 8 0 8 8", 3),
+            ("[texture a.png]\n = 1 0 0 8 8", 2),
+            ("[texture a.png]\na#b = 1 0 0 8 8", 2),
+        ];
+        for (text, line) in cases {
+            let err = parse_atlas(text).unwrap_err();
+            assert_eq!(err.line, line, "{text:?}: {err}");
+        }
+    }
+
+    #[test]
+    fn hashes_inside_paths_are_not_comments() {
+        let mut atlas = Atlas::new();
+        let texture = atlas.add_texture("ui#2.png");
+        assert!(atlas.add_region(1, "big icon", texture, rect(0.0, 0.0, 8.0, 8.0)));
+        let text = atlas_to_string(&atlas);
+        assert_eq!(text, "[texture ui#2.png]\nbig icon = 1 0 0 8 8\n");
+        let parsed = parse_atlas(&format!("{text}# end\n")).unwrap();
+        assert_eq!(parsed.textures, ["ui#2.png"]);
+        assert_eq!(parsed.regions(), atlas.regions());
+    }
+
+    #[test]
+    fn names_that_would_not_round_trip_are_rejected() {
+        let mut atlas = Atlas::new();
+        let texture = atlas.add_texture("a.png");
+        for name in ["", " a", "a ", "[a", "a=b", "=a", "a#b", "#a", "a\nb"] {
+            assert!(
+                !atlas.add_region(1, name, texture, rect(0.0, 0.0, 8.0, 8.0)),
+                "{name:?}"
+            );
+        }
+        assert!(atlas.regions().is_empty());
+        assert_eq!(
+            atlas.add_packed_texture("b.png", &[(1, "a=b", Vec2::ONE)], 64.0, 0.0),
+            None
+        );
+        assert_eq!(atlas.textures, ["a.png"]);
+    }
+
+    #[test]
+    fn reusing_a_name_replaces_the_other_region() {
+        let mut atlas = Atlas::new();
+        let texture = atlas.add_texture("a.png");
+        atlas.add_region(1, "shared", texture, rect(0.0, 0.0, 8.0, 8.0));
+        atlas.add_region(2, "shared", texture, rect(8.0, 0.0, 8.0, 8.0));
+        assert!(atlas.region(1).is_none());
+        assert_eq!(atlas.image_id("shared"), Some(2));
+        assert!(atlas.remove_region(1).is_none());
+        assert_eq!(atlas.image_id("shared"), Some(2));
+    }
+
+    #[test]
+    fn renaming_a_region_frees_its_old_name() {
+        let mut atlas = Atlas::new();
+        let texture = atlas.add_texture("a.png");
+        atlas.add_region(1, "old", texture, rect(0.0, 0.0, 8.0, 8.0));
+        atlas.add_region(1, "new", texture, rect(0.0, 0.0, 8.0, 8.0));
+        assert_eq!(atlas.image_id("old"), None);
+        assert_eq!(atlas.image_id("new"), Some(1));
+    }
+
+    #[test]
+    fn packs_tallest_first_into_shelves() {
+        let sizes = [
+            Vec2::new(10.0, 5.0),
+            Vec2::new(10.0, 20.0),
+            Vec2::new(15.0, 10.0),
+        ];
+        let packing = pack_rects(&sizes, 30.0, 1.0).unwrap();
+        assert_eq!(packing.rects[1], rect(0.0, 0.0, 10.0, 20.0));
+        assert_eq!(packing.rects[2], rect(11.0, 0.0, 15.0, 10.0));
+        assert_eq!(packing.rects[0], rect(0.0, 21.0, 10.0, 5.0));
+        assert_eq!(packing.size, Vec2::new(26.0, 26.0));
+    }
+
+    #[test]
+    fn packed_rects_never_overlap() {
+        let sizes: Vec<Vec2> = (1..20)
+            .map(|i| Vec2::new((i * 7 % 23 + 1) as f32, (i * 5 % 17 + 1) as f32))
+            .collect();
+        let packing = pack_rects(&sizes, 40.0, 2.0).unwrap();
+        for (i, a) in packing.rects.iter().enumerate() {
+            assert_eq!(a.size, sizes[i]);
+            assert!(a.position.x + a.size.x <= 40.0);
+            for b in &packing.rects[i + 1..] {
+                let apart = a.position.x + a.size.x <= b.position.x
+                    || b.position.x + b.size.x <= a.position.x
+                    || a.position.y + a.size.y <= b.position.y
+                    || b.position.y + b.size.y <= a.position.y;
+                assert!(apart, "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn packing_rejects_rects_wider_than_the_texture() {
+        assert_eq!(pack_rects(&[Vec2::new(41.0, 1.0)], 40.0, 0.0), None);
+        let empty = pack_rects(&[], 40.0, 0.0).unwrap();
+        assert!(empty.rects.is_empty());
+        assert_eq!(empty.size, Vec2::ZERO);
+    }
+}
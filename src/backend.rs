@@ -1,5 +1,7 @@
 use glam::Vec2;
 
+use crate::animation::lerp_color;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Color {
     pub r: u8,
@@ -17,6 +19,69 @@ impl Color {
         Self { r, g, b, a: 255 }
     }
 
+    /// Parse `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` (leading `#` optional).
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+        let nibble = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+        let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        match hex.len() {
+            3 => Some(Self::rgb(nibble(0)?, nibble(1)?, nibble(2)?)),
+            4 => Some(Self::rgba(nibble(0)?, nibble(1)?, nibble(2)?, nibble(3)?)),
+            6 => Some(Self::rgb(byte(0)?, byte(2)?, byte(4)?)),
+            8 => Some(Self::rgba(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+            _ => None,
+        }
+    }
+
+    /// Format as `#rrggbb`, or `#rrggbbaa` when not fully opaque.
+    pub fn to_hex(self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    /// Opaque color from hue in degrees and saturation/value in `0..=1`.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let chroma = value.clamp(0.0, 1.0) * saturation.clamp(0.0, 1.0);
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let offset = value.clamp(0.0, 1.0) - chroma;
+        let channel = |c: f32| ((c + offset) * 255.0).round() as u8;
+        Self::rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// `(hue, saturation, value)` with hue in degrees and the rest in `0..=1`. Alpha is
+    /// ignored. Grays have hue `0.0`.
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let [r, g, b] = [self.r, self.g, self.b].map(|c| c as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let chroma = max - r.min(g).min(b);
+        let hue = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+        (hue, saturation, max)
+    }
+
     pub fn scaled(self, factor: f32) -> Self {
         let clamp = |value: f32| -> u8 { value.clamp(0.0, 255.0) as u8 };
         Self {
@@ -28,7 +93,7 @@ impl Color {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Rect {
     pub position: Vec2,
     pub size: Vec2,
@@ -38,50 +103,202 @@ impl Rect {
     pub fn new(position: Vec2, size: Vec2) -> Self {
         Self { position, size }
     }
+
+    /// Strict containment, matching how widgets hit-test the mouse.
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x > self.position.x
+            && point.x < self.position.x + self.size.x
+            && point.y > self.position.y
+            && point.y < self.position.y + self.size.y
+    }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ImageLayout {
     Stretch,
     Tile,
     Center,
+    /// Corners keep their size, edges stretch along one axis and the center stretches.
+    /// Insets are in source image pixels.
+    NineSlice {
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+    },
+    /// Like `NineSlice`, but the center is tiled instead of stretched.
+    NineSliceTiledCenter {
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+    },
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Region of an image to draw.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageSource {
+    /// Region in image pixels.
+    Pixels(Rect),
+    /// Region in normalized `0..1` image coordinates.
+    Uv(Rect),
+}
+
+impl ImageSource {
+    /// Region in pixels, using `image_size` to convert UVs.
+    pub fn to_pixels(self, image_size: Option<Vec2>) -> Option<Rect> {
+        match self {
+            ImageSource::Pixels(rect) => Some(rect),
+            ImageSource::Uv(rect) => {
+                let size = image_size?;
+                Some(Rect::new(rect.position * size, rect.size * size))
+            }
+        }
+    }
+}
+
+/// Clockwise rotation applied to an image after flipping.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ImageRotation {
+    #[default]
+    None,
+    Cw90,
+    Cw180,
+    Cw270,
+}
+
+impl ImageRotation {
+    pub fn degrees(self) -> f32 {
+        match self {
+            ImageRotation::None => 0.0,
+            ImageRotation::Cw90 => 90.0,
+            ImageRotation::Cw180 => 180.0,
+            ImageRotation::Cw270 => 270.0,
+        }
+    }
+
+    /// Whether the image's width and height trade places.
+    pub fn swaps_axes(self) -> bool {
+        matches!(self, ImageRotation::Cw90 | ImageRotation::Cw270)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct ImageStyle {
     pub image_id: u64,
     pub layout: ImageLayout,
     pub tint: Color,
     pub draw_over_content: bool,
+    /// Region of the image to draw. `None` draws the whole image.
+    pub source: Option<ImageSource>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub rotation: ImageRotation,
+    /// Multiplies the tint alpha, `0.0..=1.0`.
+    pub opacity: f32,
 }
 
 impl ImageStyle {
     pub fn stretched(image_id: u64) -> Self {
-        Self {
-            image_id,
-            layout: ImageLayout::Stretch,
-            tint: Color::rgb(255, 255, 255),
-            draw_over_content: false,
-        }
+        Self::with_layout(image_id, ImageLayout::Stretch)
     }
 
     pub fn tiled(image_id: u64) -> Self {
+        Self::with_layout(image_id, ImageLayout::Tile)
+    }
+
+    pub fn centered(image_id: u64) -> Self {
+        Self::with_layout(image_id, ImageLayout::Center)
+    }
+
+    pub fn nine_slice(image_id: u64, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self::with_layout(
+            image_id,
+            ImageLayout::NineSlice {
+                left,
+                top,
+                right,
+                bottom,
+            },
+        )
+    }
+
+    pub fn with_layout(image_id: u64, layout: ImageLayout) -> Self {
         Self {
             image_id,
-            layout: ImageLayout::Tile,
+            layout,
             tint: Color::rgb(255, 255, 255),
             draw_over_content: false,
+            source: None,
+            flip_x: false,
+            flip_y: false,
+            rotation: ImageRotation::None,
+            opacity: 1.0,
         }
     }
 
-    pub fn centered(image_id: u64) -> Self {
+    /// `tint` with its alpha multiplied by `opacity`. Backends should draw with this.
+    pub fn effective_tint(&self) -> Color {
+        Color {
+            a: (self.tint.a as f32 * self.opacity.clamp(0.0, 1.0)).round() as u8,
+            ..self.tint
+        }
+    }
+}
+
+/// Interaction state used to pick a `StateImages` image.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WidgetState {
+    pub hovered: bool,
+    pub pressed: bool,
+    pub focused: bool,
+    pub disabled: bool,
+}
+
+/// One image per widget state. Missing states fall back to `normal`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StateImages {
+    pub normal: ImageStyle,
+    pub hovered: Option<ImageStyle>,
+    pub pressed: Option<ImageStyle>,
+    pub focused: Option<ImageStyle>,
+    pub disabled: Option<ImageStyle>,
+}
+
+impl StateImages {
+    pub fn new(normal: ImageStyle) -> Self {
         Self {
-            image_id,
-            layout: ImageLayout::Center,
-            tint: Color::rgb(255, 255, 255),
-            draw_over_content: false,
+            normal,
+            hovered: None,
+            pressed: None,
+            focused: None,
+            disabled: None,
         }
     }
+
+    /// Image for `state`, checking disabled, then pressed, hovered and focused.
+    pub fn pick(&self, state: WidgetState) -> ImageStyle {
+        let candidates = [
+            (state.disabled, self.disabled),
+            (state.pressed, self.pressed),
+            (state.hovered, self.hovered),
+            (state.focused, self.focused),
+        ];
+        candidates
+            .into_iter()
+            .find_map(|(active, image)| if active { image } else { None })
+            .unwrap_or(self.normal)
+    }
+}
+
+/// Backend-defined font handle key. Backends map ids to their loaded fonts the same
+/// way they map `ImageStyle::image_id` to textures.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FontId(pub u64);
+
+impl FontId {
+    /// The backend's built-in font.
+    pub const DEFAULT: FontId = FontId(0);
 }
 
 pub trait DrawBackend {
@@ -93,4 +310,293 @@ pub trait DrawBackend {
     fn pop_clip_rect(&mut self) {}
 
     fn draw_image(&mut self, _image: ImageStyle, _rect: Rect) {}
+
+    /// Pixel size of an image. Used to slice images that have no `source` region.
+    fn image_size(&self, _image_id: u64) -> Option<Vec2> {
+        None
+    }
+
+    /// Draw an image with a `NineSlice` or `NineSliceTiledCenter` layout. Defaults to
+    /// `draw_nine_slice_parts`, which issues one `draw_image` per slice.
+    fn draw_nine_slice(&mut self, image: ImageStyle, rect: Rect) {
+        draw_nine_slice_parts(self, image, rect);
+    }
+
+    /// Fill a rect blending between corner colors, given clockwise from the top-left.
+    /// Defaults to `fill_gradient_parts`, which approximates it with flat cells.
+    fn fill_rect_gradient(&mut self, rect: Rect, corners: [Color; 4]) {
+        fill_gradient_parts(self, rect, corners);
+    }
+
+    /// Width in pixels of `text` rendered as a single line, or `None` if the backend
+    /// can't measure text. Themes fall back to `estimate_text_width` in that case.
+    fn measure_text(&self, _text: &str, _font_size: f32) -> Option<f32> {
+        None
+    }
+
+    /// Draw text with a specific font. Defaults to `draw_text`, ignoring the font.
+    fn draw_text_with_font(
+        &mut self,
+        text: &str,
+        position: Vec2,
+        _font: FontId,
+        font_size: f32,
+        color: Color,
+    ) {
+        self.draw_text(text, position, font_size, color);
+    }
+
+    /// Measure text with a specific font. Defaults to `measure_text`, ignoring the font.
+    fn measure_text_with_font(&self, text: &str, _font: FontId, font_size: f32) -> Option<f32> {
+        self.measure_text(text, font_size)
+    }
+}
+
+/// Draw an image, routing nine-slice layouts through `DrawBackend::draw_nine_slice`.
+/// Themes draw every `ImageStyle` through this.
+pub fn draw_styled_image<B: DrawBackend + ?Sized>(backend: &mut B, image: ImageStyle, rect: Rect) {
+    match image.layout {
+        ImageLayout::NineSlice { .. } | ImageLayout::NineSliceTiledCenter { .. } => {
+            backend.draw_nine_slice(image, rect)
+        }
+        _ => backend.draw_image(image, rect),
+    }
+}
+
+/// Split a nine-slice image into up to nine `draw_image` calls with pixel `source`
+/// sub-regions. Corners shrink proportionally when `rect` is smaller than the insets.
+/// Flips and 180-degree rotation mirror the slice placement; 90/270-degree rotation
+/// isn't supported for nine-slice and is ignored. Without a pixel `source` or a known
+/// `image_size`, the image is drawn stretched instead.
+pub fn draw_nine_slice_parts<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    image: ImageStyle,
+    rect: Rect,
+) {
+    let (left, top, right, bottom, tiled_center) = match image.layout {
+        ImageLayout::NineSlice {
+            left,
+            top,
+            right,
+            bottom,
+        } => (left, top, right, bottom, false),
+        ImageLayout::NineSliceTiledCenter {
+            left,
+            top,
+            right,
+            bottom,
+        } => (left, top, right, bottom, true),
+        _ => {
+            backend.draw_image(image, rect);
+            return;
+        }
+    };
+
+    let image_size = backend.image_size(image.image_id);
+    let source = match image.source {
+        Some(source) => source.to_pixels(image_size),
+        None => image_size.map(|size| Rect::new(Vec2::ZERO, size)),
+    };
+    let Some(source) = source else {
+        let stretched = ImageStyle {
+            layout: ImageLayout::Stretch,
+            ..image
+        };
+        backend.draw_image(stretched, rect);
+        return;
+    };
+
+    let rotation = if image.rotation.swaps_axes() {
+        ImageRotation::None
+    } else {
+        image.rotation
+    };
+    let half_turn = rotation == ImageRotation::Cw180;
+    let scale_x = (rect.size.x / (left + right)).min(1.0);
+    let scale_y = (rect.size.y / (top + bottom)).min(1.0);
+    let columns = slice_spans(
+        (source.position.x, source.size.x),
+        (rect.position.x, rect.size.x),
+        (left, right),
+        scale_x,
+        image.flip_x != half_turn,
+    );
+    let rows = slice_spans(
+        (source.position.y, source.size.y),
+        (rect.position.y, rect.size.y),
+        (top, bottom),
+        scale_y,
+        image.flip_y != half_turn,
+    );
+
+    for (row, (src_y, src_h, dst_y, dst_h)) in rows.into_iter().enumerate() {
+        for (column, (src_x, src_w, dst_x, dst_w)) in columns.into_iter().enumerate() {
+            if src_w <= 0.0 || src_h <= 0.0 || dst_w <= 0.0 || dst_h <= 0.0 {
+                continue;
+            }
+            let is_center = row == 1 && column == 1;
+            let slice = ImageStyle {
+                layout: if is_center && tiled_center {
+                    ImageLayout::Tile
+                } else {
+                    ImageLayout::Stretch
+                },
+                source: Some(ImageSource::Pixels(Rect::new(
+                    Vec2::new(src_x, src_y),
+                    Vec2::new(src_w, src_h),
+                ))),
+                rotation,
+                ..image
+            };
+            backend.draw_image(
+                slice,
+                Rect::new(Vec2::new(dst_x, dst_y), Vec2::new(dst_w, dst_h)),
+            );
+        }
+    }
+}
+
+/// Approximate a four-corner gradient with a grid of `fill_rect` calls, each the
+/// blended color at its center. Gradients along one axis use strips along the other.
+pub fn fill_gradient_parts<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    rect: Rect,
+    corners: [Color; 4],
+) {
+    const CELLS: usize = 16;
+    let [top_left, top_right, bottom_right, bottom_left] = corners;
+    let columns = if top_left == top_right && bottom_left == bottom_right {
+        1
+    } else {
+        CELLS
+    };
+    let rows = if top_left == bottom_left && top_right == bottom_right {
+        1
+    } else {
+        CELLS
+    };
+    let cell = rect.size / Vec2::new(columns as f32, rows as f32);
+    for row in 0..rows {
+        for column in 0..columns {
+            let t = (Vec2::new(column as f32, row as f32) + 0.5)
+                / Vec2::new(columns as f32, rows as f32);
+            let top = lerp_color(top_left, top_right, t.x);
+            let bottom = lerp_color(bottom_left, bottom_right, t.x);
+            let position = rect.position + cell * Vec2::new(column as f32, row as f32);
+            backend.fill_rect(Rect::new(position, cell), lerp_color(top, bottom, t.y));
+        }
+    }
+}
+
+/// `(source start, source length, destination start, destination length)` for the
+/// three source slices along one axis. When `mirrored`, the first source slice is
+/// placed at the far end of the destination.
+fn slice_spans(
+    (src_start, src_len): (f32, f32),
+    (dst_start, dst_len): (f32, f32),
+    (start_inset, end_inset): (f32, f32),
+    scale: f32,
+    mirrored: bool,
+) -> [(f32, f32, f32, f32); 3] {
+    let sources = [
+        (src_start, start_inset),
+        (src_start + start_inset, src_len - start_inset - end_inset),
+        (src_start + src_len - end_inset, end_inset),
+    ];
+    let (first_len, last_len) = if mirrored {
+        (end_inset * scale, start_inset * scale)
+    } else {
+        (start_inset * scale, end_inset * scale)
+    };
+    let mut destinations = [
+        (dst_start, first_len),
+        (dst_start + first_len, dst_len - first_len - last_len),
+        (dst_start + dst_len - last_len, last_len),
+    ];
+    if mirrored {
+        destinations.reverse();
+    }
+    [0, 1, 2].map(|i| {
+        (
+            sources[i].0,
+            sources[i].1,
+            destinations[i].0,
+            destinations[i].1,
+        )
+    })
+}
+
+/// Rough single-line text width used when a backend provides no text metrics.
+pub fn estimate_text_width(text: &str, font_size: f32) -> f32 {
+    text.chars().count() as f32 * font_size * 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_hex_form() {
+        let cases = [
+            ("#f80", Color::rgb(0xff, 0x88, 0x00)),
+            ("f80", Color::rgb(0xff, 0x88, 0x00)),
+            ("#abcd", Color::rgba(0xaa, 0xbb, 0xcc, 0xdd)),
+            ("#12Ab9f", Color::rgb(0x12, 0xab, 0x9f)),
+            ("#12ab9f80", Color::rgba(0x12, 0xab, 0x9f, 0x80)),
+        ];
+        for (text, color) in cases {
+            assert_eq!(Color::from_hex(text), Some(color), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_hex() {
+        for text in [
+            "",
+            "#",
+            "#ab",
+            "#abcde",
+            "#abcdefa",
+            "#abcdef012",
+            "#ggg",
+            "#12 456",
+            "##123",
+            "#+12",
+            "#éa",
+        ] {
+            assert_eq!(Color::from_hex(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        for color in [
+            Color::rgb(0, 0, 0),
+            Color::rgb(0x12, 0xab, 0x9f),
+            Color::rgba(0xff, 0xff, 0xff, 0),
+            Color::rgba(1, 2, 3, 254),
+        ] {
+            assert_eq!(Color::from_hex(&color.to_hex()), Some(color));
+        }
+        assert_eq!(Color::rgb(0x12, 0xab, 0x9f).to_hex(), "#12ab9f");
+        assert_eq!(Color::rgba(0x12, 0xab, 0x9f, 0x80).to_hex(), "#12ab9f80");
+    }
+
+    #[test]
+    fn hsv_round_trips() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(51) {
+                    let color = Color::rgb(r, g, b);
+                    let (hue, saturation, value) = color.to_hsv();
+                    assert_eq!(Color::from_hsv(hue, saturation, value), color);
+                }
+            }
+        }
+        assert_eq!(Color::rgb(255, 0, 0).to_hsv(), (0.0, 1.0, 1.0));
+        assert_eq!(Color::rgb(0, 0, 255).to_hsv(), (240.0, 1.0, 1.0));
+        assert_eq!(Color::rgb(128, 128, 128).to_hsv().0, 0.0);
+        assert_eq!(Color::from_hsv(-120.0, 1.0, 1.0), Color::rgb(0, 0, 255));
+        assert_eq!(Color::from_hsv(480.0, 2.0, 1.0), Color::rgb(0, 255, 0));
+    }
 }
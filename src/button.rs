@@ -1,6 +1,8 @@
 use std::sync::atomic::Ordering;
 
-use super::{common::ELEMENT_NEXT_ID, Event, ImageStyle};
+use super::{
+    common::ELEMENT_NEXT_ID, Event, FontId, ImageStyle, RichText, StateImages, StyleOverride,
+};
 
 use glam::Vec2;
 pub struct Button {
@@ -9,7 +11,15 @@ pub struct Button {
     pub size: Vec2,
 
     pub label: Option<String>,
+    /// Markup label drawn instead of `label` when set.
+    pub rich_label: Option<RichText>,
     pub background_image: Option<ImageStyle>,
+    /// Per-state skin drawn instead of the theme's bevel box.
+    pub state_images: Option<StateImages>,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
 
     pub hovered: bool,
     pub pressed: bool,
@@ -24,7 +34,13 @@ impl Button {
             size,
 
             label,
+            rich_label: None,
             background_image: None,
+            state_images: None,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
 
             hovered: false,
             pressed: false,
@@ -36,6 +52,38 @@ impl Button {
         self.background_image = Some(image);
     }
 
+    pub fn set_state_images(&mut self, images: StateImages) {
+        self.state_images = Some(images);
+    }
+
+    pub fn set_rich_label(&mut self, markup: &str) {
+        self.rich_label = Some(RichText::parse(markup));
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// Drop hover and press state without an event, so a press in progress never
+    /// completes. `Gui` does this while the button is disabled.
+    pub fn cancel_press(&mut self) {
+        self.hovered = false;
+        self.pressed = false;
+        self.was_pressed = false;
+    }
+
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
         let mut event: Option<Event> = None;
         if !mouse_pressed && self.was_pressed {
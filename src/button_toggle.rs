@@ -2,7 +2,7 @@ use std::sync::atomic::Ordering;
 
 use glam::Vec2;
 
-use super::{Button, Event, ELEMENT_NEXT_ID};
+use super::{Button, Event, StyleOverride, ELEMENT_NEXT_ID};
 
 pub struct ButtonToggle {
     pub id: u32,
@@ -11,6 +11,8 @@ pub struct ButtonToggle {
     pub left_option: String,
     pub right_option: String,
     pub toggled_left: bool,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
     pub left_button: Button,
     pub right_button: Button,
 }
@@ -30,6 +32,8 @@ impl ButtonToggle {
             left_option: left_option.clone(),
             right_option: right_option.clone(),
             toggled_left,
+            style_class: None,
+            style: StyleOverride::default(),
             left_button: Button::new(position, Vec2::ZERO, Some(left_option)),
             right_button: Button::new(position, Vec2::ZERO, Some(right_option)),
         };
@@ -37,6 +41,14 @@ impl ButtonToggle {
         toggle
     }
 
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
     pub fn set_position(&mut self, position: Vec2) {
         self.position = position;
         self.sync_internal_buttons();
@@ -55,6 +67,11 @@ impl ButtonToggle {
         }
     }
 
+    pub fn cancel_press(&mut self) {
+        self.left_button.cancel_press();
+        self.right_button.cancel_press();
+    }
+
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
         let left_event = self.left_button.step(mouse_position, mouse_pressed);
         let right_event = self.right_button.step(mouse_position, mouse_pressed);
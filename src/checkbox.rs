@@ -0,0 +1,109 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Button, Event, FontId, Key, StyleOverride, ELEMENT_NEXT_ID};
+
+/// A check box with a label to its right. The whole rect is clickable.
+pub struct Checkbox {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub label: Option<String>,
+    pub checked: bool,
+    /// Mixed state, drawn as a dash. Usually set by the app, e.g. for a "select all"
+    /// box over a partly checked list.
+    pub indeterminate: bool,
+    /// Clicking cycles unchecked, checked, indeterminate instead of toggling.
+    pub tri_state: bool,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    pub button: Button,
+}
+
+impl Checkbox {
+    pub fn new(position: Vec2, size: Vec2, label: Option<String>, checked: bool) -> Self {
+        Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            label,
+            checked,
+            indeterminate: false,
+            tri_state: false,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            button: Button::new(position, size, None),
+        }
+    }
+
+    pub fn set_tri_state(&mut self, tri_state: bool) {
+        self.tri_state = tri_state;
+    }
+
+    pub fn set_indeterminate(&mut self, indeterminate: bool) {
+        self.indeterminate = indeterminate;
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.button.position = position;
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+        self.button.size = size;
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.button.cancel_press();
+    }
+
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        let event = self.button.step(mouse_position, mouse_pressed);
+        if matches!(event, Some(Event::ButtonReleased)) && self.button.hovered {
+            return Some(self.advance());
+        }
+        None
+    }
+
+    /// Space or Enter while focused acts like a click.
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        match key {
+            Key::Space | Key::Enter => Some(self.advance()),
+            _ => None,
+        }
+    }
+
+    fn advance(&mut self) -> Event {
+        (self.checked, self.indeterminate) = match (self.checked, self.indeterminate) {
+            (_, true) => (false, false),
+            (true, false) if self.tri_state => (false, true),
+            (checked, false) => (!checked, false),
+        };
+        Event::CheckboxChanged {
+            checked: self.checked,
+            indeterminate: self.indeterminate,
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Color, Event, FontId, Key, Rect, StyleOverride, ELEMENT_NEXT_ID};
+
+/// Saturation/value change per arrow key press, and hue change in degrees per
+/// Page Up/Down.
+const KEY_STEP: f32 = 0.05;
+const HUE_KEY_STEP: f32 = 10.0;
+
+/// Part of a `ColorPicker` being dragged.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorPickerPart {
+    /// The square, saturation left to right and value bottom to top.
+    SaturationValue,
+    /// The hue strip, 0 degrees at the top.
+    Hue,
+    /// The alpha strip, opaque at the top.
+    Alpha,
+}
+
+/// A color editor: a saturation/value square with a hue strip and an optional alpha
+/// strip to its right, then a preview and hex input row and a palette of swatches.
+/// The color is kept as HSV so the hue survives dragging through grays.
+pub struct ColorPicker {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    /// Degrees, `0.0..360.0`.
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub alpha: f32,
+    pub show_alpha: bool,
+    /// Preset colors shown below the hex input. Clicking one picks it.
+    pub swatches: Vec<Color>,
+    pub swatch_size: f32,
+    /// Width of the hue and alpha strips.
+    pub strip_width: f32,
+    /// Height of the preview and hex input row.
+    pub hex_height: f32,
+    /// Gap between the parts.
+    pub spacing: f32,
+    /// Hex text being typed, `None` when not editing.
+    pub hex_text: Option<String>,
+    /// The first typed text replaces the whole hex text.
+    pub replace_on_type: bool,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    pub hovered: bool,
+    pub hex_hovered: bool,
+    pub hovered_swatch: Option<usize>,
+    pub dragging: Option<ColorPickerPart>,
+    pub was_mouse_pressed: bool,
+}
+
+impl ColorPicker {
+    pub fn new(position: Vec2, size: Vec2, color: Color) -> Self {
+        let mut picker = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            hue: 0.0,
+            saturation: 0.0,
+            value: 0.0,
+            alpha: 1.0,
+            show_alpha: false,
+            swatches: Vec::new(),
+            swatch_size: 14.0,
+            strip_width: 16.0,
+            hex_height: 20.0,
+            spacing: 4.0,
+            hex_text: None,
+            replace_on_type: false,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            hovered: false,
+            hex_hovered: false,
+            hovered_swatch: None,
+            dragging: None,
+            was_mouse_pressed: false,
+        };
+        picker.set_color(color);
+        picker
+    }
+
+    /// Hiding the alpha strip makes `color()` opaque but keeps `alpha` for when it's
+    /// shown again.
+    pub fn set_show_alpha(&mut self, show_alpha: bool) {
+        self.show_alpha = show_alpha;
+    }
+
+    pub fn set_swatches(&mut self, swatches: Vec<Color>) {
+        self.swatches = swatches;
+    }
+
+    pub fn add_swatch(&mut self, color: Color) {
+        self.swatches.push(color);
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// The edited color, opaque unless `show_alpha` is set.
+    pub fn color(&self) -> Color {
+        let Color { r, g, b, .. } = Color::from_hsv(self.hue, self.saturation, self.value);
+        let alpha = if self.show_alpha { self.alpha } else { 1.0 };
+        Color::rgba(r, g, b, (alpha * 255.0).round() as u8)
+    }
+
+    /// Set the color without an event. Grays keep the current hue and black keeps the
+    /// current saturation, so the square doesn't jump.
+    pub fn set_color(&mut self, color: Color) {
+        let (hue, saturation, value) = color.to_hsv();
+        if saturation > 0.0 {
+            self.hue = hue;
+        }
+        if value > 0.0 {
+            self.saturation = saturation;
+        }
+        self.value = value;
+        self.alpha = color.a as f32 / 255.0;
+    }
+
+    /// The color at full saturation and value, the square's top-right corner.
+    pub fn hue_color(&self) -> Color {
+        Color::from_hsv(self.hue, 1.0, 1.0)
+    }
+
+    pub fn saturation_value_rect(&self) -> Rect {
+        let strips = if self.show_alpha { 2.0 } else { 1.0 };
+        Rect::new(
+            self.position,
+            Vec2::new(
+                (self.size.x - strips * (self.strip_width + self.spacing)).max(0.0),
+                self.top_height(),
+            ),
+        )
+    }
+
+    pub fn hue_rect(&self) -> Rect {
+        let square = self.saturation_value_rect();
+        Rect::new(
+            square.position + Vec2::new(square.size.x + self.spacing, 0.0),
+            Vec2::new(self.strip_width, square.size.y),
+        )
+    }
+
+    /// `None` unless `show_alpha` is set.
+    pub fn alpha_rect(&self) -> Option<Rect> {
+        if !self.show_alpha {
+            return None;
+        }
+        let hue = self.hue_rect();
+        Some(Rect::new(
+            hue.position + Vec2::new(hue.size.x + self.spacing, 0.0),
+            hue.size,
+        ))
+    }
+
+    /// Swatch of the current color, left of the hex input.
+    pub fn preview_rect(&self) -> Rect {
+        Rect::new(
+            self.position + Vec2::new(0.0, self.top_height() + self.spacing),
+            Vec2::new(self.hex_height * 1.5, self.hex_height),
+        )
+    }
+
+    pub fn hex_rect(&self) -> Rect {
+        let preview = self.preview_rect();
+        let x = preview.size.x + self.spacing;
+        Rect::new(
+            preview.position + Vec2::new(x, 0.0),
+            Vec2::new((self.size.x - x).max(0.0), self.hex_height),
+        )
+    }
+
+    /// One rect per swatch, in rows below the hex input.
+    pub fn swatch_rects(&self) -> Vec<Rect> {
+        let per_row = self.swatches_per_row();
+        let top = self.hex_rect().position.y + self.hex_height + self.spacing;
+        let pitch = self.swatch_size + self.spacing;
+        (0..self.swatches.len())
+            .map(|index| {
+                let (row, column) = (index / per_row, index % per_row);
+                Rect::new(
+                    Vec2::new(
+                        self.position.x + column as f32 * pitch,
+                        top + row as f32 * pitch,
+                    ),
+                    Vec2::splat(self.swatch_size),
+                )
+            })
+            .collect()
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.hex_text.is_some()
+    }
+
+    /// Start typing a hex color, with the current one selected so typing replaces it.
+    pub fn begin_edit(&mut self) {
+        self.hex_text = Some(self.color().to_hex());
+        self.replace_on_type = true;
+    }
+
+    /// Apply the typed hex color. Text that doesn't parse is discarded.
+    pub fn commit_edit(&mut self) {
+        let Some(color) = self.hex_text.take().and_then(|text| Color::from_hex(&text)) else {
+            return;
+        };
+        self.set_color(color);
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.hex_text = None;
+    }
+
+    /// Add typed characters to the hex input while editing. Only hex digits and `#` are
+    /// kept.
+    pub fn text_input(&mut self, text: &str) {
+        let Some(hex_text) = &mut self.hex_text else {
+            return;
+        };
+        if self.replace_on_type {
+            hex_text.clear();
+            self.replace_on_type = false;
+        }
+        for ch in text.chars() {
+            if (ch.is_ascii_hexdigit() || ch == '#') && hex_text.len() < 9 {
+                hex_text.push(ch);
+            }
+        }
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.hovered = false;
+        self.hex_hovered = false;
+        self.hovered_swatch = None;
+        self.dragging = None;
+    }
+
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        let before = self.color();
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+
+        self.hovered = Rect::new(self.position, self.size).contains(mouse_position);
+        self.hex_hovered = self.hex_rect().contains(mouse_position);
+        self.hovered_swatch = self
+            .swatch_rects()
+            .iter()
+            .position(|rect| rect.contains(mouse_position));
+        if press_started {
+            let on_hex = self.hex_hovered;
+            if self.is_editing() && !on_hex {
+                // clicking elsewhere applies the typed color
+                self.commit_edit();
+            }
+            if self.saturation_value_rect().contains(mouse_position) {
+                self.dragging = Some(ColorPickerPart::SaturationValue);
+            } else if self.hue_rect().contains(mouse_position) {
+                self.dragging = Some(ColorPickerPart::Hue);
+            } else if self
+                .alpha_rect()
+                .is_some_and(|rect| rect.contains(mouse_position))
+            {
+                self.dragging = Some(ColorPickerPart::Alpha);
+            } else if on_hex && !self.is_editing() {
+                self.begin_edit();
+            } else if let Some(index) = self.hovered_swatch {
+                self.set_color(self.swatches[index]);
+            }
+        }
+        if !mouse_pressed {
+            self.dragging = None;
+        }
+        if let Some(part) = self.dragging {
+            self.drag_to(part, mouse_position);
+        }
+        self.changed_since(before)
+    }
+
+    /// While editing the hex input, Enter applies it, Escape discards it and Backspace
+    /// deletes. Otherwise Left/Right change saturation, Up/Down value, Page Up/Down hue,
+    /// and Enter starts editing the hex input.
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        let before = self.color();
+        if let Some(hex_text) = &mut self.hex_text {
+            match key {
+                Key::Enter => self.commit_edit(),
+                Key::Escape => self.cancel_edit(),
+                Key::Backspace => {
+                    if self.replace_on_type {
+                        hex_text.clear();
+                        self.replace_on_type = false;
+                    } else {
+                        hex_text.pop();
+                    }
+                }
+                _ => {}
+            }
+            return self.changed_since(before);
+        }
+        match key {
+            Key::Left => self.saturation = (self.saturation - KEY_STEP).max(0.0),
+            Key::Right => self.saturation = (self.saturation + KEY_STEP).min(1.0),
+            Key::Down => self.value = (self.value - KEY_STEP).max(0.0),
+            Key::Up => self.value = (self.value + KEY_STEP).min(1.0),
+            Key::PageUp => self.hue = (self.hue - HUE_KEY_STEP).rem_euclid(360.0),
+            Key::PageDown => self.hue = (self.hue + HUE_KEY_STEP).rem_euclid(360.0),
+            Key::Enter => self.begin_edit(),
+            _ => {}
+        }
+        self.changed_since(before)
+    }
+
+    fn drag_to(&mut self, part: ColorPickerPart, mouse_position: Vec2) {
+        let fraction = |rect: Rect| {
+            ((mouse_position - rect.position) / rect.size.max(Vec2::ONE))
+                .clamp(Vec2::ZERO, Vec2::ONE)
+        };
+        match part {
+            ColorPickerPart::SaturationValue => {
+                let at = fraction(self.saturation_value_rect());
+                self.saturation = at.x;
+                self.value = 1.0 - at.y;
+            }
+            // stop just short of 360 so the bottom of the strip stays red
+            ColorPickerPart::Hue => self.hue = (fraction(self.hue_rect()).y * 360.0).min(359.9),
+            ColorPickerPart::Alpha => {
+                if let Some(rect) = self.alpha_rect() {
+                    self.alpha = 1.0 - fraction(rect).y;
+                }
+            }
+        }
+    }
+
+    fn changed_since(&self, before: Color) -> Option<Event> {
+        let color = self.color();
+        (color != before).then_some(Event::ColorChanged { color })
+    }
+
+    fn swatches_per_row(&self) -> usize {
+        (((self.size.x + self.spacing) / (self.swatch_size + self.spacing)) as usize).max(1)
+    }
+
+    /// Height of the square and strips: what's left after the hex row and swatches.
+    fn top_height(&self) -> f32 {
+        let rows = self.swatches.len().div_ceil(self.swatches_per_row());
+        let swatches_height = rows as f32 * (self.swatch_size + self.spacing);
+        (self.size.y - self.hex_height - self.spacing - swatches_height).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_alpha_while_the_strip_is_hidden() {
+        let mut picker = ColorPicker::new(
+            Vec2::ZERO,
+            Vec2::new(200.0, 200.0),
+            Color::rgba(255, 0, 0, 128),
+        );
+        assert_eq!(picker.color(), Color::rgb(255, 0, 0));
+        picker.set_show_alpha(true);
+        assert_eq!(picker.color(), Color::rgba(255, 0, 0, 128));
+        picker.set_show_alpha(false);
+        picker.set_show_alpha(true);
+        assert_eq!(picker.color().a, 128);
+    }
+}
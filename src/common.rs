@@ -1,6 +1,8 @@
 use glam::Vec2;
 use std::sync::atomic::AtomicU32;
 
+use crate::Color;
+
 pub static ELEMENT_NEXT_ID: AtomicU32 = AtomicU32::new(0);
 
 #[derive(Clone, Copy, Debug)]
@@ -10,6 +12,31 @@ pub struct TaggedEvent<T: Clone + Copy> {
     pub event: Event,
 }
 
+/// Keys that `Gui::key_pressed` routes to the open popup or focused element.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Enter,
+    Space,
+    Escape,
+    Tab,
+    Backspace,
+}
+
+/// Modifier keys held down, see `Gui::modifiers`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum Event {
     ButtonPressed,
@@ -20,6 +47,17 @@ pub enum Event {
     SliderReleased {
         value: f32,
     },
+    XYPadMoved {
+        value: Vec2,
+    },
+    XYPadReleased {
+        value: Vec2,
+    },
+    /// A `RangeSlider` thumb or its range moved.
+    RangeChanged {
+        low: f32,
+        high: f32,
+    },
     DraggablePressed,
     DraggableReleased {
         new_pos: Vec2,
@@ -38,4 +76,65 @@ pub enum Event {
         target_position: Vec2,
         target_size: Vec2,
     },
+    /// A rich text link in a `Label` was clicked. Look up the target with `Label::link_target`.
+    LinkClicked {
+        link_index: usize,
+    },
+    /// The mouse was pressed over a disabled element.
+    DisabledClicked,
+    TweenFinished {
+        tween_id: u32,
+    },
+    CheckboxChanged {
+        checked: bool,
+        indeterminate: bool,
+    },
+    RadioSelected {
+        index: usize,
+    },
+    /// The selection of a `ListView` changed at row `index`. Read the whole selection
+    /// with `ListView::selected_rows`.
+    ListSelectionChanged {
+        index: usize,
+    },
+    /// A `ListView` row was dragged from `from` to `to`. Move the app's row data the
+    /// same way: remove at `from`, then insert at `to`.
+    ListReordered {
+        from: usize,
+        to: usize,
+    },
+    /// A `TreeView` node was expanded. Lazy children are loaded by then.
+    TreeNodeExpanded {
+        node: u64,
+    },
+    TreeNodeCollapsed {
+        node: u64,
+    },
+    /// The selected `TreeView` node changed, by click, keyboard or because its
+    /// ancestor collapsed.
+    TreeSelectionChanged {
+        node: u64,
+    },
+    /// A `TabContainer` switched to tab `index`. Page visibility is already updated.
+    TabChanged {
+        index: usize,
+    },
+    /// A `MessageBox` button was chosen. The box has already closed.
+    DialogResult {
+        button: usize,
+    },
+    /// A menu item was chosen. `menu_tag` is the `Menu::tag` of the menu or submenu
+    /// holding the item. Checkable items are already toggled.
+    MenuItemChosen {
+        menu_tag: u32,
+        item_index: usize,
+    },
+    /// A `NumberField` value changed by arrows, dragging, keys or typed input.
+    NumberChanged {
+        value: f32,
+    },
+    /// A `ColorPicker` color changed by dragging, a swatch, keys or the hex input.
+    ColorChanged {
+        color: Color,
+    },
 }
@@ -0,0 +1,109 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{
+    Event, FontId, Key, Menu, MenuPopup, MenuPopupResult, Rect, StyleOverride, ELEMENT_NEXT_ID,
+};
+
+/// A popup menu opened at the pointer by `Gui::secondary_click` over one of its
+/// `target_ids`, e.g. every slot of an inventory.
+pub struct ContextMenu {
+    pub id: u32,
+    pub menu: Menu,
+    /// Elements a secondary click opens this menu on.
+    pub target_ids: Vec<u32>,
+    pub open: bool,
+    /// Target the menu was last opened on. Chosen items are reported with this id.
+    pub opened_on: Option<u32>,
+    pub popup: MenuPopup,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+}
+
+impl ContextMenu {
+    pub fn new(menu: Menu, target_ids: Vec<u32>, item_width: f32, item_height: f32) -> Self {
+        Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            menu,
+            target_ids,
+            open: false,
+            opened_on: None,
+            popup: MenuPopup::new(item_width, item_height),
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+        }
+    }
+
+    pub fn add_target(&mut self, id: u32) {
+        if !self.target_ids.contains(&id) {
+            self.target_ids.push(id);
+        }
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// Open with the top-level panel at `position`, kept inside `bounds`.
+    pub fn open_at(&mut self, position: Vec2, target: u32, bounds: Option<Rect>) {
+        self.open = true;
+        self.opened_on = Some(target);
+        self.popup.bounds = bounds;
+        self.popup.open_at(position, false);
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.popup.open_path.clear();
+        self.popup.hovered = None;
+    }
+
+    /// Rects of the open panels, top level first.
+    pub fn panel_rects(&self) -> Vec<Rect> {
+        if !self.open {
+            return Vec::new();
+        }
+        self.popup.panel_rects(&self.menu)
+    }
+
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        if !self.open {
+            return None;
+        }
+        let result = self
+            .popup
+            .step(&mut self.menu, mouse_position, mouse_pressed);
+        self.finish(result)
+    }
+
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        if !self.open {
+            return None;
+        }
+        let result = self.popup.key_pressed(&mut self.menu, key);
+        self.finish(result)
+    }
+
+    fn finish(&mut self, result: Option<MenuPopupResult>) -> Option<Event> {
+        let result = result?;
+        self.close();
+        result.event()
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::atomic::Ordering;
 
 use glam::Vec2;
 
-use super::{Event, ImageStyle, ELEMENT_NEXT_ID};
+use super::{Event, FontId, ImageStyle, StateImages, StyleOverride, ELEMENT_NEXT_ID};
 
 pub struct Draggable {
     pub id: u32,
@@ -11,6 +11,12 @@ pub struct Draggable {
 
     pub label: Option<String>,
     pub background_image: Option<ImageStyle>,
+    /// Per-state skin drawn instead of the theme's bevel box.
+    pub state_images: Option<StateImages>,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
 
     pub hovered: bool,
     pub being_dragged: bool,
@@ -29,6 +35,11 @@ impl Draggable {
 
             label,
             background_image: None,
+            state_images: None,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
 
             hovered: false,
             being_dragged: false,
@@ -42,6 +53,34 @@ impl Draggable {
         self.background_image = Some(image);
     }
 
+    pub fn set_state_images(&mut self, images: StateImages) {
+        self.state_images = Some(images);
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.hovered = false;
+        self.being_dragged = false;
+        self.mouse_last_position = None;
+        self.was_pre_hovered = false;
+        self.pre_hover_countdown = 0;
+    }
+
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
         let mut event: Option<Event> = None;
 
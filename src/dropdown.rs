@@ -0,0 +1,264 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{
+    place_tooltip, Button, Event, FontId, Key, Rect, StyleOverride, TooltipPlacement,
+    ELEMENT_NEXT_ID,
+};
+
+/// Shows the selected option and opens a scrollable popup list to pick another.
+pub struct Dropdown {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub options: Vec<String>,
+    pub selected_option_index: usize,
+    /// Rows the popup shows before it scrolls.
+    pub max_visible_rows: usize,
+    /// Height of one popup row.
+    pub row_height: f32,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    /// The closed box. Pressing it opens and closes the popup.
+    pub head_button: Button,
+
+    pub open: bool,
+    /// Option under the mouse or keyboard cursor while open.
+    pub highlighted_index: usize,
+    /// First option shown in the popup.
+    pub scroll_offset: usize,
+    /// `Gui::bounds` from the last step. The popup shows fewer rows to fit inside it.
+    pub popup_bounds: Option<Rect>,
+    pub was_mouse_pressed: bool,
+}
+
+impl Dropdown {
+    pub fn new(
+        position: Vec2,
+        size: Vec2,
+        options: Vec<String>,
+        starting_option_index: usize,
+    ) -> Self {
+        let selected_option_index = if options.is_empty() {
+            0
+        } else {
+            starting_option_index.min(options.len() - 1)
+        };
+
+        Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            options,
+            selected_option_index,
+            max_visible_rows: 8,
+            row_height: size.y,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            head_button: Button::new(position, size, None),
+            open: false,
+            highlighted_index: selected_option_index,
+            scroll_offset: 0,
+            popup_bounds: None,
+            was_mouse_pressed: false,
+        }
+    }
+
+    pub fn selected_option(&self) -> Option<&str> {
+        self.options
+            .get(self.selected_option_index)
+            .map(String::as_str)
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.head_button.position = position;
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+        self.head_button.size = size;
+    }
+
+    pub fn set_open(&mut self, open: bool) {
+        self.open = open && !self.options.is_empty();
+        if self.open {
+            self.highlighted_index = self.selected_option_index;
+            self.scroll_to(self.highlighted_index);
+        }
+    }
+
+    /// Number of rows the popup shows.
+    pub fn visible_rows(&self) -> usize {
+        let rows = self.options.len().min(self.max_visible_rows.max(1));
+        let Some(bounds) = self.popup_bounds else {
+            return rows;
+        };
+        let below = bounds.position.y + bounds.size.y - (self.position.y + self.size.y);
+        let above = self.position.y - bounds.position.y;
+        let fitting_rows = (below.max(above) / self.row_height.max(1.0)) as usize;
+        rows.min(fitting_rows.max(1))
+    }
+
+    /// The popup list, below the box, or above it when it doesn't fit in `popup_bounds`.
+    pub fn popup_rect(&self) -> Rect {
+        let popup_size = Vec2::new(self.size.x, self.visible_rows() as f32 * self.row_height);
+        place_tooltip(
+            Rect::new(self.position, self.size),
+            popup_size,
+            TooltipPlacement::Below,
+            0.0,
+            self.popup_bounds,
+        )
+    }
+
+    /// Scroll the popup by whole rows. Positive scrolls down.
+    pub fn scroll_by(&mut self, rows: i32) {
+        let max_offset = self.options.len().saturating_sub(self.visible_rows());
+        self.scroll_offset = self
+            .scroll_offset
+            .saturating_add_signed(rows as isize)
+            .min(max_offset);
+    }
+
+    /// Scroll the popup just enough to show `index`.
+    pub fn scroll_to(&mut self, index: usize) {
+        let visible_rows = self.visible_rows();
+        if index < self.scroll_offset {
+            self.scroll_offset = index;
+        } else if visible_rows > 0 && index >= self.scroll_offset + visible_rows {
+            self.scroll_offset = index + 1 - visible_rows;
+        }
+        self.scroll_by(0);
+    }
+
+    /// Close the popup and drop the head button's press state.
+    pub fn cancel_press(&mut self) {
+        self.open = false;
+        self.head_button.cancel_press();
+    }
+
+    /// `bounds` keeps the popup inside the gui, see `Gui::bounds`.
+    pub fn step(
+        &mut self,
+        mouse_position: Vec2,
+        mouse_pressed: bool,
+        bounds: Option<Rect>,
+    ) -> Option<Event> {
+        self.popup_bounds = bounds;
+        let head_event = self.head_button.step(mouse_position, mouse_pressed);
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        let released = !mouse_pressed && self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+
+        if !self.open {
+            if matches!(head_event, Some(Event::ButtonPressed)) {
+                self.set_open(true);
+            }
+            return None;
+        }
+
+        let popup = self.popup_rect();
+        let hovered_row = popup.contains(mouse_position).then(|| {
+            let row = ((mouse_position.y - popup.position.y) / self.row_height) as usize;
+            (self.scroll_offset + row).min(self.options.len() - 1)
+        });
+        if let Some(row) = hovered_row {
+            self.highlighted_index = row;
+        }
+
+        if matches!(head_event, Some(Event::ButtonPressed)) {
+            self.open = false;
+        } else if press_started && hovered_row.is_none() {
+            // click outside
+            self.open = false;
+        } else if released {
+            // choose on release, so press-drag-release from the box works too
+            if let Some(row) = hovered_row {
+                return self.choose(row);
+            }
+        }
+        None
+    }
+
+    /// Keyboard input while focused. Arrows change the selection when closed and move
+    /// the highlight when open.
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        if self.options.is_empty() {
+            return None;
+        }
+        let last = self.options.len() - 1;
+        let page = self.visible_rows();
+        let current = if self.open {
+            self.highlighted_index
+        } else {
+            self.selected_option_index
+        };
+        let target = match key {
+            Key::Up => current.saturating_sub(1),
+            Key::Down => (current + 1).min(last),
+            Key::PageUp => current.saturating_sub(page),
+            Key::PageDown => (current + page).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            Key::Enter | Key::Space => {
+                if self.open {
+                    return self.choose(self.highlighted_index);
+                }
+                self.set_open(true);
+                return None;
+            }
+            Key::Escape => {
+                self.open = false;
+                return None;
+            }
+            _ => return None,
+        };
+
+        if self.open {
+            self.highlighted_index = target;
+            self.scroll_to(target);
+            None
+        } else {
+            self.select(target)
+        }
+    }
+
+    fn choose(&mut self, index: usize) -> Option<Event> {
+        self.open = false;
+        self.select(index)
+    }
+
+    fn select(&mut self, index: usize) -> Option<Event> {
+        if index == self.selected_option_index {
+            return None;
+        }
+        self.selected_option_index = index;
+        self.highlighted_index = index;
+        Some(Event::SelectionChanged {
+            selected_option_index: index,
+            end_of_options_reached: index == 0 || index == self.options.len() - 1,
+        })
+    }
+}
@@ -2,7 +2,11 @@ use glam::Vec2;
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    ButtonToggle, Draggable, Label, LeftRightSelector, MoveAndResizeThumbs, VerticalSlider,
+    layout_label_rich_text, ActiveTween, ButtonToggle, Checkbox, Color, ColorPicker, ContextMenu,
+    Draggable, DrawBackend, Dropdown, ElementVisual, Event, ImageStyle, Key, Label,
+    LeftRightSelector, ListView, MenuBar, MessageBox, Modifiers, MoveAndResizeThumbs, NumberField,
+    ProgressBar, RadioGroup, RangeSlider, Rect, StyleClasses, StyleOverride, TabContainer, Theme,
+    Tooltip, TreeView, Tween, TweenTarget, VerticalSlider, XYPad,
 };
 
 use super::{Button, Slider, TaggedEvent};
@@ -10,6 +14,9 @@ use super::{Button, Slider, TaggedEvent};
 pub struct Gui<T: Clone + Copy> {
     pub el_to_tag_map: HashMap<u32, T>,
     pub hidden_ids: HashSet<u32>,
+    pub disabled_ids: HashSet<u32>,
+    /// Element with keyboard focus, drawn with its `StateImages::focused` skin.
+    pub focused_id: Option<u32>,
     pub buttons: Vec<Button>,
     pub sliders: Vec<Slider>,
     pub vertical_sliders: Vec<VerticalSlider>,
@@ -18,6 +25,54 @@ pub struct Gui<T: Clone + Copy> {
     pub left_right_selectors: Vec<LeftRightSelector>,
     pub button_toggles: Vec<ButtonToggle>,
     pub move_and_resize_thumbs: Vec<MoveAndResizeThumbs>,
+    pub dropdowns: Vec<Dropdown>,
+    pub checkboxes: Vec<Checkbox>,
+    pub radio_groups: Vec<RadioGroup>,
+    pub progress_bars: Vec<ProgressBar>,
+    pub list_views: Vec<ListView>,
+    pub tree_views: Vec<TreeView>,
+    pub tab_containers: Vec<TabContainer>,
+    pub message_boxes: Vec<MessageBox>,
+    pub context_menus: Vec<ContextMenu>,
+    /// Element ids of each open modal, bottom to top. While any is open, only the
+    /// top modal's elements get input.
+    pub modal_layers: Vec<Vec<u32>>,
+    pub menu_bars: Vec<MenuBar>,
+    pub number_fields: Vec<NumberField>,
+    pub color_pickers: Vec<ColorPicker>,
+    pub xy_pads: Vec<XYPad>,
+    pub range_sliders: Vec<RangeSlider>,
+    /// Inline icons for rich text `{icon:name}` markup.
+    pub icons: HashMap<String, ImageStyle>,
+    /// Style classes that widgets reference through `style_class`.
+    pub style_classes: StyleClasses,
+    /// Mouse button state from the previous `step`, for click edge detection.
+    pub was_mouse_pressed: bool,
+    /// Mouse position from the previous `step`.
+    pub mouse_position: Vec2,
+    /// Set when a press landed while a popup was open. Widgets ignore the mouse until
+    /// it is released.
+    pub block_mouse_until_release: bool,
+    /// Area the gui is drawn in, in gui pixels. Tooltips are kept inside it when set.
+    pub bounds: Option<Rect>,
+    /// Modifier keys held down. Set it before `step` and `key_pressed` so list views can
+    /// extend their selection.
+    pub modifiers: Modifiers,
+    /// Running tweens, in the order they were added.
+    pub tweens: Vec<ActiveTween>,
+    pub next_tween_id: u32,
+    /// Tint/opacity/scale for elements that have been given one.
+    pub visuals: HashMap<u32, ElementVisual>,
+    /// Seconds for hover highlights to fade in and out. `0.0` switches instantly.
+    pub hover_fade_seconds: f32,
+    /// Hover highlight strength `0..=1` by element id, advanced by `update`.
+    pub hover_fades: HashMap<u32, f32>,
+    /// Hover tooltips by element id.
+    pub tooltips: HashMap<u32, Tooltip>,
+    /// Seconds the mouse has to rest on an element before its tooltip shows.
+    pub tooltip_delay_seconds: f32,
+    /// Element under the mouse that has a tooltip, and for how long it has been hovered.
+    pub tooltip_hover: Option<(u32, f32)>,
 }
 
 impl<T: Clone + Copy> Gui<T> {
@@ -25,6 +80,8 @@ impl<T: Clone + Copy> Gui<T> {
         Self {
             el_to_tag_map: HashMap::new(),
             hidden_ids: HashSet::new(),
+            disabled_ids: HashSet::new(),
+            focused_id: None,
             buttons: Vec::new(),
             sliders: Vec::new(),
             vertical_sliders: Vec::new(),
@@ -33,48 +90,84 @@ impl<T: Clone + Copy> Gui<T> {
             left_right_selectors: Vec::new(),
             button_toggles: Vec::new(),
             move_and_resize_thumbs: Vec::new(),
+            dropdowns: Vec::new(),
+            checkboxes: Vec::new(),
+            radio_groups: Vec::new(),
+            progress_bars: Vec::new(),
+            list_views: Vec::new(),
+            tree_views: Vec::new(),
+            tab_containers: Vec::new(),
+            message_boxes: Vec::new(),
+            context_menus: Vec::new(),
+            modal_layers: Vec::new(),
+            menu_bars: Vec::new(),
+            number_fields: Vec::new(),
+            color_pickers: Vec::new(),
+            xy_pads: Vec::new(),
+            range_sliders: Vec::new(),
+            icons: HashMap::new(),
+            style_classes: StyleClasses::new(),
+            was_mouse_pressed: false,
+            mouse_position: Vec2::new(-1.0, -1.0),
+            block_mouse_until_release: false,
+            bounds: None,
+            modifiers: Modifiers::default(),
+            tweens: Vec::new(),
+            next_tween_id: 0,
+            visuals: HashMap::new(),
+            hover_fade_seconds: 0.0,
+            hover_fades: HashMap::new(),
+            tooltips: HashMap::new(),
+            tooltip_delay_seconds: 0.5,
+            tooltip_hover: None,
         }
     }
 
     //// ADD ELEMENTS
     pub fn add_button(&mut self, button: Button, tag: T) {
         self.el_to_tag_map.insert(button.id, tag);
-        self.hidden_ids.remove(&button.id);
+        self.reset_visibility(button.id);
         self.buttons.push(button);
     }
 
     pub fn add_slider(&mut self, slider: Slider, tag: T) {
         self.el_to_tag_map.insert(slider.id, tag);
-        self.hidden_ids.remove(&slider.id);
+        self.reset_visibility(slider.id);
         self.sliders.push(slider);
     }
 
     pub fn add_vertical_slider(&mut self, vertical_slider: VerticalSlider, tag: T) {
         self.el_to_tag_map.insert(vertical_slider.id, tag);
-        self.hidden_ids.remove(&vertical_slider.id);
+        self.reset_visibility(vertical_slider.id);
         self.vertical_sliders.push(vertical_slider);
     }
 
     pub fn add_draggable(&mut self, draggable: Draggable, tag: T) {
         self.el_to_tag_map.insert(draggable.id, tag);
-        self.hidden_ids.remove(&draggable.id);
+        self.reset_visibility(draggable.id);
         self.draggables.push(draggable);
     }
 
     pub fn add_label(&mut self, label: Label) {
-        self.hidden_ids.remove(&label.id);
+        self.reset_visibility(label.id);
         self.labels.push(label);
     }
 
+    /// Add a label that emits events (rich text `LinkClicked`) under `tag`.
+    pub fn add_tagged_label(&mut self, label: Label, tag: T) {
+        self.el_to_tag_map.insert(label.id, tag);
+        self.add_label(label);
+    }
+
     pub fn add_left_right_selector(&mut self, left_right_selector: LeftRightSelector, tag: T) {
         self.el_to_tag_map.insert(left_right_selector.id, tag);
-        self.hidden_ids.remove(&left_right_selector.id);
+        self.reset_visibility(left_right_selector.id);
         self.left_right_selectors.push(left_right_selector);
     }
 
     pub fn add_button_toggle(&mut self, button_toggle: ButtonToggle, tag: T) {
         self.el_to_tag_map.insert(button_toggle.id, tag);
-        self.hidden_ids.remove(&button_toggle.id);
+        self.reset_visibility(button_toggle.id);
         self.button_toggles.push(button_toggle);
     }
 
@@ -84,33 +177,997 @@ impl<T: Clone + Copy> Gui<T> {
         tag: T,
     ) {
         self.el_to_tag_map.insert(move_and_resize_thumbs.id, tag);
-        self.hidden_ids.remove(&move_and_resize_thumbs.id);
+        self.reset_visibility(move_and_resize_thumbs.id);
         self.move_and_resize_thumbs.push(move_and_resize_thumbs);
     }
 
+    pub fn add_dropdown(&mut self, dropdown: Dropdown, tag: T) {
+        self.el_to_tag_map.insert(dropdown.id, tag);
+        self.reset_visibility(dropdown.id);
+        self.dropdowns.push(dropdown);
+    }
+
+    pub fn add_checkbox(&mut self, checkbox: Checkbox, tag: T) {
+        self.el_to_tag_map.insert(checkbox.id, tag);
+        self.reset_visibility(checkbox.id);
+        self.checkboxes.push(checkbox);
+    }
+
+    pub fn add_radio_group(&mut self, group: RadioGroup, tag: T) {
+        self.el_to_tag_map.insert(group.id, tag);
+        self.reset_visibility(group.id);
+        self.radio_groups.push(group);
+    }
+
+    /// Progress bars are display-only, so they have no tag.
+    pub fn add_progress_bar(&mut self, progress_bar: ProgressBar) {
+        self.reset_visibility(progress_bar.id);
+        self.progress_bars.push(progress_bar);
+    }
+
+    pub fn add_list_view(&mut self, list: ListView, tag: T) {
+        self.el_to_tag_map.insert(list.id, tag);
+        self.reset_visibility(list.id);
+        self.list_views.push(list);
+    }
+
+    pub fn add_tree_view(&mut self, tree: TreeView, tag: T) {
+        self.el_to_tag_map.insert(tree.id, tag);
+        self.reset_visibility(tree.id);
+        self.tree_views.push(tree);
+    }
+
+    /// Hides every page but the selected tab's.
+    pub fn add_tab_container(&mut self, container: TabContainer, tag: T) {
+        let id = container.id;
+        self.el_to_tag_map.insert(id, tag);
+        self.tab_containers.push(container);
+        self.reset_visibility(id);
+    }
+
+    /// Add a message box and open it as a modal above everything else. It is removed
+    /// when it emits `Event::DialogResult`.
+    pub fn add_message_box(&mut self, message_box: MessageBox, tag: T) {
+        let id = message_box.id;
+        self.el_to_tag_map.insert(id, tag);
+        self.reset_visibility(id);
+        self.message_boxes.push(message_box);
+        self.open_modal(vec![id]);
+        self.focused_id = Some(id);
+    }
+
+    /// Add a context menu. Its events carry `tag` and the id of the target element it
+    /// was opened on.
+    pub fn add_context_menu(&mut self, context_menu: ContextMenu, tag: T) {
+        self.el_to_tag_map.insert(context_menu.id, tag);
+        self.reset_visibility(context_menu.id);
+        self.context_menus.push(context_menu);
+    }
+
+    pub fn add_menu_bar(&mut self, menu_bar: MenuBar, tag: T) {
+        self.el_to_tag_map.insert(menu_bar.id, tag);
+        self.reset_visibility(menu_bar.id);
+        self.menu_bars.push(menu_bar);
+    }
+
+    pub fn add_number_field(&mut self, field: NumberField, tag: T) {
+        self.el_to_tag_map.insert(field.id, tag);
+        self.reset_visibility(field.id);
+        self.number_fields.push(field);
+    }
+
+    pub fn add_color_picker(&mut self, picker: ColorPicker, tag: T) {
+        self.el_to_tag_map.insert(picker.id, tag);
+        self.reset_visibility(picker.id);
+        self.color_pickers.push(picker);
+    }
+
+    pub fn add_xy_pad(&mut self, pad: XYPad, tag: T) {
+        self.el_to_tag_map.insert(pad.id, tag);
+        self.reset_visibility(pad.id);
+        self.xy_pads.push(pad);
+    }
+
+    pub fn add_range_slider(&mut self, range: RangeSlider, tag: T) {
+        self.el_to_tag_map.insert(range.id, tag);
+        self.reset_visibility(range.id);
+        self.range_sliders.push(range);
+    }
+
+    pub fn register_icon(&mut self, name: &str, image: ImageStyle) {
+        self.icons.insert(name.to_string(), image);
+    }
+
+    pub fn add_style_class(&mut self, name: &str, style: StyleOverride) {
+        self.style_classes.add(name, style);
+    }
+
+    /// Lay out rich text labels with `backend`'s text measurements so `step` can
+    /// hit-test their links. Call it after changing a label's text, width or font, or
+    /// once per frame. Moving a label keeps its links in place without a new layout.
+    pub fn layout_text<B: DrawBackend + ?Sized>(&mut self, backend: &B, theme: &Theme) {
+        for label in self.labels.iter_mut() {
+            match layout_label_rich_text(backend, label, theme, &self.style_classes, &self.icons) {
+                Some(layout) => label.set_link_rects(&layout),
+                None => label.link_rects.clear(),
+            }
+        }
+    }
+
+    /// Showing or hiding a `TabContainer` also shows or hides its selected page.
     pub fn set_visible(&mut self, id: u32, visible: bool) {
+        self.set_visible_from(id, visible, &mut Vec::new());
+    }
+
+    /// `syncing` holds the containers whose pages are being updated, so a container
+    /// listed on its own page, or a nesting cycle, doesn't recurse forever.
+    fn set_visible_from(&mut self, id: u32, visible: bool, syncing: &mut Vec<u32>) {
         if visible {
             self.hidden_ids.remove(&id);
         } else {
             self.hidden_ids.insert(id);
         }
+        if self.get_tab_container(id).is_some() {
+            self.sync_tab_pages_from(id, syncing);
+        }
+    }
+
+    /// Show a new element, unless a `TabContainer` lists it on a page that isn't showing.
+    fn reset_visibility(&mut self, id: u32) {
+        let on_hidden_page = self.tab_containers.iter().any(|container| {
+            let showing = self.is_visible(container.id);
+            container.tabs.iter().enumerate().any(|(index, tab)| {
+                tab.page_ids.contains(&id) && !(showing && index == container.selected_index)
+            })
+        });
+        self.set_visible(id, !on_hidden_page);
     }
 
     pub fn is_visible(&self, id: u32) -> bool {
         !self.hidden_ids.contains(&id)
     }
 
+    /// Disabled elements are still drawn (greyed out) but skip interaction in `step`,
+    /// which also cancels a press in progress without its release event.
+    /// Pressing the mouse over one emits `Event::DisabledClicked`.
+    pub fn set_enabled(&mut self, id: u32, enabled: bool) {
+        if enabled {
+            self.disabled_ids.remove(&id);
+        } else {
+            self.disabled_ids.insert(id);
+            self.release_focus(id);
+        }
+    }
+
+    pub fn is_enabled(&self, id: u32) -> bool {
+        !self.disabled_ids.contains(&id)
+    }
+
+    pub fn set_focus(&mut self, id: Option<u32>) {
+        self.focused_id = id;
+    }
+
+    pub fn is_focused(&self, id: u32) -> bool {
+        self.focused_id == Some(id)
+    }
+
+    fn release_focus(&mut self, id: u32) {
+        if self.focused_id == Some(id) {
+            self.focused_id = None;
+        }
+    }
+
+    /// Drops state kept outside the widget lists. Only for the `remove_*` paths, since it
+    /// also removes the tooltip, visuals and tweens.
+    fn clear_element_state(&mut self, id: u32) {
+        self.release_focus(id);
+        self.visuals.remove(&id);
+        self.tweens.retain(|tween| tween.tween.element_id != id);
+        self.tooltips.remove(&id);
+    }
+
+    /// Whether a dropdown popup, context menu or menu bar menu is open. While one is, it gets all mouse
+    /// input.
+    pub fn has_open_popup(&self) -> bool {
+        self.dropdowns.iter().any(|dropdown| dropdown.open)
+            || self
+                .context_menus
+                .iter()
+                .any(|context_menu| context_menu.open)
+            || self.menu_bars.iter().any(|menu_bar| menu_bar.is_open())
+    }
+
+    /// Open the context menu of the element under `mouse_position` at the pointer, closing
+    /// any open popup. Call it when the secondary (right) mouse button is pressed.
+    /// Returns whether a menu opened.
+    pub fn secondary_click(&mut self, mouse_position: Vec2) -> bool {
+        for dropdown in self.dropdowns.iter_mut() {
+            dropdown.open = false;
+        }
+        for context_menu in self.context_menus.iter_mut() {
+            context_menu.close();
+        }
+        for menu_bar in self.menu_bars.iter_mut() {
+            menu_bar.close();
+        }
+        // the smallest target under the mouse wins, as with tooltips
+        let target = self
+            .context_menus
+            .iter()
+            .filter(|context_menu| {
+                self.is_visible(context_menu.id) && self.is_enabled(context_menu.id)
+            })
+            .flat_map(|context_menu| {
+                let menu_id = context_menu.id;
+                context_menu.target_ids.iter().map(move |id| (menu_id, *id))
+            })
+            .filter(|(_, id)| self.is_visible(*id) && !self.is_blocked_by_modal(*id))
+            .filter_map(|(menu_id, id)| Some((menu_id, id, self.element_rect(id)?)))
+            .filter(|(_, _, rect)| rect.contains(mouse_position))
+            .min_by(|(_, id_a, a), (_, id_b, b)| {
+                (a.size.x * a.size.y)
+                    .total_cmp(&(b.size.x * b.size.y))
+                    .then(id_a.cmp(id_b))
+            });
+        let Some((menu_id, target, _)) = target else {
+            return false;
+        };
+        let bounds = self.bounds;
+        if let Some(context_menu) = self.get_context_menu_mut(menu_id) {
+            context_menu.open_at(mouse_position, target, bounds);
+        }
+        true
+    }
+
+    /// Route a key press to the open popup, or else the focused element.
+    pub fn key_pressed(&mut self, key: Key) -> Vec<TaggedEvent<T>> {
+        let mut tagged_events = Vec::new();
+        let target = self
+            .dropdowns
+            .iter()
+            .find(|dropdown| dropdown.open)
+            .map(|dropdown| dropdown.id)
+            .or_else(|| {
+                self.context_menus
+                    .iter()
+                    .find(|context_menu| context_menu.open)
+                    .map(|context_menu| context_menu.id)
+            })
+            .or_else(|| {
+                self.menu_bars
+                    .iter()
+                    .find(|menu_bar| menu_bar.is_open())
+                    .map(|menu_bar| menu_bar.id)
+            })
+            .or(self.focused_id);
+        let Some(id) = target else {
+            return tagged_events;
+        };
+        if !self.is_visible(id) || !self.is_enabled(id) || self.is_blocked_by_modal(id) {
+            return tagged_events;
+        }
+
+        let modifiers = self.modifiers;
+        let bounds = self.bounds;
+        // a tree key can both collapse a node and move the selection
+        let events: Vec<Event> = if let Some(tree) = self.get_tree_view_mut(id) {
+            tree.key_pressed(key)
+        } else {
+            let event = if let Some(dropdown) = self.get_dropdown_mut(id) {
+                dropdown.key_pressed(key)
+            } else if let Some(checkbox) = self.get_checkbox_mut(id) {
+                checkbox.key_pressed(key)
+            } else if let Some(group) = self.get_radio_group_mut(id) {
+                group.key_pressed(key)
+            } else if let Some(list) = self.get_list_view_mut(id) {
+                list.key_pressed(key, modifiers)
+            } else if let Some(container) = self.get_tab_container_mut(id) {
+                container.key_pressed(key)
+            } else if let Some(message_box) = self.get_message_box_mut(id) {
+                message_box.key_pressed(key)
+            } else if let Some(context_menu) = self.get_context_menu_mut(id) {
+                context_menu.key_pressed(key)
+            } else if let Some(menu_bar) = self.get_menu_bar_mut(id) {
+                menu_bar.key_pressed(key, bounds)
+            } else if let Some(field) = self.get_number_field_mut(id) {
+                field.key_pressed(key)
+            } else if let Some(picker) = self.get_color_picker_mut(id) {
+                picker.key_pressed(key)
+            } else {
+                None
+            };
+            event.into_iter().collect()
+        };
+        if events
+            .iter()
+            .any(|event| matches!(event, Event::TabChanged { .. }))
+        {
+            self.sync_tab_pages(id);
+        }
+        // context menu events report the element the menu was opened on
+        let element_id = self
+            .get_context_menu(id)
+            .and_then(|context_menu| context_menu.opened_on)
+            .unwrap_or(id);
+        for event in events {
+            if let Some(tag) = self.el_to_tag_map.get(&id) {
+                tagged_events.push(TaggedEvent {
+                    tag: *tag,
+                    element_id,
+                    event,
+                });
+            }
+        }
+        if tagged_events
+            .iter()
+            .any(|tagged| matches!(tagged.event, Event::DialogResult { .. }))
+        {
+            self.remove_message_box(id);
+        }
+        tagged_events
+    }
+
+    /// Choose the menu bar item with this `MenuItem::shortcut`, e.g. `"Ctrl+S"`. Apps call
+    /// it when they detect the key combination, so items and shortcuts share one event.
+    pub fn trigger_shortcut(&mut self, shortcut: &str) -> Vec<TaggedEvent<T>> {
+        let mut tagged_events = Vec::new();
+        for menu_bar in self.menu_bars.iter_mut() {
+            let id = menu_bar.id;
+            if self.hidden_ids.contains(&id)
+                || self.disabled_ids.contains(&id)
+                || self
+                    .modal_layers
+                    .last()
+                    .is_some_and(|modal_ids| !modal_ids.contains(&id))
+            {
+                continue;
+            }
+            let Some(event) = menu_bar.trigger_shortcut(shortcut) else {
+                continue;
+            };
+            if let Some(tag) = self.el_to_tag_map.get(&id) {
+                tagged_events.push(TaggedEvent {
+                    tag: *tag,
+                    element_id: id,
+                    event,
+                });
+            }
+            break;
+        }
+        tagged_events
+    }
+
+    /// Send typed text to the focused element, for number fields and color picker hex
+    /// inputs being edited. Apps pass
+    /// the characters their window reported this frame.
+    pub fn text_input(&mut self, text: &str) {
+        let Some(id) = self.focused_id else {
+            return;
+        };
+        if !self.is_visible(id) || !self.is_enabled(id) || self.is_blocked_by_modal(id) {
+            return;
+        }
+        if let Some(field) = self.get_number_field_mut(id) {
+            field.text_input(text);
+        } else if let Some(picker) = self.get_color_picker_mut(id) {
+            picker.text_input(text);
+        }
+    }
+
+    /// Scroll the open popup, or else the list view, tree view or overflowing tab strip
+    /// under the mouse, by a mouse wheel move, positive scrolling up. Returns whether the
+    /// wheel was used, so the app can skip its own wheel handling. Elements beneath an
+    /// open modal don't scroll.
+    pub fn mouse_wheel(&mut self, delta: f32) -> bool {
+        if let Some(dropdown) = self.dropdowns.iter_mut().find(|dropdown| dropdown.open) {
+            dropdown.scroll_by(-delta.round() as i32);
+            return true;
+        }
+        let mouse_position = self.mouse_position;
+        let hidden_ids = &self.hidden_ids;
+        let top_modal = self.modal_layers.last();
+        let hidden_ids = |id: &u32| {
+            hidden_ids.contains(id) || top_modal.is_some_and(|modal_ids| !modal_ids.contains(id))
+        };
+        if let Some(list) = self.list_views.iter_mut().find(|list| {
+            !hidden_ids(&list.id)
+                && (list.dragging || Rect::new(list.position, list.size).contains(mouse_position))
+        }) {
+            list.scroll_by(-delta * list.row_height);
+            return true;
+        }
+        if let Some(tree) = self.tree_views.iter_mut().find(|tree| {
+            !hidden_ids(&tree.id) && Rect::new(tree.position, tree.size).contains(mouse_position)
+        }) {
+            tree.scroll_by(-delta * tree.row_height);
+            return true;
+        }
+        if let Some(container) = self.tab_containers.iter_mut().find(|container| {
+            !hidden_ids(&container.id)
+                && container.overflows()
+                && container.strip_rect().contains(mouse_position)
+        }) {
+            container.scroll_by(-delta * container.tab_width);
+            return true;
+        }
+        false
+    }
+
+    //// MODALS
+    /// Open a modal layer over the gui. Until it is closed, only `element_ids` get mouse
+    /// and keyboard input, and `draw_gui` dims everything beneath them. Modals stack, so
+    /// a modal can open another one.
+    pub fn open_modal(&mut self, element_ids: Vec<u32>) {
+        for dropdown in self.dropdowns.iter_mut() {
+            if !element_ids.contains(&dropdown.id) {
+                dropdown.open = false;
+            }
+        }
+        for context_menu in self.context_menus.iter_mut() {
+            if !element_ids.contains(&context_menu.id) {
+                context_menu.close();
+            }
+        }
+        for menu_bar in self.menu_bars.iter_mut() {
+            if !element_ids.contains(&menu_bar.id) {
+                menu_bar.close();
+            }
+        }
+        if self.focused_id.is_some_and(|id| !element_ids.contains(&id)) {
+            self.focused_id = None;
+        }
+        self.modal_layers.push(element_ids);
+    }
+
+    /// Close the top modal and return its element ids. The elements stay in the gui;
+    /// remove or hide them if they were only part of the modal.
+    pub fn close_modal(&mut self) -> Option<Vec<u32>> {
+        let element_ids = self.modal_layers.pop()?;
+        self.after_modal_closed(&element_ids);
+        Some(element_ids)
+    }
+
+    /// Close the modal holding element `id`, along with any opened above it.
+    pub fn close_modal_of(&mut self, id: u32) {
+        if let Some(layer) = self.modal_layers.iter().position(|ids| ids.contains(&id)) {
+            let closed: Vec<u32> = self.modal_layers.drain(layer..).flatten().collect();
+            self.after_modal_closed(&closed);
+        }
+    }
+
+    pub fn has_modal(&self) -> bool {
+        !self.modal_layers.is_empty()
+    }
+
+    /// `0` for elements outside any modal, otherwise the position of their modal in
+    /// `modal_layers` plus one. `draw_gui` draws layers in this order.
+    pub fn modal_layer(&self, id: u32) -> usize {
+        self.modal_layers
+            .iter()
+            .rposition(|ids| ids.contains(&id))
+            .map_or(0, |layer| layer + 1)
+    }
+
+    /// Whether a modal above element `id` keeps it from getting input.
+    pub fn is_blocked_by_modal(&self, id: u32) -> bool {
+        self.modal_layer(id) < self.modal_layers.len()
+    }
+
+    fn after_modal_closed(&mut self, element_ids: &[u32]) {
+        // focus goes back to the modal beneath, e.g. the message box that opened this one
+        if self.focused_id.is_some_and(|id| element_ids.contains(&id)) {
+            self.focused_id = self
+                .modal_layers
+                .last()
+                .and_then(|modal_ids| modal_ids.first().copied());
+        }
+        // the press that closed it doesn't reach the widgets underneath
+        if self.was_mouse_pressed {
+            self.block_mouse_until_release = true;
+        }
+    }
+
+    //// TABS
+    /// Select a tab and update page visibility right away, without an event.
+    pub fn select_tab(&mut self, id: u32, index: usize) {
+        if let Some(container) = self.get_tab_container_mut(id) {
+            container.set_selected_index(index);
+        }
+        self.sync_tab_pages(id);
+    }
+
+    /// Show the selected tab's page elements and hide the other pages, or hide every
+    /// page if the container is hidden. `step` calls this when the tab changes; call it
+    /// after editing `tabs` or their `page_ids`.
+    pub fn sync_tab_pages(&mut self, id: u32) {
+        self.sync_tab_pages_from(id, &mut Vec::new());
+    }
+
+    fn sync_tab_pages_from(&mut self, id: u32, syncing: &mut Vec<u32>) {
+        syncing.push(id);
+        let visible = self.is_visible(id);
+        let Some(container) = self.get_tab_container(id) else {
+            return;
+        };
+        let pages: Vec<(u32, bool)> = container
+            .tabs
+            .iter()
+            .enumerate()
+            .flat_map(|(index, tab)| {
+                let shown = visible && index == container.selected_index;
+                tab.page_ids.iter().map(move |page_id| (*page_id, shown))
+            })
+            .collect();
+        // nested containers pass this on to their own pages, but a container already
+        // being synced is left alone
+        for (page_id, shown) in pages {
+            if !syncing.contains(&page_id) {
+                self.set_visible_from(page_id, shown, syncing);
+            }
+        }
+    }
+
+    //// TOOLTIPS
+    pub fn set_tooltip(&mut self, id: u32, tooltip: Tooltip) {
+        self.tooltips.insert(id, tooltip);
+    }
+
+    pub fn remove_tooltip(&mut self, id: u32) {
+        self.tooltips.remove(&id);
+    }
+
+    /// Element whose tooltip is showing, once it has been hovered for
+    /// `tooltip_delay_seconds`. Tooltips hide while a popup is open, and for elements
+    /// beneath a modal.
+    pub fn active_tooltip(&self) -> Option<u32> {
+        if self.has_open_popup() {
+            return None;
+        }
+        let (id, seconds) = self.tooltip_hover?;
+        (seconds >= self.tooltip_delay_seconds
+            && self.tooltips.contains_key(&id)
+            && !self.is_blocked_by_modal(id))
+        .then_some(id)
+    }
+
+    fn update_tooltip(&mut self, dt: f32) {
+        if self.has_open_popup() {
+            self.tooltip_hover = None;
+            return;
+        }
+        // the smallest element under the mouse wins, so a button beats the panel behind it
+        let hovered = self
+            .tooltips
+            .keys()
+            .filter(|id| self.is_visible(**id) && !self.is_blocked_by_modal(**id))
+            .filter_map(|id| Some((*id, self.element_rect(*id)?)))
+            .filter(|(_, rect)| rect.contains(self.mouse_position))
+            .min_by(|(id_a, a), (id_b, b)| {
+                (a.size.x * a.size.y)
+                    .total_cmp(&(b.size.x * b.size.y))
+                    .then(id_a.cmp(id_b))
+            })
+            .map(|(id, _)| id);
+        self.tooltip_hover = match (hovered, self.tooltip_hover) {
+            (None, _) => None,
+            // pressing hides the tooltip and restarts the delay
+            (Some(id), _) if self.was_mouse_pressed => Some((id, 0.0)),
+            (Some(id), Some((hover_id, seconds))) if id == hover_id => Some((id, seconds + dt)),
+            (Some(id), _) => Some((id, 0.0)),
+        };
+    }
+
+    //// VISUALS AND ANIMATION
+    pub fn visual(&self, id: u32) -> ElementVisual {
+        self.visuals.get(&id).copied().unwrap_or_default()
+    }
+
+    pub fn set_visual(&mut self, id: u32, visual: ElementVisual) {
+        if visual.is_identity() {
+            self.visuals.remove(&id);
+        } else {
+            self.visuals.insert(id, visual);
+        }
+    }
+
+    pub fn set_tint(&mut self, id: u32, tint: Color) {
+        self.set_visual(
+            id,
+            ElementVisual {
+                tint,
+                ..self.visual(id)
+            },
+        );
+    }
+
+    pub fn set_opacity(&mut self, id: u32, opacity: f32) {
+        self.set_visual(
+            id,
+            ElementVisual {
+                opacity,
+                ..self.visual(id)
+            },
+        );
+    }
+
+    pub fn set_scale(&mut self, id: u32, scale: f32) {
+        self.set_visual(
+            id,
+            ElementVisual {
+                scale,
+                ..self.visual(id)
+            },
+        );
+    }
+
+    /// Bounds of any element. For `MoveAndResizeThumbs` this is the target rect.
+    pub fn element_rect(&self, id: u32) -> Option<Rect> {
+        if let Some(button) = self.get_button(id) {
+            return Some(Rect::new(button.position, button.size));
+        }
+        if let Some(slider) = self.get_slider(id) {
+            return Some(Rect::new(slider.position, slider.size));
+        }
+        if let Some(slider) = self.get_vertical_slider(id) {
+            return Some(Rect::new(slider.position, slider.size));
+        }
+        if let Some(draggable) = self.get_draggable(id) {
+            return Some(Rect::new(draggable.position, draggable.size));
+        }
+        if let Some(label) = self.get_label(id) {
+            return Some(Rect::new(label.position, label.size));
+        }
+        if let Some(selector) = self.get_left_right_selector(id) {
+            return Some(Rect::new(selector.position, selector.size));
+        }
+        if let Some(toggle) = self.get_button_toggle(id) {
+            return Some(Rect::new(toggle.position, toggle.size));
+        }
+        if let Some(thumbs) = self.get_move_and_resize_thumbs(id) {
+            return Some(Rect::new(thumbs.target_position, thumbs.target_size));
+        }
+        if let Some(dropdown) = self.get_dropdown(id) {
+            return Some(Rect::new(dropdown.position, dropdown.size));
+        }
+        if let Some(checkbox) = self.get_checkbox(id) {
+            return Some(Rect::new(checkbox.position, checkbox.size));
+        }
+        if let Some(group) = self.get_radio_group(id) {
+            return Some(Rect::new(group.position, group.size));
+        }
+        if let Some(bar) = self.get_progress_bar(id) {
+            return Some(Rect::new(bar.position, bar.size));
+        }
+        if let Some(list) = self.get_list_view(id) {
+            return Some(Rect::new(list.position, list.size));
+        }
+        if let Some(tree) = self.get_tree_view(id) {
+            return Some(Rect::new(tree.position, tree.size));
+        }
+        if let Some(container) = self.get_tab_container(id) {
+            return Some(Rect::new(container.position, container.size));
+        }
+        if let Some(message_box) = self.get_message_box(id) {
+            return Some(Rect::new(message_box.position, message_box.size));
+        }
+        if let Some(context_menu) = self.get_context_menu(id) {
+            return context_menu.panel_rects().first().copied();
+        }
+        if let Some(menu_bar) = self.get_menu_bar(id) {
+            return Some(Rect::new(menu_bar.position, menu_bar.size));
+        }
+        if let Some(field) = self.get_number_field(id) {
+            return Some(Rect::new(field.position, field.size));
+        }
+        if let Some(picker) = self.get_color_picker(id) {
+            return Some(Rect::new(picker.position, picker.size));
+        }
+        if let Some(pad) = self.get_xy_pad(id) {
+            return Some(Rect::new(pad.position, pad.size));
+        }
+        if let Some(range) = self.get_range_slider(id) {
+            return Some(Rect::new(range.position, range.size));
+        }
+        None
+    }
+
+    /// Move/resize any element, keeping composite widgets' parts in sync.
+    pub fn set_element_rect(&mut self, id: u32, rect: Rect) {
+        if let Some(button) = self.get_button_mut(id) {
+            button.position = rect.position;
+            button.size = rect.size;
+        } else if let Some(slider) = self.get_slider_mut(id) {
+            slider.position = rect.position;
+            slider.size = rect.size;
+        } else if let Some(slider) = self.get_vertical_slider_mut(id) {
+            slider.position = rect.position;
+            slider.size = rect.size;
+        } else if let Some(draggable) = self.get_draggable_mut(id) {
+            draggable.position = rect.position;
+            draggable.size = rect.size;
+        } else if let Some(label) = self.get_label_mut(id) {
+            label.position = rect.position;
+            label.size = rect.size;
+        } else if let Some(selector) = self.get_left_right_selector_mut(id) {
+            selector.set_position(rect.position);
+            selector.set_size(rect.size);
+        } else if let Some(toggle) = self.get_button_toggle_mut(id) {
+            toggle.set_position(rect.position);
+            toggle.set_size(rect.size);
+        } else if let Some(thumbs) = self.get_move_and_resize_thumbs_mut(id) {
+            thumbs.set_target(rect.position, rect.size);
+        } else if let Some(dropdown) = self.get_dropdown_mut(id) {
+            dropdown.set_position(rect.position);
+            dropdown.set_size(rect.size);
+        } else if let Some(checkbox) = self.get_checkbox_mut(id) {
+            checkbox.set_position(rect.position);
+            checkbox.set_size(rect.size);
+        } else if let Some(group) = self.get_radio_group_mut(id) {
+            group.set_position(rect.position);
+            group.set_size(rect.size);
+        } else if let Some(bar) = self.get_progress_bar_mut(id) {
+            bar.position = rect.position;
+            bar.size = rect.size;
+        } else if let Some(list) = self.get_list_view_mut(id) {
+            list.position = rect.position;
+            list.size = rect.size;
+            list.scroll_by(0.0);
+        } else if let Some(tree) = self.get_tree_view_mut(id) {
+            tree.position = rect.position;
+            tree.size = rect.size;
+            tree.scroll_by(0.0);
+        } else if let Some(container) = self.get_tab_container_mut(id) {
+            container.set_position(rect.position);
+            container.set_size(rect.size);
+        } else if let Some(message_box) = self.get_message_box_mut(id) {
+            message_box.set_position(rect.position);
+            message_box.set_size(rect.size);
+        } else if let Some(menu_bar) = self.get_menu_bar_mut(id) {
+            menu_bar.set_position(rect.position);
+            menu_bar.set_size(rect.size);
+        } else if let Some(field) = self.get_number_field_mut(id) {
+            field.set_position(rect.position);
+            field.set_size(rect.size);
+        } else if let Some(picker) = self.get_color_picker_mut(id) {
+            picker.position = rect.position;
+            picker.size = rect.size;
+        } else if let Some(pad) = self.get_xy_pad_mut(id) {
+            pad.position = rect.position;
+            pad.size = rect.size;
+        } else if let Some(range) = self.get_range_slider_mut(id) {
+            range.position = rect.position;
+            range.size = rect.size;
+        }
+    }
+
+    /// Start a tween and return its id. Tweens apply in the order they were added, so a
+    /// later tween on the same property wins.
+    pub fn add_tween(&mut self, tween: Tween) -> u32 {
+        let id = self.next_tween_id;
+        self.next_tween_id += 1;
+        self.tweens.push(ActiveTween {
+            id,
+            tween,
+            start: None,
+            elapsed: 0.0,
+        });
+        id
+    }
+
+    /// Stop a tween where it is, without a `TweenFinished` event.
+    pub fn cancel_tween(&mut self, tween_id: u32) {
+        self.tweens.retain(|tween| tween.id != tween_id);
+    }
+
+    pub fn cancel_tweens_for(&mut self, element_id: u32) {
+        self.tweens
+            .retain(|tween| tween.tween.element_id != element_id);
+    }
+
+    pub fn is_tween_active(&self, tween_id: u32) -> bool {
+        self.tweens.iter().any(|tween| tween.id == tween_id)
+    }
+
+    /// Advance tweens, hover fades, the tooltip delay, progress bar trails and held number
+    /// field arrows by `dt` seconds. Emits `Event::TweenFinished` for each finished tween
+    /// whose element is tagged, and `Event::NumberChanged` for arrow repeats.
+    pub fn update(&mut self, dt: f32) -> Vec<TaggedEvent<T>> {
+        self.update_hover_fades(dt);
+        self.update_tooltip(dt);
+        for bar in self.progress_bars.iter_mut() {
+            bar.update(dt);
+        }
+
+        let mut tagged_events = Vec::new();
+        for field in self.number_fields.iter_mut() {
+            if self.hidden_ids.contains(&field.id) || self.disabled_ids.contains(&field.id) {
+                continue;
+            }
+            if let Some(event) = field.update(dt) {
+                if let Some(tag) = self.el_to_tag_map.get(&field.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: field.id,
+                        event,
+                    });
+                }
+            }
+        }
+        let mut index = 0;
+        while index < self.tweens.len() {
+            let mut active = self.tweens[index];
+            let waiting = active
+                .tween
+                .after
+                .is_some_and(|after| self.is_tween_active(after));
+            if waiting {
+                index += 1;
+                continue;
+            }
+
+            let element_id = active.tween.element_id;
+            let (value, finished) =
+                active.advance(dt, |target| self.tween_property(element_id, target));
+            if let Some(value) = value {
+                self.apply_tween_property(element_id, value);
+            }
+            if finished {
+                self.tweens.remove(index);
+                if let Some(tag) = self.el_to_tag_map.get(&element_id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id,
+                        event: Event::TweenFinished {
+                            tween_id: active.id,
+                        },
+                    });
+                }
+            } else {
+                self.tweens[index] = active;
+                index += 1;
+            }
+        }
+        tagged_events
+    }
+
+    /// Current value of the property `target` animates, or `None` if the element is gone.
+    fn tween_property(&self, id: u32, target: TweenTarget) -> Option<TweenTarget> {
+        let visual = self.visual(id);
+        let rect = self.element_rect(id)?;
+        Some(match target {
+            TweenTarget::Position(_) => TweenTarget::Position(rect.position),
+            TweenTarget::Size(_) => TweenTarget::Size(rect.size),
+            TweenTarget::Tint(_) => TweenTarget::Tint(visual.tint),
+            TweenTarget::Opacity(_) => TweenTarget::Opacity(visual.opacity),
+            TweenTarget::Scale(_) => TweenTarget::Scale(visual.scale),
+        })
+    }
+
+    fn apply_tween_property(&mut self, id: u32, value: TweenTarget) {
+        let Some(rect) = self.element_rect(id) else {
+            return;
+        };
+        match value {
+            TweenTarget::Position(position) => {
+                self.set_element_rect(id, Rect::new(position, rect.size))
+            }
+            TweenTarget::Size(size) => self.set_element_rect(id, Rect::new(rect.position, size)),
+            TweenTarget::Tint(tint) => self.set_tint(id, tint),
+            TweenTarget::Opacity(opacity) => self.set_opacity(id, opacity),
+            TweenTarget::Scale(scale) => self.set_scale(id, scale),
+        }
+    }
+
+    fn update_hover_fades(&mut self, dt: f32) {
+        if self.hover_fade_seconds <= 0.0 {
+            self.hover_fades.clear();
+            return;
+        }
+        let mut hovered = Vec::new();
+        for button in &self.buttons {
+            hovered.push((button.id, button.hovered));
+        }
+        for slider in &self.sliders {
+            hovered.push((slider.id, slider.hovered));
+        }
+        for slider in &self.vertical_sliders {
+            hovered.push((slider.id, slider.hovered));
+        }
+        for draggable in &self.draggables {
+            hovered.push((draggable.id, draggable.hovered));
+        }
+        for selector in &self.left_right_selectors {
+            hovered.push((selector.left_button.id, selector.left_button.hovered));
+            hovered.push((selector.right_button.id, selector.right_button.hovered));
+        }
+        for toggle in &self.button_toggles {
+            hovered.push((toggle.left_button.id, toggle.left_button.hovered));
+            hovered.push((toggle.right_button.id, toggle.right_button.hovered));
+        }
+        for thumbs in &self.move_and_resize_thumbs {
+            hovered.push((thumbs.move_thumb.id, thumbs.move_thumb.hovered));
+            hovered.push((thumbs.resize_thumb.id, thumbs.resize_thumb.hovered));
+        }
+        for dropdown in &self.dropdowns {
+            hovered.push((dropdown.head_button.id, dropdown.head_button.hovered));
+        }
+        for checkbox in &self.checkboxes {
+            hovered.push((checkbox.button.id, checkbox.button.hovered));
+        }
+        for group in &self.radio_groups {
+            for button in &group.option_buttons {
+                hovered.push((button.id, button.hovered));
+            }
+        }
+        for container in &self.tab_containers {
+            for button in &container.tab_buttons {
+                hovered.push((button.id, button.hovered));
+            }
+            hovered.push((
+                container.scroll_left_button.id,
+                container.scroll_left_button.hovered,
+            ));
+            hovered.push((
+                container.scroll_right_button.id,
+                container.scroll_right_button.hovered,
+            ));
+        }
+        for message_box in &self.message_boxes {
+            for button in &message_box.buttons {
+                hovered.push((button.id, button.hovered));
+            }
+        }
+        for menu_bar in &self.menu_bars {
+            for button in &menu_bar.title_buttons {
+                hovered.push((button.id, button.hovered));
+            }
+        }
+        for field in &self.number_fields {
+            hovered.push((field.increment_button.id, field.increment_button.hovered));
+            hovered.push((field.decrement_button.id, field.decrement_button.hovered));
+            hovered.push((field.id, field.hovered));
+        }
+        for picker in &self.color_pickers {
+            hovered.push((picker.id, picker.hex_hovered));
+        }
+        for pad in &self.xy_pads {
+            hovered.push((pad.id, pad.hovered));
+        }
+        for range in &self.range_sliders {
+            hovered.push((range.id, range.hovered_part.is_some()));
+        }
+
+        let rate = dt / self.hover_fade_seconds;
+        let mut fades = HashMap::new();
+        for (id, is_hovered) in hovered {
+            let fade = self.hover_fades.get(&id).copied().unwrap_or(0.0);
+            let fade = if is_hovered {
+                (fade + rate).min(1.0)
+            } else {
+                (fade - rate).max(0.0)
+            };
+            if fade > 0.0 {
+                fades.insert(id, fade);
+            }
+        }
+        self.hover_fades = fades;
+    }
+
     //// REMOVE ELEMENTS
     pub fn remove_button(&mut self, id: u32) {
         self.buttons.retain(|button| button.id != id);
         self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     pub fn remove_slider(&mut self, id: u32) {
         self.sliders.retain(|slider| slider.id != id);
         self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     pub fn remove_vertical_slider(&mut self, id: u32) {
@@ -118,17 +1175,24 @@ impl<T: Clone + Copy> Gui<T> {
             .retain(|vertical_slider| vertical_slider.id != id);
         self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     pub fn remove_draggable(&mut self, id: u32) {
         self.draggables.retain(|draggable| draggable.id != id);
         self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     pub fn remove_label(&mut self, id: u32) {
         self.labels.retain(|label| label.id != id);
+        self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     pub fn remove_left_right_selector(&mut self, id: u32) {
@@ -136,18 +1200,153 @@ impl<T: Clone + Copy> Gui<T> {
             .retain(|selector| selector.id != id);
         self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     pub fn remove_button_toggle(&mut self, id: u32) {
         self.button_toggles.retain(|toggle| toggle.id != id);
         self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     pub fn remove_move_and_resize_thumbs(&mut self, id: u32) {
         self.move_and_resize_thumbs.retain(|thumbs| thumbs.id != id);
         self.el_to_tag_map.remove(&id);
         self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_dropdown(&mut self, id: u32) {
+        self.dropdowns.retain(|dropdown| dropdown.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_checkbox(&mut self, id: u32) {
+        self.checkboxes.retain(|checkbox| checkbox.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_radio_group(&mut self, id: u32) {
+        self.radio_groups.retain(|group| group.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_progress_bar(&mut self, id: u32) {
+        self.progress_bars.retain(|bar| bar.id != id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_list_view(&mut self, id: u32) {
+        self.list_views.retain(|list| list.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_tree_view(&mut self, id: u32) {
+        self.tree_views.retain(|tree| tree.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    /// Page elements stay in the `Gui` and are shown again, unless another container
+    /// hides them.
+    pub fn remove_tab_container(&mut self, id: u32) {
+        let page_ids: Vec<u32> = self
+            .get_tab_container(id)
+            .map(|container| {
+                container
+                    .tabs
+                    .iter()
+                    .flat_map(|tab| tab.page_ids.iter().copied())
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.tab_containers.retain(|container| container.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+        for page_id in page_ids {
+            self.reset_visibility(page_id);
+        }
+    }
+
+    pub fn remove_message_box(&mut self, id: u32) {
+        self.message_boxes
+            .retain(|message_box| message_box.id != id);
+        self.close_modal_of(id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_context_menu(&mut self, id: u32) {
+        self.context_menus
+            .retain(|context_menu| context_menu.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_menu_bar(&mut self, id: u32) {
+        self.menu_bars.retain(|menu_bar| menu_bar.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_number_field(&mut self, id: u32) {
+        self.number_fields.retain(|field| field.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_color_picker(&mut self, id: u32) {
+        self.color_pickers.retain(|picker| picker.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_xy_pad(&mut self, id: u32) {
+        self.xy_pads.retain(|pad| pad.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
+    }
+
+    pub fn remove_range_slider(&mut self, id: u32) {
+        self.range_sliders.retain(|range| range.id != id);
+        self.el_to_tag_map.remove(&id);
+        self.hidden_ids.remove(&id);
+        self.disabled_ids.remove(&id);
+        self.clear_element_state(id);
     }
 
     //// GET ELEMENTS
@@ -173,20 +1372,82 @@ impl<T: Clone + Copy> Gui<T> {
         self.labels.iter().find(|label| label.id == id)
     }
 
-    pub fn get_left_right_selector(&self, id: u32) -> Option<&LeftRightSelector> {
-        self.left_right_selectors
-            .iter()
-            .find(|selector| selector.id == id)
+    pub fn get_left_right_selector(&self, id: u32) -> Option<&LeftRightSelector> {
+        self.left_right_selectors
+            .iter()
+            .find(|selector| selector.id == id)
+    }
+
+    pub fn get_button_toggle(&self, id: u32) -> Option<&ButtonToggle> {
+        self.button_toggles.iter().find(|toggle| toggle.id == id)
+    }
+
+    pub fn get_move_and_resize_thumbs(&self, id: u32) -> Option<&MoveAndResizeThumbs> {
+        self.move_and_resize_thumbs
+            .iter()
+            .find(|thumbs| thumbs.id == id)
+    }
+
+    pub fn get_dropdown(&self, id: u32) -> Option<&Dropdown> {
+        self.dropdowns.iter().find(|dropdown| dropdown.id == id)
+    }
+
+    pub fn get_checkbox(&self, id: u32) -> Option<&Checkbox> {
+        self.checkboxes.iter().find(|checkbox| checkbox.id == id)
+    }
+
+    pub fn get_radio_group(&self, id: u32) -> Option<&RadioGroup> {
+        self.radio_groups.iter().find(|group| group.id == id)
+    }
+
+    pub fn get_progress_bar(&self, id: u32) -> Option<&ProgressBar> {
+        self.progress_bars.iter().find(|bar| bar.id == id)
+    }
+
+    pub fn get_list_view(&self, id: u32) -> Option<&ListView> {
+        self.list_views.iter().find(|list| list.id == id)
+    }
+
+    pub fn get_tree_view(&self, id: u32) -> Option<&TreeView> {
+        self.tree_views.iter().find(|tree| tree.id == id)
+    }
+
+    pub fn get_tab_container(&self, id: u32) -> Option<&TabContainer> {
+        self.tab_containers
+            .iter()
+            .find(|container| container.id == id)
+    }
+
+    pub fn get_message_box(&self, id: u32) -> Option<&MessageBox> {
+        self.message_boxes
+            .iter()
+            .find(|message_box| message_box.id == id)
+    }
+
+    pub fn get_context_menu(&self, id: u32) -> Option<&ContextMenu> {
+        self.context_menus
+            .iter()
+            .find(|context_menu| context_menu.id == id)
+    }
+
+    pub fn get_menu_bar(&self, id: u32) -> Option<&MenuBar> {
+        self.menu_bars.iter().find(|menu_bar| menu_bar.id == id)
+    }
+
+    pub fn get_number_field(&self, id: u32) -> Option<&NumberField> {
+        self.number_fields.iter().find(|field| field.id == id)
+    }
+
+    pub fn get_color_picker(&self, id: u32) -> Option<&ColorPicker> {
+        self.color_pickers.iter().find(|picker| picker.id == id)
     }
 
-    pub fn get_button_toggle(&self, id: u32) -> Option<&ButtonToggle> {
-        self.button_toggles.iter().find(|toggle| toggle.id == id)
+    pub fn get_xy_pad(&self, id: u32) -> Option<&XYPad> {
+        self.xy_pads.iter().find(|pad| pad.id == id)
     }
 
-    pub fn get_move_and_resize_thumbs(&self, id: u32) -> Option<&MoveAndResizeThumbs> {
-        self.move_and_resize_thumbs
-            .iter()
-            .find(|thumbs| thumbs.id == id)
+    pub fn get_range_slider(&self, id: u32) -> Option<&RangeSlider> {
+        self.range_sliders.iter().find(|range| range.id == id)
     }
 
     //// GET ELEMENTS MUT
@@ -232,14 +1493,207 @@ impl<T: Clone + Copy> Gui<T> {
             .find(|thumbs| thumbs.id == id)
     }
 
+    pub fn get_dropdown_mut(&mut self, id: u32) -> Option<&mut Dropdown> {
+        self.dropdowns.iter_mut().find(|dropdown| dropdown.id == id)
+    }
+
+    pub fn get_checkbox_mut(&mut self, id: u32) -> Option<&mut Checkbox> {
+        self.checkboxes
+            .iter_mut()
+            .find(|checkbox| checkbox.id == id)
+    }
+
+    pub fn get_radio_group_mut(&mut self, id: u32) -> Option<&mut RadioGroup> {
+        self.radio_groups.iter_mut().find(|group| group.id == id)
+    }
+
+    pub fn get_progress_bar_mut(&mut self, id: u32) -> Option<&mut ProgressBar> {
+        self.progress_bars.iter_mut().find(|bar| bar.id == id)
+    }
+
+    pub fn get_list_view_mut(&mut self, id: u32) -> Option<&mut ListView> {
+        self.list_views.iter_mut().find(|list| list.id == id)
+    }
+
+    pub fn get_tree_view_mut(&mut self, id: u32) -> Option<&mut TreeView> {
+        self.tree_views.iter_mut().find(|tree| tree.id == id)
+    }
+
+    pub fn get_tab_container_mut(&mut self, id: u32) -> Option<&mut TabContainer> {
+        self.tab_containers
+            .iter_mut()
+            .find(|container| container.id == id)
+    }
+
+    pub fn get_message_box_mut(&mut self, id: u32) -> Option<&mut MessageBox> {
+        self.message_boxes
+            .iter_mut()
+            .find(|message_box| message_box.id == id)
+    }
+
+    pub fn get_context_menu_mut(&mut self, id: u32) -> Option<&mut ContextMenu> {
+        self.context_menus
+            .iter_mut()
+            .find(|context_menu| context_menu.id == id)
+    }
+
+    pub fn get_menu_bar_mut(&mut self, id: u32) -> Option<&mut MenuBar> {
+        self.menu_bars.iter_mut().find(|menu_bar| menu_bar.id == id)
+    }
+
+    pub fn get_number_field_mut(&mut self, id: u32) -> Option<&mut NumberField> {
+        self.number_fields.iter_mut().find(|field| field.id == id)
+    }
+
+    pub fn get_color_picker_mut(&mut self, id: u32) -> Option<&mut ColorPicker> {
+        self.color_pickers.iter_mut().find(|picker| picker.id == id)
+    }
+
+    pub fn get_xy_pad_mut(&mut self, id: u32) -> Option<&mut XYPad> {
+        self.xy_pads.iter_mut().find(|pad| pad.id == id)
+    }
+
+    pub fn get_range_slider_mut(&mut self, id: u32) -> Option<&mut RangeSlider> {
+        self.range_sliders.iter_mut().find(|range| range.id == id)
+    }
+
     /// Step the gui using mouse coordinates in this gui's pixel space.
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Vec<TaggedEvent<T>> {
         let mut tagged_events = Vec::new();
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+        self.mouse_position = mouse_position;
+        let mut disabled_clicks = Vec::new();
+        let mut changed_tab_containers = Vec::new();
+        let mut closed_message_boxes = Vec::new();
+
+        // an open popup takes the mouse, and a press that closes it doesn't reach the
+        // widgets underneath
+        let popup_open = self.has_open_popup();
+        if !mouse_pressed {
+            self.block_mouse_until_release = false;
+        }
+        let widget_mouse = if popup_open || self.block_mouse_until_release {
+            Vec2::new(-1.0, -1.0)
+        } else {
+            mouse_position
+        };
+        // only the top modal's elements get the mouse
+        let top_modal = self.modal_layers.last().cloned();
+        let mouse_for = |id: u32| match &top_modal {
+            Some(modal_ids) if !modal_ids.contains(&id) => Vec2::new(-1.0, -1.0),
+            _ => widget_mouse,
+        };
+        for dropdown in self.dropdowns.iter_mut() {
+            if self.hidden_ids.contains(&dropdown.id) {
+                dropdown.open = false;
+                continue;
+            }
+            let widget_mouse = mouse_for(dropdown.id);
+            if self.disabled_ids.contains(&dropdown.id) {
+                dropdown.cancel_press();
+                if press_started
+                    && Rect::new(dropdown.position, dropdown.size).contains(widget_mouse)
+                {
+                    disabled_clicks.push(dropdown.id);
+                }
+                continue;
+            }
+            let was_open = dropdown.open;
+            let mouse = if was_open {
+                mouse_position
+            } else {
+                widget_mouse
+            };
+            let event = dropdown.step(mouse, mouse_pressed, self.bounds);
+            if dropdown.open && !was_open {
+                self.focused_id = Some(dropdown.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&dropdown.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: dropdown.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for context_menu in self.context_menus.iter_mut() {
+            if self.hidden_ids.contains(&context_menu.id)
+                || self.disabled_ids.contains(&context_menu.id)
+            {
+                context_menu.close();
+                continue;
+            }
+            let Some(event) = context_menu.step(mouse_position, mouse_pressed) else {
+                continue;
+            };
+            if let (Some(tag), Some(target)) = (
+                self.el_to_tag_map.get(&context_menu.id),
+                context_menu.opened_on,
+            ) {
+                tagged_events.push(TaggedEvent {
+                    tag: *tag,
+                    element_id: target,
+                    event,
+                });
+            }
+        }
+        for menu_bar in self.menu_bars.iter_mut() {
+            if self.hidden_ids.contains(&menu_bar.id) {
+                menu_bar.close();
+                continue;
+            }
+            let widget_mouse = mouse_for(menu_bar.id);
+            if self.disabled_ids.contains(&menu_bar.id) {
+                menu_bar.cancel_press();
+                if press_started
+                    && Rect::new(menu_bar.position, menu_bar.size).contains(widget_mouse)
+                {
+                    disabled_clicks.push(menu_bar.id);
+                }
+                continue;
+            }
+            let event = {
+                // an open menu takes the mouse like other popups
+                let mouse = if menu_bar.is_open() {
+                    mouse_position
+                } else {
+                    widget_mouse
+                };
+                menu_bar.step(mouse, mouse_pressed, self.bounds)
+            };
+            if menu_bar.is_open() {
+                self.focused_id = Some(menu_bar.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&menu_bar.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: menu_bar.id,
+                        event,
+                    });
+                }
+            }
+        }
+        if popup_open && mouse_pressed {
+            self.block_mouse_until_release = true;
+        }
+
         for button in self.buttons.iter_mut() {
             if self.hidden_ids.contains(&button.id) {
                 continue;
             }
-            if let Some(event) = button.step(mouse_position, mouse_pressed) {
+            let widget_mouse = mouse_for(button.id);
+            if self.disabled_ids.contains(&button.id) {
+                button.cancel_press();
+                if press_started && Rect::new(button.position, button.size).contains(widget_mouse) {
+                    disabled_clicks.push(button.id);
+                }
+                continue;
+            }
+            if let Some(event) = button.step(widget_mouse, mouse_pressed) {
                 if let Some(tag) = self.el_to_tag_map.get(&button.id) {
                     tagged_events.push(TaggedEvent {
                         tag: *tag,
@@ -253,7 +1707,15 @@ impl<T: Clone + Copy> Gui<T> {
             if self.hidden_ids.contains(&slider.id) {
                 continue;
             }
-            if let Some(event) = slider.step(mouse_position, mouse_pressed) {
+            let widget_mouse = mouse_for(slider.id);
+            if self.disabled_ids.contains(&slider.id) {
+                slider.cancel_press();
+                if press_started && Rect::new(slider.position, slider.size).contains(widget_mouse) {
+                    disabled_clicks.push(slider.id);
+                }
+                continue;
+            }
+            if let Some(event) = slider.step(widget_mouse, mouse_pressed) {
                 if let Some(tag) = self.el_to_tag_map.get(&slider.id) {
                     tagged_events.push(TaggedEvent {
                         tag: *tag,
@@ -267,7 +1729,18 @@ impl<T: Clone + Copy> Gui<T> {
             if self.hidden_ids.contains(&vertical_slider.id) {
                 continue;
             }
-            if let Some(event) = vertical_slider.step(mouse_position, mouse_pressed) {
+            let widget_mouse = mouse_for(vertical_slider.id);
+            if self.disabled_ids.contains(&vertical_slider.id) {
+                vertical_slider.cancel_press();
+                if press_started
+                    && Rect::new(vertical_slider.position, vertical_slider.size)
+                        .contains(widget_mouse)
+                {
+                    disabled_clicks.push(vertical_slider.id);
+                }
+                continue;
+            }
+            if let Some(event) = vertical_slider.step(widget_mouse, mouse_pressed) {
                 if let Some(tag) = self.el_to_tag_map.get(&vertical_slider.id) {
                     tagged_events.push(TaggedEvent {
                         tag: *tag,
@@ -281,7 +1754,17 @@ impl<T: Clone + Copy> Gui<T> {
             if self.hidden_ids.contains(&draggable.id) {
                 continue;
             }
-            if let Some(event) = draggable.step(mouse_position, mouse_pressed) {
+            let widget_mouse = mouse_for(draggable.id);
+            if self.disabled_ids.contains(&draggable.id) {
+                draggable.cancel_press();
+                if press_started
+                    && Rect::new(draggable.position, draggable.size).contains(widget_mouse)
+                {
+                    disabled_clicks.push(draggable.id);
+                }
+                continue;
+            }
+            if let Some(event) = draggable.step(widget_mouse, mouse_pressed) {
                 if let Some(tag) = self.el_to_tag_map.get(&draggable.id) {
                     tagged_events.push(TaggedEvent {
                         tag: *tag,
@@ -291,11 +1774,43 @@ impl<T: Clone + Copy> Gui<T> {
                 }
             }
         }
+        for label in self.labels.iter_mut() {
+            if self.hidden_ids.contains(&label.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(label.id);
+            if self.disabled_ids.contains(&label.id) {
+                label.cancel_press();
+                if press_started && Rect::new(label.position, label.size).contains(widget_mouse) {
+                    disabled_clicks.push(label.id);
+                }
+                continue;
+            }
+            if let Some(event) = label.step(widget_mouse, mouse_pressed) {
+                if let Some(tag) = self.el_to_tag_map.get(&label.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: label.id,
+                        event,
+                    });
+                }
+            }
+        }
         for selector in self.left_right_selectors.iter_mut() {
             if self.hidden_ids.contains(&selector.id) {
                 continue;
             }
-            if let Some(event) = selector.step(mouse_position, mouse_pressed) {
+            let widget_mouse = mouse_for(selector.id);
+            if self.disabled_ids.contains(&selector.id) {
+                selector.cancel_press();
+                if press_started
+                    && Rect::new(selector.position, selector.size).contains(widget_mouse)
+                {
+                    disabled_clicks.push(selector.id);
+                }
+                continue;
+            }
+            if let Some(event) = selector.step(widget_mouse, mouse_pressed) {
                 if let Some(tag) = self.el_to_tag_map.get(&selector.id) {
                     tagged_events.push(TaggedEvent {
                         tag: *tag,
@@ -309,7 +1824,15 @@ impl<T: Clone + Copy> Gui<T> {
             if self.hidden_ids.contains(&toggle.id) {
                 continue;
             }
-            if let Some(event) = toggle.step(mouse_position, mouse_pressed) {
+            let widget_mouse = mouse_for(toggle.id);
+            if self.disabled_ids.contains(&toggle.id) {
+                toggle.cancel_press();
+                if press_started && Rect::new(toggle.position, toggle.size).contains(widget_mouse) {
+                    disabled_clicks.push(toggle.id);
+                }
+                continue;
+            }
+            if let Some(event) = toggle.step(widget_mouse, mouse_pressed) {
                 if let Some(tag) = self.el_to_tag_map.get(&toggle.id) {
                     tagged_events.push(TaggedEvent {
                         tag: *tag,
@@ -323,7 +1846,20 @@ impl<T: Clone + Copy> Gui<T> {
             if self.hidden_ids.contains(&thumbs.id) {
                 continue;
             }
-            if let Some(event) = thumbs.step(mouse_position, mouse_pressed) {
+            let widget_mouse = mouse_for(thumbs.id);
+            if self.disabled_ids.contains(&thumbs.id) {
+                thumbs.cancel_press();
+                if press_started
+                    && (Rect::new(thumbs.move_thumb.position, thumbs.move_thumb.size)
+                        .contains(widget_mouse)
+                        || Rect::new(thumbs.resize_thumb.position, thumbs.resize_thumb.size)
+                            .contains(widget_mouse))
+                {
+                    disabled_clicks.push(thumbs.id);
+                }
+                continue;
+            }
+            if let Some(event) = thumbs.step(widget_mouse, mouse_pressed) {
                 if let Some(tag) = self.el_to_tag_map.get(&thumbs.id) {
                     tagged_events.push(TaggedEvent {
                         tag: *tag,
@@ -333,6 +1869,281 @@ impl<T: Clone + Copy> Gui<T> {
                 }
             }
         }
+        for checkbox in self.checkboxes.iter_mut() {
+            if self.hidden_ids.contains(&checkbox.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(checkbox.id);
+            if self.disabled_ids.contains(&checkbox.id) {
+                checkbox.cancel_press();
+                if press_started
+                    && Rect::new(checkbox.position, checkbox.size).contains(widget_mouse)
+                {
+                    disabled_clicks.push(checkbox.id);
+                }
+                continue;
+            }
+            let event = checkbox.step(widget_mouse, mouse_pressed);
+            if checkbox.button.pressed {
+                self.focused_id = Some(checkbox.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&checkbox.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: checkbox.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for group in self.radio_groups.iter_mut() {
+            if self.hidden_ids.contains(&group.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(group.id);
+            if self.disabled_ids.contains(&group.id) {
+                group.cancel_press();
+                if press_started && Rect::new(group.position, group.size).contains(widget_mouse) {
+                    disabled_clicks.push(group.id);
+                }
+                continue;
+            }
+            let event = group.step(widget_mouse, mouse_pressed);
+            if group.option_buttons.iter().any(|button| button.pressed) {
+                self.focused_id = Some(group.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&group.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: group.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for list in self.list_views.iter_mut() {
+            if self.hidden_ids.contains(&list.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(list.id);
+            if self.disabled_ids.contains(&list.id) {
+                list.cancel_press();
+                if press_started && Rect::new(list.position, list.size).contains(widget_mouse) {
+                    disabled_clicks.push(list.id);
+                }
+                continue;
+            }
+            let event = list.step(widget_mouse, mouse_pressed, self.modifiers);
+            if list.pressed_row.is_some() {
+                self.focused_id = Some(list.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&list.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: list.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for tree in self.tree_views.iter_mut() {
+            if self.hidden_ids.contains(&tree.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(tree.id);
+            if self.disabled_ids.contains(&tree.id) {
+                tree.cancel_press();
+                if press_started && Rect::new(tree.position, tree.size).contains(widget_mouse) {
+                    disabled_clicks.push(tree.id);
+                }
+                continue;
+            }
+            let events = tree.step(widget_mouse, mouse_pressed);
+            if press_started && tree.hovered_row.is_some() {
+                self.focused_id = Some(tree.id);
+            }
+            for event in events {
+                if let Some(tag) = self.el_to_tag_map.get(&tree.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: tree.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for container in self.tab_containers.iter_mut() {
+            if self.hidden_ids.contains(&container.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(container.id);
+            if self.disabled_ids.contains(&container.id) {
+                container.cancel_press();
+                if press_started
+                    && Rect::new(container.position, container.size).contains(widget_mouse)
+                {
+                    disabled_clicks.push(container.id);
+                }
+                continue;
+            }
+            let event = container.step(widget_mouse, mouse_pressed);
+            if matches!(event, Some(Event::TabChanged { .. })) {
+                changed_tab_containers.push(container.id);
+            }
+            if container.tab_buttons.iter().any(|button| button.pressed) {
+                self.focused_id = Some(container.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&container.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: container.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for message_box in self.message_boxes.iter_mut() {
+            if self.hidden_ids.contains(&message_box.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(message_box.id);
+            if self.disabled_ids.contains(&message_box.id) {
+                message_box.cancel_press();
+                if press_started
+                    && Rect::new(message_box.position, message_box.size).contains(widget_mouse)
+                {
+                    disabled_clicks.push(message_box.id);
+                }
+                continue;
+            }
+            if let Some(event) = message_box.step(widget_mouse, mouse_pressed) {
+                closed_message_boxes.push(message_box.id);
+                if let Some(tag) = self.el_to_tag_map.get(&message_box.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: message_box.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for id in changed_tab_containers {
+            self.sync_tab_pages(id);
+        }
+        for id in closed_message_boxes {
+            self.remove_message_box(id);
+        }
+        for field in self.number_fields.iter_mut() {
+            if self.hidden_ids.contains(&field.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(field.id);
+            if self.disabled_ids.contains(&field.id) {
+                field.cancel_press();
+                if press_started && Rect::new(field.position, field.size).contains(widget_mouse) {
+                    disabled_clicks.push(field.id);
+                }
+                continue;
+            }
+            let event = field.step(widget_mouse, mouse_pressed, self.modifiers);
+            if field.press.is_some() || field.repeat.is_some() {
+                self.focused_id = Some(field.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&field.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: field.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for picker in self.color_pickers.iter_mut() {
+            if self.hidden_ids.contains(&picker.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(picker.id);
+            if self.disabled_ids.contains(&picker.id) {
+                picker.cancel_press();
+                if press_started && Rect::new(picker.position, picker.size).contains(widget_mouse) {
+                    disabled_clicks.push(picker.id);
+                }
+                continue;
+            }
+            let event = picker.step(widget_mouse, mouse_pressed);
+            if press_started && picker.hovered {
+                self.focused_id = Some(picker.id);
+            }
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&picker.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: picker.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for pad in self.xy_pads.iter_mut() {
+            if self.hidden_ids.contains(&pad.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(pad.id);
+            if self.disabled_ids.contains(&pad.id) {
+                pad.cancel_press();
+                if press_started && Rect::new(pad.position, pad.size).contains(widget_mouse) {
+                    disabled_clicks.push(pad.id);
+                }
+                continue;
+            }
+            let event = pad.step(widget_mouse, mouse_pressed);
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&pad.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: pad.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for range in self.range_sliders.iter_mut() {
+            if self.hidden_ids.contains(&range.id) {
+                continue;
+            }
+            let widget_mouse = mouse_for(range.id);
+            if self.disabled_ids.contains(&range.id) {
+                range.cancel_press();
+                if press_started && Rect::new(range.position, range.size).contains(widget_mouse) {
+                    disabled_clicks.push(range.id);
+                }
+                continue;
+            }
+            let event = range.step(widget_mouse, mouse_pressed);
+            if let Some(event) = event {
+                if let Some(tag) = self.el_to_tag_map.get(&range.id) {
+                    tagged_events.push(TaggedEvent {
+                        tag: *tag,
+                        element_id: range.id,
+                        event,
+                    });
+                }
+            }
+        }
+        for id in disabled_clicks {
+            if let Some(tag) = self.el_to_tag_map.get(&id) {
+                tagged_events.push(TaggedEvent {
+                    tag: *tag,
+                    element_id: id,
+                    event: Event::DisabledClicked,
+                });
+            }
+        }
         tagged_events
     }
 
@@ -371,3 +2182,71 @@ impl<T: Clone + Copy> Default for Gui<T> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tab;
+
+    fn tabs(pages: [Vec<u32>; 2]) -> TabContainer {
+        let [a, b] = pages;
+        TabContainer::new(
+            Vec2::ZERO,
+            Vec2::new(200.0, 100.0),
+            20.0,
+            60.0,
+            vec![
+                Tab::new("a").with_page_ids(a),
+                Tab::new("b").with_page_ids(b),
+            ],
+        )
+    }
+
+    #[test]
+    fn elements_added_after_their_tab_container_follow_its_page() {
+        let mut gui = Gui::new();
+        let shown = Button::new(Vec2::ZERO, Vec2::ONE, None);
+        let hidden = Button::new(Vec2::ZERO, Vec2::ONE, None);
+        let (shown_id, hidden_id) = (shown.id, hidden.id);
+        gui.add_tab_container(tabs([vec![shown_id], vec![hidden_id]]), 0);
+        gui.add_button(shown, 1);
+        gui.add_button(hidden, 2);
+        assert!(gui.is_visible(shown_id));
+        assert!(!gui.is_visible(hidden_id));
+    }
+
+    #[test]
+    fn tab_containers_on_their_own_pages_do_not_recurse() {
+        let mut gui = Gui::new();
+        let outer = tabs([vec![], vec![]]);
+        let inner = tabs([vec![], vec![]]);
+        let (outer_id, inner_id) = (outer.id, inner.id);
+        gui.add_tab_container(outer, 0);
+        gui.add_tab_container(inner, 1);
+        if let Some(outer) = gui.get_tab_container_mut(outer_id) {
+            outer.tabs[0].page_ids = vec![outer_id, inner_id];
+        }
+        if let Some(inner) = gui.get_tab_container_mut(inner_id) {
+            inner.tabs[0].page_ids = vec![outer_id];
+        }
+        gui.sync_tab_pages(outer_id);
+        gui.set_visible(outer_id, false);
+        assert!(!gui.is_visible(inner_id));
+        gui.set_visible(outer_id, true);
+        assert!(gui.is_visible(inner_id));
+    }
+
+    #[test]
+    fn removing_a_tab_container_shows_its_pages() {
+        let mut gui = Gui::new();
+        let button = Button::new(Vec2::ZERO, Vec2::ONE, None);
+        let button_id = button.id;
+        gui.add_button(button, 1);
+        let container = tabs([vec![], vec![button_id]]);
+        let container_id = container.id;
+        gui.add_tab_container(container, 0);
+        assert!(!gui.is_visible(button_id));
+        gui.remove_tab_container(container_id);
+        assert!(gui.is_visible(button_id));
+    }
+}
@@ -2,7 +2,9 @@ use std::sync::atomic::Ordering;
 
 use glam::Vec2;
 
-use super::{ImageStyle, ELEMENT_NEXT_ID};
+use super::{
+    Event, FontId, ImageStyle, Rect, RichLayout, RichText, StyleOverride, TextRole, ELEMENT_NEXT_ID,
+};
 
 pub struct Label {
     pub id: u32,
@@ -10,7 +12,29 @@ pub struct Label {
     pub size: Vec2,
 
     pub text: Option<String>,
+    /// Markup text drawn instead of `text` when set. Always wraps at the label width.
+    pub rich_text: Option<RichText>,
     pub background_image: Option<ImageStyle>,
+    /// Theme font role used when no per-label font is set.
+    pub text_role: TextRole,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+
+    /// Wrap text at the label width. Explicit newlines always start a new line.
+    pub wrap: bool,
+    /// Extra pixels between wrapped lines.
+    pub line_spacing_px: f32,
+    /// Grow the drawn height to fit the text when it doesn't fit in `size.y`.
+    pub auto_height: bool,
+
+    pub hovered_link: Option<usize>,
+    pub pressed_link: Option<usize>,
+    pub was_pressed: bool,
+    /// Link rects relative to `position` with their link index, set by
+    /// `Gui::layout_text` and hit-tested in `step`.
+    pub link_rects: Vec<(Rect, usize)>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -22,11 +46,112 @@ impl Label {
             size,
 
             text,
+            rich_text: None,
             background_image: None,
+            text_role: TextRole::Body,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+
+            wrap: false,
+            line_spacing_px: 2.0,
+            auto_height: false,
+
+            hovered_link: None,
+            pressed_link: None,
+            was_pressed: false,
+            link_rects: Vec::new(),
         }
     }
 
+    pub fn set_rich_text(&mut self, markup: &str) {
+        self.rich_text = Some(RichText::parse(markup));
+        self.link_rects.clear();
+    }
+
+    pub fn link_target(&self, link_index: usize) -> Option<&str> {
+        self.rich_text
+            .as_ref()?
+            .links
+            .get(link_index)
+            .map(String::as_str)
+    }
+
     pub fn set_background_image(&mut self, image: ImageStyle) {
         self.background_image = Some(image);
     }
+
+    pub fn set_text_role(&mut self, text_role: TextRole) {
+        self.text_role = text_role;
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    pub fn set_auto_height(&mut self, auto_height: bool) {
+        self.auto_height = auto_height;
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.hovered_link = None;
+        self.pressed_link = None;
+    }
+
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        let mut event: Option<Event> = None;
+
+        let local_mouse = mouse_position - self.position;
+        self.hovered_link = self
+            .link_rects
+            .iter()
+            .find(|(rect, _)| rect.contains(local_mouse))
+            .map(|(_, link_index)| *link_index);
+
+        if mouse_pressed && !self.was_pressed {
+            self.pressed_link = self.hovered_link;
+        }
+
+        if !mouse_pressed && self.was_pressed {
+            // click completes only when released over the link it started on
+            if let Some(link_index) = self.pressed_link.take() {
+                if self.hovered_link == Some(link_index) {
+                    event = Some(Event::LinkClicked { link_index });
+                }
+            }
+        }
+        self.was_pressed = mouse_pressed;
+
+        event
+    }
+
+    /// Keep the link rects of `layout`, laid out at the label's current position.
+    pub fn set_link_rects(&mut self, layout: &RichLayout) {
+        self.link_rects = layout
+            .items
+            .iter()
+            .filter_map(|item| {
+                let link_index = item.link?;
+                let rect = Rect::new(item.rect.position - self.position, item.rect.size);
+                Some((rect, link_index))
+            })
+            .collect();
+    }
 }
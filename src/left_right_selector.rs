@@ -2,7 +2,7 @@ use std::sync::atomic::Ordering;
 
 use glam::Vec2;
 
-use super::{Button, Event, ELEMENT_NEXT_ID};
+use super::{Button, Event, FontId, StyleOverride, ELEMENT_NEXT_ID};
 
 pub struct LeftRightSelector {
     pub id: u32,
@@ -11,6 +11,10 @@ pub struct LeftRightSelector {
     pub button_width: f32,
     pub options: Vec<String>,
     pub selected_option_index: usize,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
     pub left_button: Button,
     pub right_button: Button,
 }
@@ -36,6 +40,10 @@ impl LeftRightSelector {
             button_width,
             options,
             selected_option_index,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
             left_button: Button::new(position, Vec2::ZERO, Some("<".to_string())),
             right_button: Button::new(position, Vec2::ZERO, Some(">".to_string())),
         };
@@ -49,6 +57,22 @@ impl LeftRightSelector {
             .map(String::as_str)
     }
 
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
     pub fn set_position(&mut self, position: Vec2) {
         self.position = position;
         self.sync_internal_buttons();
@@ -59,6 +83,11 @@ impl LeftRightSelector {
         self.sync_internal_buttons();
     }
 
+    pub fn cancel_press(&mut self) {
+        self.left_button.cancel_press();
+        self.right_button.cancel_press();
+    }
+
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
         let left_event = self.left_button.step(mouse_position, mouse_pressed);
         let right_event = self.right_button.step(mouse_position, mouse_pressed);
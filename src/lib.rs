@@ -1,27 +1,67 @@
+mod animation;
+mod atlas;
 mod backend;
 mod button;
 mod button_toggle;
+mod checkbox;
+mod color_picker;
 mod common;
+mod context_menu;
 mod draggable;
+mod dropdown;
 mod gui;
 mod label;
 mod left_right_selector;
+mod list_view;
+mod menu;
+mod menu_bar;
+mod message_box;
 mod move_and_resize_thumbs;
+mod number_field;
+mod progress_bar;
+mod radio_group;
+mod range_slider;
+mod rich_text;
 mod slider;
+mod tab_container;
 mod theme;
+mod theme_file;
+mod tooltip;
+mod tree_view;
 mod utils;
 mod vertical_slider;
+mod xy_pad;
 
+pub use self::animation::*;
+pub use self::atlas::*;
 pub use self::backend::*;
 pub use self::button::Button;
 pub use self::button_toggle::ButtonToggle;
+pub use self::checkbox::Checkbox;
+pub use self::color_picker::{ColorPicker, ColorPickerPart};
 pub use self::common::*;
+pub use self::context_menu::ContextMenu;
 pub use self::draggable::Draggable;
+pub use self::dropdown::Dropdown;
 pub use self::gui::Gui;
 pub use self::label::Label;
 pub use self::left_right_selector::LeftRightSelector;
+pub use self::list_view::{ListRow, ListRowDraw, ListRowStep, ListSelectionMode, ListView};
+pub use self::menu::{Menu, MenuItem, MenuPopup, MenuPopupResult};
+pub use self::menu_bar::MenuBar;
+pub use self::message_box::MessageBox;
 pub use self::move_and_resize_thumbs::MoveAndResizeThumbs;
+pub use self::number_field::{evaluate_expression, NumberField};
+pub use self::progress_bar::{ProgressBar, ProgressDirection, ProgressText};
+pub use self::radio_group::{RadioGroup, RadioLayout};
+pub use self::range_slider::{RangeSlider, RangeSliderPart};
+pub use self::rich_text::*;
 pub use self::slider::Slider;
+pub use self::tab_container::{Tab, TabContainer};
 pub use self::theme::*;
+pub use self::theme_file::*;
+pub use self::tooltip::*;
+pub use self::tree_view::{TreeLoadChildren, TreeNode, TreeRow, TreeView};
 pub use self::utils::*;
 pub use self::vertical_slider::VerticalSlider;
+pub use self::xy_pad::XYPad;
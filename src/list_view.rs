@@ -0,0 +1,412 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{
+    DrawBackend, Event, FontId, Key, Modifiers, Rect, StyleOverride, Theme, ELEMENT_NEXT_ID,
+};
+
+/// How many rows a `ListView` lets the user select.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ListSelectionMode {
+    #[default]
+    Single,
+    /// Shift selects ranges, Ctrl toggles single rows.
+    Multiple,
+}
+
+/// A visible row, passed to the row callbacks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ListRow {
+    pub index: usize,
+    /// Where the row is on screen. It may stick out of the list, drawing is clipped.
+    pub rect: Rect,
+    pub selected: bool,
+    pub hovered: bool,
+    /// The keyboard cursor is on this row.
+    pub cursor: bool,
+    /// The list's font overrides, used by `draw_list_row_text`.
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+}
+
+/// Draws the content of one row over its selection highlight.
+pub type ListRowDraw = Box<dyn Fn(&mut dyn DrawBackend, &Theme, &ListRow)>;
+
+/// Steps one row with the mouse position and button. Returning `true` means the row
+/// used the mouse itself, e.g. for a button inside it, so the list doesn't select or
+/// start a drag.
+pub type ListRowStep = Box<dyn FnMut(&ListRow, Vec2, bool) -> bool>;
+
+/// Scrollable list of `row_count` rows of equal height. Only visible rows are drawn and
+/// stepped, so it scales to thousands of rows. The app owns the row data and draws it
+/// through `draw_row`.
+pub struct ListView {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub row_count: usize,
+    pub row_height: f32,
+    pub selection_mode: ListSelectionMode,
+    /// Rows can be dragged to a new place, see `Event::ListReordered`.
+    pub reorderable: bool,
+    /// Pixels scrolled from the top.
+    pub scroll_offset: f32,
+    pub selected: BTreeSet<usize>,
+    /// Keyboard cursor row.
+    pub cursor: Option<usize>,
+    /// Where Shift range selection starts.
+    pub anchor: Option<usize>,
+    pub hovered_row: Option<usize>,
+    pub draw_row: Option<ListRowDraw>,
+    pub step_row: Option<ListRowStep>,
+    /// Font for `draw_list_row_text`.
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+
+    /// Row the mouse was pressed on, while it is held.
+    pub pressed_row: Option<usize>,
+    pub press_position: Vec2,
+    pub dragging: bool,
+    /// Gap a dragged row would drop into, `0..=row_count`. `None` when the drop
+    /// wouldn't move it.
+    pub drop_gap: Option<usize>,
+    pub was_mouse_pressed: bool,
+}
+
+impl ListView {
+    pub fn new(position: Vec2, size: Vec2, row_count: usize, row_height: f32) -> Self {
+        Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            row_count,
+            row_height,
+            selection_mode: ListSelectionMode::Single,
+            reorderable: false,
+            scroll_offset: 0.0,
+            selected: BTreeSet::new(),
+            cursor: None,
+            anchor: None,
+            hovered_row: None,
+            draw_row: None,
+            step_row: None,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            pressed_row: None,
+            press_position: Vec2::ZERO,
+            dragging: false,
+            drop_gap: None,
+            was_mouse_pressed: false,
+        }
+    }
+
+    pub fn set_selection_mode(&mut self, mode: ListSelectionMode) {
+        self.selection_mode = mode;
+        if mode == ListSelectionMode::Single {
+            let keep = self.cursor.filter(|cursor| self.selected.contains(cursor));
+            self.selected.clear();
+            self.selected.extend(keep);
+        }
+    }
+
+    pub fn set_reorderable(&mut self, reorderable: bool) {
+        self.reorderable = reorderable;
+    }
+
+    pub fn set_draw_row(
+        &mut self,
+        draw_row: impl Fn(&mut dyn DrawBackend, &Theme, &ListRow) + 'static,
+    ) {
+        self.draw_row = Some(Box::new(draw_row));
+    }
+
+    pub fn set_step_row(&mut self, step_row: impl FnMut(&ListRow, Vec2, bool) -> bool + 'static) {
+        self.step_row = Some(Box::new(step_row));
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// Change the number of rows. Selection past the end is dropped.
+    pub fn set_row_count(&mut self, row_count: usize) {
+        self.row_count = row_count;
+        self.selected.retain(|index| *index < row_count);
+        let in_range = |index: Option<usize>| index.filter(|index| *index < row_count);
+        self.cursor = in_range(self.cursor);
+        self.anchor = in_range(self.anchor);
+        self.hovered_row = in_range(self.hovered_row);
+        self.pressed_row = None;
+        self.dragging = false;
+        self.drop_gap = None;
+        self.scroll_by(0.0);
+    }
+
+    /// Selected rows in ascending order.
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// Select only `index`, move the cursor to it and scroll it into view. Emits no
+    /// event.
+    pub fn select_row(&mut self, index: usize) {
+        if index >= self.row_count {
+            return;
+        }
+        self.selected.clear();
+        self.selected.insert(index);
+        self.cursor = Some(index);
+        self.anchor = Some(index);
+        self.scroll_to(index);
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+
+    pub fn content_height(&self) -> f32 {
+        self.row_count as f32 * self.row_height
+    }
+
+    pub fn max_scroll(&self) -> f32 {
+        (self.content_height() - self.size.y).max(0.0)
+    }
+
+    /// Scroll by `pixels`, positive scrolls down.
+    pub fn scroll_by(&mut self, pixels: f32) {
+        self.scroll_offset = (self.scroll_offset + pixels).clamp(0.0, self.max_scroll());
+    }
+
+    /// Scroll just enough to show all of row `index`.
+    pub fn scroll_to(&mut self, index: usize) {
+        let top = index as f32 * self.row_height;
+        if top < self.scroll_offset {
+            self.scroll_offset = top;
+        } else if top + self.row_height > self.scroll_offset + self.size.y {
+            self.scroll_offset = top + self.row_height - self.size.y;
+        }
+        self.scroll_by(0.0);
+    }
+
+    /// Rows at least partly inside the list.
+    pub fn visible_range(&self) -> Range<usize> {
+        if self.row_height <= 0.0 {
+            return 0..0;
+        }
+        let first = (self.scroll_offset / self.row_height) as usize;
+        let last = ((self.scroll_offset + self.size.y) / self.row_height).ceil() as usize;
+        first.min(self.row_count)..last.min(self.row_count)
+    }
+
+    pub fn row_rect(&self, index: usize) -> Rect {
+        Rect::new(
+            self.position + Vec2::new(0.0, index as f32 * self.row_height - self.scroll_offset),
+            Vec2::new(self.size.x, self.row_height),
+        )
+    }
+
+    /// State of row `index` as the callbacks see it.
+    pub fn row(&self, index: usize) -> ListRow {
+        ListRow {
+            index,
+            rect: self.row_rect(index),
+            selected: self.selected.contains(&index),
+            hovered: self.hovered_row == Some(index),
+            cursor: self.cursor == Some(index),
+            font: self.font,
+            font_size_px: self.font_size_px,
+        }
+    }
+
+    pub fn row_at(&self, position: Vec2) -> Option<usize> {
+        if !Rect::new(self.position, self.size).contains(position) || self.row_height <= 0.0 {
+            return None;
+        }
+        let index =
+            ((position.y - self.position.y + self.scroll_offset) / self.row_height) as usize;
+        (index < self.row_count).then_some(index)
+    }
+
+    /// Y of the line drawn at `drop_gap` while dragging.
+    pub fn drop_indicator_y(&self) -> Option<f32> {
+        let gap = self.drop_gap?;
+        Some(self.position.y + gap as f32 * self.row_height - self.scroll_offset)
+    }
+
+    /// Drop hover and press state, abandoning a drag reorder without an event.
+    pub fn cancel_press(&mut self) {
+        self.hovered_row = None;
+        self.pressed_row = None;
+        self.dragging = false;
+        self.drop_gap = None;
+    }
+
+    /// `modifiers` decide how a click changes the selection, see `ListSelectionMode`.
+    pub fn step(
+        &mut self,
+        mouse_position: Vec2,
+        mouse_pressed: bool,
+        modifiers: Modifiers,
+    ) -> Option<Event> {
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+        self.hovered_row = self.row_at(mouse_position);
+
+        let mut row_used_mouse = false;
+        if self.step_row.is_some() {
+            let rows: Vec<ListRow> = self.visible_range().map(|index| self.row(index)).collect();
+            if let Some(step_row) = self.step_row.as_mut() {
+                for row in &rows {
+                    row_used_mouse |= step_row(row, mouse_position, mouse_pressed);
+                }
+            }
+        }
+
+        if press_started {
+            if row_used_mouse {
+                return None;
+            }
+            let index = self.hovered_row?;
+            self.pressed_row = Some(index);
+            self.press_position = mouse_position;
+            return self.click(index, modifiers);
+        }
+
+        let from = self.pressed_row?;
+        if mouse_pressed {
+            let drag_threshold = (self.row_height * 0.25).max(2.0);
+            if self.reorderable
+                && !self.dragging
+                && (mouse_position.y - self.press_position.y).abs() > drag_threshold
+            {
+                self.dragging = true;
+            }
+            if self.dragging {
+                let gap = ((mouse_position.y - self.position.y + self.scroll_offset)
+                    / self.row_height)
+                    .round()
+                    .clamp(0.0, self.row_count as f32) as usize;
+                self.drop_gap = (gap != from && gap != from + 1).then_some(gap);
+            }
+            return None;
+        }
+
+        self.pressed_row = None;
+        self.dragging = false;
+        let gap = self.drop_gap.take()?;
+        let to = if gap > from { gap - 1 } else { gap };
+        self.move_row(from, to);
+        Some(Event::ListReordered { from, to })
+    }
+
+    /// Keyboard input while focused. Arrows, Page Up/Down and Home/End move the cursor
+    /// and select its row, Shift extends the selection and Ctrl moves only the cursor.
+    /// Space selects the cursor row, or toggles it with Ctrl.
+    pub fn key_pressed(&mut self, key: Key, modifiers: Modifiers) -> Option<Event> {
+        if self.row_count == 0 {
+            return None;
+        }
+        let last = self.row_count - 1;
+        let page = ((self.size.y / self.row_height.max(1.0)) as usize).max(1);
+        let current = self.cursor.unwrap_or(0);
+        let target = match key {
+            Key::Up => current.saturating_sub(1),
+            Key::Down if self.cursor.is_none() => 0,
+            Key::Down => (current + 1).min(last),
+            Key::PageUp => current.saturating_sub(page),
+            Key::PageDown => (current + page).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            Key::Space => {
+                self.cursor = Some(current);
+                return self.click(
+                    current,
+                    Modifiers {
+                        shift: false,
+                        ..modifiers
+                    },
+                );
+            }
+            _ => return None,
+        };
+        self.scroll_to(target);
+        if modifiers.ctrl && self.selection_mode == ListSelectionMode::Multiple {
+            self.cursor = Some(target);
+            return None;
+        }
+        self.click(
+            target,
+            Modifiers {
+                ctrl: false,
+                ..modifiers
+            },
+        )
+    }
+
+    /// Apply a click on row `index` to the selection.
+    fn click(&mut self, index: usize, modifiers: Modifiers) -> Option<Event> {
+        let before = self.selected.clone();
+        self.cursor = Some(index);
+        let multiple = self.selection_mode == ListSelectionMode::Multiple;
+        if multiple && modifiers.shift {
+            let anchor = self.anchor.unwrap_or(index);
+            if !modifiers.ctrl {
+                self.selected.clear();
+            }
+            self.selected.extend(anchor.min(index)..=anchor.max(index));
+        } else if multiple && modifiers.ctrl {
+            if !self.selected.remove(&index) {
+                self.selected.insert(index);
+            }
+            self.anchor = Some(index);
+        } else {
+            self.selected.clear();
+            self.selected.insert(index);
+            self.anchor = Some(index);
+        }
+        (self.selected != before).then_some(Event::ListSelectionChanged { index })
+    }
+
+    /// Keep the selection, cursor and anchor on the same rows after the row at `from`
+    /// moves to `to`.
+    fn move_row(&mut self, from: usize, to: usize) {
+        let moved = |index: usize| {
+            if index == from {
+                to
+            } else if from < to && index > from && index <= to {
+                index - 1
+            } else if to < from && index >= to && index < from {
+                index + 1
+            } else {
+                index
+            }
+        };
+        self.selected = self.selected.iter().map(|index| moved(*index)).collect();
+        self.cursor = self.cursor.map(moved);
+        self.anchor = self.anchor.map(moved);
+        self.hovered_row = None;
+    }
+}
@@ -0,0 +1,388 @@
+use glam::Vec2;
+
+use super::{Event, Key, Rect};
+
+/// A list of menu items. `tag` is chosen by the app and reported in
+/// `Event::MenuItemChosen`, so items of different submenus can be told apart.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Menu {
+    pub tag: u32,
+    pub items: Vec<MenuItem>,
+}
+
+impl Menu {
+    pub fn new(tag: u32, items: Vec<MenuItem>) -> Self {
+        Self { tag, items }
+    }
+
+    /// Height of all items stacked, separators included.
+    pub fn height(&self, item_height: f32) -> f32 {
+        self.items.iter().map(|item| item.height(item_height)).sum()
+    }
+
+    /// Choose the enabled item whose `shortcut` is `shortcut`, searching submenus too.
+    /// Checkable items are toggled.
+    pub fn trigger_shortcut(&mut self, shortcut: &str) -> Option<Event> {
+        let menu_tag = self.tag;
+        for (item_index, item) in self.items.iter_mut().enumerate() {
+            if !item.is_selectable() {
+                continue;
+            }
+            if let Some(submenu) = &mut item.submenu {
+                if let Some(event) = submenu.trigger_shortcut(shortcut) {
+                    return Some(event);
+                }
+            } else if item.shortcut.as_deref() == Some(shortcut) {
+                if let Some(checked) = &mut item.checked {
+                    *checked = !*checked;
+                }
+                return Some(Event::MenuItemChosen {
+                    menu_tag,
+                    item_index,
+                });
+            }
+        }
+        None
+    }
+
+    /// Rect of every item inside a panel whose top-left is `position`.
+    pub fn item_rects(&self, position: Vec2, width: f32, item_height: f32) -> Vec<Rect> {
+        let mut y = position.y;
+        self.items
+            .iter()
+            .map(|item| {
+                let height = item.height(item_height);
+                let rect = Rect::new(Vec2::new(position.x, y), Vec2::new(width, height));
+                y += height;
+                rect
+            })
+            .collect()
+    }
+}
+
+/// One entry of a `Menu`: an action, a checkable action, a submenu or a separator.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MenuItem {
+    pub label: String,
+    pub enabled: bool,
+    /// `Some` for checkable items. Choosing one toggles it before the event is emitted.
+    pub checked: Option<bool>,
+    /// Opens to the side when the item is hovered.
+    pub submenu: Option<Menu>,
+    /// Drawn as a thin line; never hovered or chosen.
+    pub separator: bool,
+    /// Key combination shown right-aligned, e.g. `"Ctrl+S"`. Apps pass it to
+    /// `Gui::trigger_shortcut` when the keys are pressed.
+    pub shortcut: Option<String>,
+}
+
+impl MenuItem {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn separator() -> Self {
+        Self {
+            separator: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn checkable(label: &str, checked: bool) -> Self {
+        Self {
+            checked: Some(checked),
+            ..Self::new(label)
+        }
+    }
+
+    pub fn submenu(label: &str, menu: Menu) -> Self {
+        Self {
+            submenu: Some(menu),
+            ..Self::new(label)
+        }
+    }
+
+    pub fn with_shortcut(mut self, shortcut: &str) -> Self {
+        self.shortcut = Some(shortcut.to_string());
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Whether the item can be hovered and chosen.
+    pub fn is_selectable(&self) -> bool {
+        self.enabled && !self.separator
+    }
+
+    pub fn height(&self, item_height: f32) -> f32 {
+        if self.separator {
+            item_height * 0.5
+        } else {
+            item_height
+        }
+    }
+}
+
+/// How an interaction with an open `MenuPopup` ended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MenuPopupResult {
+    Chosen {
+        menu_tag: u32,
+        item_index: usize,
+    },
+    /// Closed without a choice, by a press outside or Escape.
+    Dismissed,
+}
+
+impl MenuPopupResult {
+    pub fn event(self) -> Option<Event> {
+        match self {
+            Self::Chosen {
+                menu_tag,
+                item_index,
+            } => Some(Event::MenuItemChosen {
+                menu_tag,
+                item_index,
+            }),
+            Self::Dismissed => None,
+        }
+    }
+}
+
+/// State of an open menu and its open submenus, shared by menu widgets. Panels are
+/// laid out from `position` and kept inside `bounds`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuPopup {
+    /// Top-left of the top-level panel.
+    pub position: Vec2,
+    pub item_width: f32,
+    pub item_height: f32,
+    /// Area the panels are kept inside, usually `Gui::bounds`.
+    pub bounds: Option<Rect>,
+    /// Index of the item whose submenu is open, for each open level.
+    pub open_path: Vec<usize>,
+    /// Item under the mouse or keyboard cursor, as `(level, index)`.
+    pub hovered: Option<(usize, usize)>,
+    pub was_mouse_pressed: bool,
+}
+
+impl MenuPopup {
+    pub fn new(item_width: f32, item_height: f32) -> Self {
+        Self {
+            position: Vec2::ZERO,
+            item_width,
+            item_height,
+            bounds: None,
+            open_path: Vec::new(),
+            hovered: None,
+            was_mouse_pressed: false,
+        }
+    }
+
+    /// Reset to only the top-level panel open at `position`.
+    pub fn open_at(&mut self, position: Vec2, mouse_pressed: bool) {
+        self.position = position;
+        self.open_path.clear();
+        self.hovered = None;
+        self.was_mouse_pressed = mouse_pressed;
+    }
+
+    /// Menus of the open panels, top level first.
+    pub fn open_menus<'a>(&self, menu: &'a Menu) -> Vec<&'a Menu> {
+        let mut menus = vec![menu];
+        for index in &self.open_path {
+            let Some(submenu) = menus
+                .last()
+                .and_then(|menu| menu.items.get(*index))
+                .and_then(|item| item.submenu.as_ref())
+            else {
+                break;
+            };
+            menus.push(submenu);
+        }
+        menus
+    }
+
+    /// Rects of the open panels, top level first. Submenus open to the right of their
+    /// item, or to the left when that would leave `bounds`.
+    pub fn panel_rects(&self, menu: &Menu) -> Vec<Rect> {
+        let menus = self.open_menus(menu);
+        let mut rects: Vec<Rect> = Vec::with_capacity(menus.len());
+        for (level, menu) in menus.iter().enumerate() {
+            let size = Vec2::new(self.item_width, menu.height(self.item_height));
+            let mut position = match rects.last() {
+                None => self.position,
+                Some(parent) => {
+                    let parent_menu = menus[level - 1];
+                    let item_index = self.open_path[level - 1];
+                    let item_y = parent_menu.item_rects(parent.position, 0.0, self.item_height)
+                        [item_index]
+                        .position
+                        .y;
+                    let mut x = parent.position.x + parent.size.x;
+                    if let Some(bounds) = self.bounds {
+                        if x + size.x > bounds.position.x + bounds.size.x {
+                            x = parent.position.x - size.x;
+                        }
+                    }
+                    Vec2::new(x, item_y)
+                }
+            };
+            if let Some(bounds) = self.bounds {
+                let max = bounds.position + bounds.size - size;
+                position = position.min(max).max(bounds.position);
+            }
+            rects.push(Rect::new(position, size));
+        }
+        rects
+    }
+
+    /// Whether `point` is over any open panel.
+    pub fn contains(&self, menu: &Menu, point: Vec2) -> bool {
+        self.panel_rects(menu)
+            .iter()
+            .any(|rect| rect.contains(point))
+    }
+
+    /// Hovering an item opens its submenu. Releasing the mouse over an item chooses it
+    /// and a press outside every panel dismisses the menu.
+    pub fn step(
+        &mut self,
+        menu: &mut Menu,
+        mouse_position: Vec2,
+        mouse_pressed: bool,
+    ) -> Option<MenuPopupResult> {
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        let released = !mouse_pressed && self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+
+        let hovered = self.item_at(menu, mouse_position);
+        let over_panel = self.contains(menu, mouse_position);
+        match hovered {
+            Some((level, index)) => {
+                self.hover(menu, level, index);
+                if released {
+                    return self.choose(menu, level, index);
+                }
+            }
+            // keep the keyboard cursor while the mouse is away from the menu
+            None if over_panel => self.hovered = None,
+            None => {}
+        }
+        if press_started && !over_panel {
+            return Some(MenuPopupResult::Dismissed);
+        }
+        None
+    }
+
+    /// Up/Down move through the deepest open panel, Right opens a submenu and Left
+    /// closes it. Enter/Space choose, or open a submenu, and Escape dismisses.
+    pub fn key_pressed(&mut self, menu: &mut Menu, key: Key) -> Option<MenuPopupResult> {
+        let level = match self.hovered {
+            Some((level, _)) => level,
+            None => self.open_path.len(),
+        };
+        let menus = self.open_menus(menu);
+        let items = &menus.get(level)?.items;
+        if items.is_empty() {
+            return None;
+        }
+        let current = self.hovered.map(|(_, index)| index);
+        match key {
+            Key::Up | Key::Down => {
+                let count = items.len();
+                let mut index = current.unwrap_or(if key == Key::Up { 0 } else { count - 1 });
+                for _ in 0..count {
+                    index = if key == Key::Up {
+                        (index + count - 1) % count
+                    } else {
+                        (index + 1) % count
+                    };
+                    if items[index].is_selectable() {
+                        self.open_path.truncate(level);
+                        self.hovered = Some((level, index));
+                        break;
+                    }
+                }
+            }
+            Key::Right | Key::Enter | Key::Space => {
+                let index = current?;
+                if items[index].submenu.is_none() {
+                    return (key != Key::Right)
+                        .then(|| self.choose(menu, level, index))
+                        .flatten();
+                }
+                if items[index].is_selectable() {
+                    self.open_path.truncate(level);
+                    self.open_path.push(index);
+                    let first = items[index]
+                        .submenu
+                        .as_ref()
+                        .and_then(|submenu| submenu.items.iter().position(MenuItem::is_selectable));
+                    self.hovered = first.map(|first| (level + 1, first));
+                }
+            }
+            Key::Left if level > 0 => {
+                self.hovered = Some((level - 1, self.open_path[level - 1]));
+                self.open_path.truncate(level - 1);
+            }
+            Key::Escape => return Some(MenuPopupResult::Dismissed),
+            _ => {}
+        }
+        None
+    }
+
+    /// Selectable item under `point`, as `(level, index)`. Deeper panels win where they
+    /// overlap their parent.
+    pub fn item_at(&self, menu: &Menu, point: Vec2) -> Option<(usize, usize)> {
+        let menus = self.open_menus(menu);
+        let rects = self.panel_rects(menu);
+        for (level, (menu, panel)) in menus.iter().zip(&rects).enumerate().rev() {
+            if !panel.contains(point) {
+                continue;
+            }
+            let item_rects = menu.item_rects(panel.position, panel.size.x, self.item_height);
+            return item_rects
+                .iter()
+                .position(|rect| rect.contains(point))
+                .filter(|index| menu.items[*index].is_selectable())
+                .map(|index| (level, index));
+        }
+        None
+    }
+
+    fn hover(&mut self, menu: &Menu, level: usize, index: usize) {
+        self.hovered = Some((level, index));
+        self.open_path.truncate(level);
+        let has_submenu = self.open_menus(menu)[level].items[index].submenu.is_some();
+        if has_submenu {
+            self.open_path.push(index);
+        }
+    }
+
+    fn choose(&mut self, menu: &mut Menu, level: usize, index: usize) -> Option<MenuPopupResult> {
+        let mut menu = menu;
+        for open_index in self.open_path.iter().take(level) {
+            menu = menu.items[*open_index].submenu.as_mut()?;
+        }
+        let menu_tag = menu.tag;
+        let item = menu.items.get_mut(index)?;
+        if !item.is_selectable() || item.submenu.is_some() {
+            return None;
+        }
+        if let Some(checked) = &mut item.checked {
+            *checked = !*checked;
+        }
+        Some(MenuPopupResult::Chosen {
+            menu_tag,
+            item_index: index,
+        })
+    }
+}
@@ -0,0 +1,305 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{
+    Button, Event, FontId, Key, Menu, MenuItem, MenuPopup, MenuPopupResult, Rect, StyleOverride,
+    ELEMENT_NEXT_ID,
+};
+
+/// A strip of top-level menus, e.g. File/Edit/View. Each entry is a `MenuItem::submenu`
+/// whose label is the title on the bar. Clicking a title opens its menu below it, and
+/// while one is open, hovering another title switches to it.
+pub struct MenuBar {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    /// Top-level menus. Entries without a submenu are shown but never open.
+    pub entries: Vec<MenuItem>,
+    pub title_width: f32,
+    /// Index of the open entry.
+    pub open_index: Option<usize>,
+    pub popup: MenuPopup,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    /// One per entry, laid out from the left.
+    pub title_buttons: Vec<Button>,
+}
+
+impl MenuBar {
+    pub fn new(
+        position: Vec2,
+        size: Vec2,
+        entries: Vec<MenuItem>,
+        title_width: f32,
+        item_width: f32,
+    ) -> Self {
+        let title_buttons = entries
+            .iter()
+            .map(|_| Button::new(position, Vec2::ZERO, None))
+            .collect();
+        let mut menu_bar = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            entries,
+            title_width,
+            open_index: None,
+            popup: MenuPopup::new(item_width, size.y),
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            title_buttons,
+        };
+        menu_bar.sync_internal_buttons();
+        menu_bar
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.sync_internal_buttons();
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+        self.sync_internal_buttons();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open_index.is_some()
+    }
+
+    /// Open entry `index` below its title, if it has an enabled menu.
+    pub fn open(&mut self, index: usize, bounds: Option<Rect>, mouse_pressed: bool) {
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        if !entry.is_selectable() || entry.submenu.is_none() {
+            return;
+        }
+        let title = &self.title_buttons[index];
+        self.open_index = Some(index);
+        self.popup.bounds = bounds;
+        self.popup
+            .open_at(title.position + Vec2::new(0.0, title.size.y), mouse_pressed);
+    }
+
+    pub fn close(&mut self) {
+        self.open_index = None;
+        self.popup.open_path.clear();
+        self.popup.hovered = None;
+    }
+
+    /// Rects of the open menu's panels, top level first.
+    pub fn panel_rects(&self) -> Vec<Rect> {
+        let Some(menu) = self.open_menu() else {
+            return Vec::new();
+        };
+        self.popup.panel_rects(menu)
+    }
+
+    pub fn open_menu(&self) -> Option<&Menu> {
+        self.entries.get(self.open_index?)?.submenu.as_ref()
+    }
+
+    /// Enabled item whose `shortcut` is `shortcut`, as the event choosing it would emit.
+    /// Checkable items are toggled.
+    pub fn trigger_shortcut(&mut self, shortcut: &str) -> Option<Event> {
+        self.entries
+            .iter_mut()
+            .filter(|entry| entry.is_selectable())
+            .filter_map(|entry| entry.submenu.as_mut())
+            .find_map(|menu| menu.trigger_shortcut(shortcut))
+    }
+
+    /// Close the open menu and drop the title buttons' press state.
+    pub fn cancel_press(&mut self) {
+        self.close();
+        for button in self.title_buttons.iter_mut() {
+            button.cancel_press();
+        }
+    }
+
+    pub fn step(
+        &mut self,
+        mouse_position: Vec2,
+        mouse_pressed: bool,
+        bounds: Option<Rect>,
+    ) -> Option<Event> {
+        let mut pressed_title = None;
+        let mut hovered_title = None;
+        for (index, button) in self.title_buttons.iter_mut().enumerate() {
+            if matches!(
+                button.step(mouse_position, mouse_pressed),
+                Some(Event::ButtonPressed)
+            ) {
+                pressed_title = Some(index);
+            }
+            if button.hovered {
+                hovered_title = Some(index);
+            }
+        }
+
+        let Some(open_index) = self.open_index else {
+            if let Some(index) = pressed_title {
+                self.open(index, bounds, mouse_pressed);
+            }
+            return None;
+        };
+        if pressed_title == Some(open_index) {
+            self.close();
+            return None;
+        }
+        if let Some(index) = hovered_title.filter(|index| *index != open_index) {
+            self.open(index, bounds, mouse_pressed);
+            return None;
+        }
+        if hovered_title.is_some() {
+            // the press that opened the menu may be released over its title
+            self.popup.was_mouse_pressed = mouse_pressed;
+            return None;
+        }
+        let index = self.open_index?;
+        let menu = self.entries[index].submenu.as_mut()?;
+        let result = self.popup.step(menu, mouse_position, mouse_pressed);
+        self.finish(result)
+    }
+
+    /// While closed, Enter/Space/Down open the first menu. While open, Left/Right on the
+    /// top-level panel switch menus and other keys go to the menu.
+    pub fn key_pressed(&mut self, key: Key, bounds: Option<Rect>) -> Option<Event> {
+        let Some(open_index) = self.open_index else {
+            if matches!(key, Key::Enter | Key::Space | Key::Down) {
+                if let Some(index) = self
+                    .entries
+                    .iter()
+                    .position(|entry| entry.is_selectable() && entry.submenu.is_some())
+                {
+                    self.open(index, bounds, false);
+                    self.open_index?;
+                    self.popup
+                        .key_pressed(self.entries[index].submenu.as_mut()?, Key::Down);
+                }
+            }
+            return None;
+        };
+
+        let on_top_level = self.popup.hovered.is_none_or(|(level, _)| level == 0);
+        // only the top-level item matters, submenu rows index their own menu
+        let hovered_has_submenu = self
+            .popup
+            .hovered
+            .filter(|(level, _)| *level == 0)
+            .zip(self.open_menu())
+            .is_some_and(|((_, index), menu)| {
+                menu.items
+                    .get(index)
+                    .is_some_and(|item| item.submenu.is_some())
+            });
+        let switch = match key {
+            Key::Left if on_top_level => Some(-1),
+            Key::Right if on_top_level && !hovered_has_submenu => Some(1),
+            _ => None,
+        };
+        if let Some(direction) = switch {
+            let count = self.entries.len() as isize;
+            let mut index = open_index as isize;
+            for _ in 0..count {
+                index = (index + direction).rem_euclid(count);
+                if self.entries[index as usize].is_selectable()
+                    && self.entries[index as usize].submenu.is_some()
+                {
+                    break;
+                }
+            }
+            self.open(index as usize, bounds, false);
+            let menu = self.entries[index as usize].submenu.as_mut()?;
+            self.popup.key_pressed(menu, Key::Down);
+            return None;
+        }
+
+        let menu = self.entries[open_index].submenu.as_mut()?;
+        let result = self.popup.key_pressed(menu, key);
+        self.finish(result)
+    }
+
+    fn finish(&mut self, result: Option<MenuPopupResult>) -> Option<Event> {
+        let result = result?;
+        self.close();
+        result.event()
+    }
+
+    fn sync_internal_buttons(&mut self) {
+        for (index, button) in self.title_buttons.iter_mut().enumerate() {
+            button.position = self.position + Vec2::new(index as f32 * self.title_width, 0.0);
+            button.size = Vec2::new(self.title_width, self.size.y);
+        }
+        self.popup.item_height = self.size.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu_bar(entries: Vec<MenuItem>) -> MenuBar {
+        MenuBar::new(Vec2::ZERO, Vec2::new(400.0, 20.0), entries, 60.0, 120.0)
+    }
+
+    #[test]
+    fn keys_move_inside_a_submenu_longer_than_its_parent() {
+        let recent = Menu::new(2, ["a", "b", "c", "d", "e"].map(MenuItem::new).to_vec());
+        let file = Menu::new(
+            1,
+            vec![MenuItem::new("New"), MenuItem::submenu("Recent", recent)],
+        );
+        let mut bar = menu_bar(vec![MenuItem::submenu("File", file)]);
+        for key in [
+            Key::Down,
+            Key::Down,
+            Key::Right,
+            Key::Down,
+            Key::Down,
+            Key::Down,
+        ] {
+            assert!(bar.key_pressed(key, None).is_none());
+        }
+        assert!(bar.is_open());
+        assert_eq!(bar.popup.hovered.map(|(level, _)| level), Some(1));
+    }
+
+    #[test]
+    fn keys_skip_disabled_menus_when_opening() {
+        let menu = || Menu::new(1, vec![MenuItem::new("item")]);
+        let mut bar = menu_bar(vec![
+            MenuItem::submenu("File", menu()).with_enabled(false),
+            MenuItem::submenu("Edit", menu()),
+        ]);
+        bar.key_pressed(Key::Down, None);
+        assert_eq!(bar.open_index, Some(1));
+
+        let mut bar = menu_bar(vec![MenuItem::submenu("File", menu()).with_enabled(false)]);
+        bar.key_pressed(Key::Down, None);
+        assert!(!bar.is_open());
+        assert_eq!(bar.popup.hovered, None);
+    }
+}
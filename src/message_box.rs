@@ -0,0 +1,170 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Button, Event, FontId, Key, Rect, StyleOverride, ELEMENT_NEXT_ID};
+
+/// A ready-made modal dialog: a title, body text and one to three buttons along the
+/// bottom. `Gui::add_message_box` opens it as a modal, and it closes itself once a
+/// button is chosen.
+pub struct MessageBox {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub title: String,
+    /// Wrapped to the box width.
+    pub text: String,
+    /// Right-aligned in the order given, so the first button is leftmost.
+    pub buttons: Vec<Button>,
+    /// Button chosen by Enter/Space. Left/Right move it.
+    pub focused_button: usize,
+    /// Button chosen by Escape, the last one by default. `None` ignores Escape.
+    pub cancel_button: Option<usize>,
+    pub button_height: f32,
+    /// Gap between buttons and around the box edge.
+    pub spacing: f32,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+}
+
+impl MessageBox {
+    pub fn new(position: Vec2, size: Vec2, title: &str, text: &str, buttons: &[&str]) -> Self {
+        debug_assert!(
+            (1..=3).contains(&buttons.len()),
+            "a message box has one to three buttons"
+        );
+        let mut message_box = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            title: title.to_string(),
+            text: text.to_string(),
+            buttons: buttons
+                .iter()
+                .map(|label| Button::new(position, Vec2::ZERO, Some(label.to_string())))
+                .collect(),
+            focused_button: 0,
+            cancel_button: buttons.len().checked_sub(1),
+            button_height: 24.0,
+            spacing: 8.0,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+        };
+        message_box.sync_internal_buttons();
+        message_box
+    }
+
+    /// A message box of `size` centered in `area`, usually `Gui::bounds`.
+    pub fn centered(area: Rect, size: Vec2, title: &str, text: &str, buttons: &[&str]) -> Self {
+        let position = area.position + (area.size - size) * 0.5;
+        Self::new(position, size, title, text, buttons)
+    }
+
+    pub fn set_cancel_button(&mut self, index: Option<usize>) {
+        self.cancel_button = index;
+    }
+
+    pub fn set_button_height(&mut self, button_height: f32) {
+        self.button_height = button_height;
+        self.sync_internal_buttons();
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.sync_internal_buttons();
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+        self.sync_internal_buttons();
+    }
+
+    /// Area between the title and the buttons where the text is drawn.
+    pub fn text_rect(&self, title_height: f32) -> Rect {
+        let top = self.position.y + self.spacing + title_height + self.spacing;
+        let bottom = self.position.y + self.size.y - self.spacing * 2.0 - self.button_height;
+        Rect::new(
+            Vec2::new(self.position.x + self.spacing, top),
+            Vec2::new(
+                (self.size.x - self.spacing * 2.0).max(0.0),
+                (bottom - top).max(0.0),
+            ),
+        )
+    }
+
+    pub fn cancel_press(&mut self) {
+        for button in self.buttons.iter_mut() {
+            button.cancel_press();
+        }
+    }
+
+    /// Releasing the mouse over a button chooses it.
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        let mut chosen = None;
+        for (index, button) in self.buttons.iter_mut().enumerate() {
+            let event = button.step(mouse_position, mouse_pressed);
+            if matches!(event, Some(Event::ButtonReleased)) && button.hovered {
+                chosen = Some(index);
+            }
+        }
+        chosen.map(|button| Event::DialogResult { button })
+    }
+
+    /// Left/Right move the focused button, Enter/Space choose it and Escape chooses the
+    /// cancel button.
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        let last = self.buttons.len().checked_sub(1)?;
+        match key {
+            Key::Left => self.focused_button = self.focused_button.saturating_sub(1),
+            Key::Right => self.focused_button = (self.focused_button + 1).min(last),
+            Key::Enter | Key::Space => {
+                return Some(Event::DialogResult {
+                    button: self.focused_button.min(last),
+                })
+            }
+            Key::Escape => {
+                return self
+                    .cancel_button
+                    .map(|button| Event::DialogResult { button })
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn sync_internal_buttons(&mut self) {
+        // room for three buttons, so boxes with fewer keep the same button width
+        let width = ((self.size.x - self.spacing * 4.0) / 3.0).max(0.0);
+        let y = self.position.y + self.size.y - self.spacing - self.button_height;
+        let right = self.position.x + self.size.x - self.spacing;
+        let count = self.buttons.len();
+        for (index, button) in self.buttons.iter_mut().enumerate() {
+            let from_right = (count - index) as f32;
+            button.position = Vec2::new(
+                right - from_right * (width + self.spacing) + self.spacing,
+                y,
+            );
+            button.size = Vec2::new(width, self.button_height);
+        }
+    }
+}
@@ -2,13 +2,15 @@ use std::sync::atomic::Ordering;
 
 use glam::Vec2;
 
-use super::{Draggable, Event, ELEMENT_NEXT_ID};
+use super::{Draggable, Event, StyleOverride, ELEMENT_NEXT_ID};
 
 pub struct MoveAndResizeThumbs {
     pub id: u32,
     pub thumb_size: Vec2,
     pub target_position: Vec2,
     pub target_size: Vec2,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
     pub move_thumb: Draggable,
     pub resize_thumb: Draggable,
 }
@@ -20,6 +22,8 @@ impl MoveAndResizeThumbs {
             thumb_size,
             target_position,
             target_size,
+            style_class: None,
+            style: StyleOverride::default(),
             move_thumb: Draggable::new(Vec2::ZERO, thumb_size, None),
             resize_thumb: Draggable::new(Vec2::ZERO, thumb_size, None),
         };
@@ -27,12 +31,25 @@ impl MoveAndResizeThumbs {
         thumbs
     }
 
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
     pub fn set_target(&mut self, position: Vec2, size: Vec2) {
         self.target_position = position;
         self.target_size = size;
         self.sync_thumbs_from_target();
     }
 
+    pub fn cancel_press(&mut self) {
+        self.move_thumb.cancel_press();
+        self.resize_thumb.cancel_press();
+    }
+
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
         let move_event = self.move_thumb.step(mouse_position, mouse_pressed);
         let resize_event = self.resize_thumb.step(mouse_position, mouse_pressed);
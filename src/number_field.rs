@@ -0,0 +1,495 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Button, Event, FontId, Key, Modifiers, Rect, StyleOverride, ELEMENT_NEXT_ID};
+
+/// Pixels the mouse has to move after a press before it counts as a drag.
+const DRAG_THRESHOLD_PX: f32 = 3.0;
+
+/// A numeric field with increment/decrement arrows. Dragging horizontally on the field
+/// changes the value, and clicking it (or Enter while focused) starts typing a value or
+/// a simple expression like `0.5 / 4`.
+pub struct NumberField {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub minimum: f32,
+    pub maximum: f32,
+    /// Values snap to multiples of `step_size` like `Slider`, and the arrows add or
+    /// subtract it. `0.0` turns snapping off.
+    pub step_size: f32,
+    pub value: f32,
+    /// Digits after the decimal point.
+    pub precision: usize,
+    /// Shown after the value, e.g. `" s"` or `"%"`.
+    pub units: Option<String>,
+    /// Value change per pixel of horizontal drag.
+    pub drag_speed: f32,
+    /// Drag speed multiplier while Shift is held.
+    pub fine_factor: f32,
+    /// Drag speed multiplier while Ctrl is held.
+    pub coarse_factor: f32,
+    /// Seconds an arrow has to be held before it repeats.
+    pub repeat_delay: f32,
+    /// Seconds between repeats while an arrow is held.
+    pub repeat_interval: f32,
+    /// Text being typed, `None` when not editing.
+    pub edit_text: Option<String>,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    pub increment_button: Button,
+    pub decrement_button: Button,
+    pub hovered: bool,
+    /// Press on the field: mouse position and the unsnapped value being dragged.
+    pub press: Option<(Vec2, f32)>,
+    pub dragging: bool,
+    /// Held arrow, `1.0` or `-1.0`, and seconds until it repeats.
+    pub repeat: Option<(f32, f32)>,
+    /// The first typed text replaces the whole value.
+    pub replace_on_type: bool,
+    pub was_mouse_pressed: bool,
+}
+
+impl NumberField {
+    pub fn new(
+        position: Vec2,
+        size: Vec2,
+        minimum: f32,
+        maximum: f32,
+        step_size: f32,
+        value: f32,
+    ) -> Self {
+        let (minimum, maximum) = (minimum.min(maximum), minimum.max(maximum));
+        let mut field = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            minimum,
+            maximum,
+            step_size,
+            value,
+            precision: 2,
+            units: None,
+            drag_speed: if step_size > 0.0 {
+                step_size
+            } else {
+                (maximum - minimum) / 200.0
+            },
+            fine_factor: 0.1,
+            coarse_factor: 10.0,
+            repeat_delay: 0.4,
+            repeat_interval: 0.05,
+            edit_text: None,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            increment_button: Button::new(position, Vec2::ZERO, None),
+            decrement_button: Button::new(position, Vec2::ZERO, None),
+            hovered: false,
+            press: None,
+            dragging: false,
+            repeat: None,
+            replace_on_type: false,
+            was_mouse_pressed: false,
+        };
+        field.value = field.constrain(value);
+        field.sync_internal_buttons();
+        field
+    }
+
+    pub fn set_precision(&mut self, precision: usize) {
+        self.precision = precision;
+    }
+
+    pub fn set_units(&mut self, units: &str) {
+        self.units = Some(units.to_string());
+    }
+
+    pub fn set_drag_speed(&mut self, drag_speed: f32) {
+        self.drag_speed = drag_speed;
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.sync_internal_buttons();
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+        self.sync_internal_buttons();
+    }
+
+    /// Set the value without an event, clamped and snapped.
+    pub fn set_value(&mut self, value: f32) {
+        self.value = self.constrain(value);
+    }
+
+    /// Area left of the arrows where the value is shown and dragged.
+    pub fn field_rect(&self) -> Rect {
+        Rect::new(
+            self.position,
+            Vec2::new(
+                (self.size.x - self.increment_button.size.x).max(0.0),
+                self.size.y,
+            ),
+        )
+    }
+
+    /// The value as displayed, with its precision and units.
+    pub fn display_text(&self) -> String {
+        format!(
+            "{:.*}{}",
+            self.precision,
+            self.value,
+            self.units.as_deref().unwrap_or("")
+        )
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.edit_text.is_some()
+    }
+
+    /// Start typing, with the current value selected so typing replaces it.
+    pub fn begin_edit(&mut self) {
+        self.edit_text = Some(format!("{:.*}", self.precision, self.value));
+        self.replace_on_type = true;
+    }
+
+    /// Evaluate the typed text and apply it. Text that doesn't evaluate is discarded.
+    pub fn commit_edit(&mut self) -> Option<Event> {
+        let text = self.edit_text.take()?;
+        let value = evaluate_expression(&text)?;
+        self.change_to(value)
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.edit_text = None;
+    }
+
+    /// Add typed characters while editing. Only characters that can appear in an
+    /// expression are kept.
+    pub fn text_input(&mut self, text: &str) {
+        let Some(edit_text) = &mut self.edit_text else {
+            return;
+        };
+        if self.replace_on_type {
+            edit_text.clear();
+            self.replace_on_type = false;
+        }
+        edit_text.extend(
+            text.chars()
+                .filter(|ch| ch.is_ascii_digit() || "+-*/.() ".contains(*ch)),
+        );
+    }
+
+    /// Drop hover, drag and arrow press state. A typed value stays being edited.
+    pub fn cancel_press(&mut self) {
+        self.increment_button.cancel_press();
+        self.decrement_button.cancel_press();
+        self.hovered = false;
+        self.press = None;
+        self.dragging = false;
+        self.repeat = None;
+    }
+
+    pub fn step(
+        &mut self,
+        mouse_position: Vec2,
+        mouse_pressed: bool,
+        modifiers: Modifiers,
+    ) -> Option<Event> {
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+        let mut pressed_arrow = None;
+        for (direction, button) in [
+            (1.0, &mut self.increment_button),
+            (-1.0, &mut self.decrement_button),
+        ] {
+            if matches!(
+                button.step(mouse_position, mouse_pressed),
+                Some(Event::ButtonPressed)
+            ) {
+                self.repeat = Some((direction, self.repeat_delay));
+                pressed_arrow = Some(direction);
+            }
+        }
+        if !mouse_pressed || !(self.increment_button.pressed || self.decrement_button.pressed) {
+            self.repeat = None;
+        }
+        if let Some(direction) = pressed_arrow {
+            let committed = self.commit_edit();
+            return self.nudge(direction, 1.0).or(committed);
+        }
+
+        let field = self.field_rect();
+        self.hovered = field.contains(mouse_position);
+        if press_started {
+            if self.hovered {
+                self.press = Some((mouse_position, self.value));
+                self.dragging = false;
+            } else if self.is_editing() {
+                // clicking elsewhere applies the typed value
+                return self.commit_edit();
+            }
+        }
+
+        let (press_position, drag_value) = self.press?;
+        if !mouse_pressed {
+            self.press = None;
+            if !self.dragging && self.hovered && !self.is_editing() {
+                self.begin_edit();
+            }
+            self.dragging = false;
+            return None;
+        }
+        if !self.dragging {
+            if (mouse_position.x - press_position.x).abs() < DRAG_THRESHOLD_PX {
+                return None;
+            }
+            self.dragging = true;
+            self.edit_text = None;
+        }
+        // the raw value keeps fractions of a step, so slow drags still add up
+        let factor = self.speed_factor(modifiers);
+        let drag_value =
+            drag_value + (mouse_position.x - press_position.x) * self.drag_speed * factor;
+        self.press = Some((mouse_position, drag_value));
+        self.change_to(drag_value)
+    }
+
+    /// Repeat a held arrow once `repeat_delay` has passed.
+    pub fn update(&mut self, dt: f32) -> Option<Event> {
+        let (direction, remaining) = self.repeat?;
+        let mut remaining = remaining - dt;
+        let mut repeats = 0;
+        while remaining <= 0.0 && self.repeat_interval > 0.0 {
+            remaining += self.repeat_interval;
+            repeats += 1;
+        }
+        self.repeat = Some((direction, remaining));
+        if repeats == 0 {
+            return None;
+        }
+        self.nudge(direction, repeats as f32)
+    }
+
+    /// While editing, Enter applies the text, Escape discards it and Backspace deletes.
+    /// Otherwise Up/Down step the value, Page Up/Down by ten steps, and Enter starts
+    /// editing.
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        if let Some(edit_text) = &mut self.edit_text {
+            match key {
+                Key::Enter => return self.commit_edit(),
+                Key::Escape => self.cancel_edit(),
+                Key::Backspace => {
+                    if self.replace_on_type {
+                        edit_text.clear();
+                        self.replace_on_type = false;
+                    } else {
+                        edit_text.pop();
+                    }
+                }
+                _ => {}
+            }
+            return None;
+        }
+        match key {
+            Key::Up => self.nudge(1.0, 1.0),
+            Key::Down => self.nudge(-1.0, 1.0),
+            Key::PageUp => self.nudge(1.0, 10.0),
+            Key::PageDown => self.nudge(-1.0, 10.0),
+            Key::Enter => {
+                self.begin_edit();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn speed_factor(&self, modifiers: Modifiers) -> f32 {
+        if modifiers.shift {
+            self.fine_factor
+        } else if modifiers.ctrl {
+            self.coarse_factor
+        } else {
+            1.0
+        }
+    }
+
+    fn nudge(&mut self, direction: f32, steps: f32) -> Option<Event> {
+        let step = if self.step_size > 0.0 {
+            self.step_size
+        } else {
+            10f32.powi(-(self.precision as i32))
+        };
+        self.change_to(self.value + direction * steps * step)
+    }
+
+    fn change_to(&mut self, value: f32) -> Option<Event> {
+        let value = self.constrain(value);
+        if value == self.value {
+            return None;
+        }
+        self.value = value;
+        Some(Event::NumberChanged { value })
+    }
+
+    fn constrain(&self, value: f32) -> f32 {
+        let value = if self.step_size > 0.0 {
+            (value / self.step_size).round() * self.step_size
+        } else {
+            value
+        };
+        // the bounds are pub, so don't let swapped ones panic
+        value.clamp(
+            self.minimum.min(self.maximum),
+            self.minimum.max(self.maximum),
+        )
+    }
+
+    fn sync_internal_buttons(&mut self) {
+        let arrow_size = Vec2::new(self.size.y * 0.8, self.size.y * 0.5);
+        let x = self.position.x + self.size.x - arrow_size.x;
+        self.increment_button.position = Vec2::new(x, self.position.y);
+        self.increment_button.size = arrow_size;
+        self.decrement_button.position = Vec2::new(x, self.position.y + arrow_size.y);
+        self.decrement_button.size = arrow_size;
+    }
+}
+
+/// Evaluate a number or an arithmetic expression with `+ - * /`, parentheses and unary
+/// minus, e.g. `"2 * (0.5 + 1)"`. Returns `None` for anything else.
+pub fn evaluate_expression(text: &str) -> Option<f32> {
+    let tokens: Vec<char> = text.chars().filter(|ch| !ch.is_whitespace()).collect();
+    let mut parser = ExpressionParser { tokens, index: 0 };
+    let value = parser.sum()?;
+    (parser.index == parser.tokens.len() && value.is_finite()).then_some(value)
+}
+
+struct ExpressionParser {
+    tokens: Vec<char>,
+    index: usize,
+}
+
+impl ExpressionParser {
+    fn peek(&self) -> Option<char> {
+        self.tokens.get(self.index).copied()
+    }
+
+    fn sum(&mut self) -> Option<f32> {
+        let mut value = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.index += 1;
+            let rhs = self.product()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    fn product(&mut self) -> Option<f32> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.index += 1;
+            let rhs = self.factor()?;
+            value = if op == '*' { value * rhs } else { value / rhs };
+        }
+        Some(value)
+    }
+
+    fn factor(&mut self) -> Option<f32> {
+        match self.peek()? {
+            '-' => {
+                self.index += 1;
+                Some(-self.factor()?)
+            }
+            '(' => {
+                self.index += 1;
+                let value = self.sum()?;
+                if self.peek()? != ')' {
+                    return None;
+                }
+                self.index += 1;
+                Some(value)
+            }
+            _ => {
+                let start = self.index;
+                while self
+                    .peek()
+                    .is_some_and(|ch| ch.is_ascii_digit() || ch == '.')
+                {
+                    self.index += 1;
+                }
+                self.tokens[start..self.index]
+                    .iter()
+                    .collect::<String>()
+                    .parse()
+                    .ok()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluates_numbers_and_expressions() {
+        let cases = [
+            ("42", 42.0),
+            (" 1.5 ", 1.5),
+            (".5", 0.5),
+            ("-3", -3.0),
+            ("--3", 3.0),
+            ("1 + 2 * 3", 7.0),
+            ("(1 + 2) * 3", 9.0),
+            ("2 * (0.5 + 1)", 3.0),
+            ("10 - 4 - 3", 3.0),
+            ("8 / 4 / 2", 1.0),
+            ("-(2 + 3)", -5.0),
+        ];
+        for (text, value) in cases {
+            assert_eq!(evaluate_expression(text), Some(value), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_and_non_finite_input() {
+        for text in [
+            "", "abc", "1/0", "0/0", "((1", "(1", "1)", "1 +", "* 2", "1..2", "1e3", "+1",
+        ] {
+            assert_eq!(evaluate_expression(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn swapped_bounds_do_not_panic() {
+        let mut field = NumberField::new(Vec2::ZERO, Vec2::new(80.0, 20.0), 10.0, 0.0, 1.0, 20.0);
+        assert_eq!((field.minimum, field.maximum), (0.0, 10.0));
+        assert_eq!(field.value, 10.0);
+        field.minimum = 5.0;
+        field.maximum = -5.0;
+        field.set_value(7.0);
+        assert_eq!(field.value, 5.0);
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Color, FontId, ImageStyle, Rect, StyleOverride, ELEMENT_NEXT_ID};
+
+/// Which edge a `ProgressBar` fills from.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ProgressDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+    BottomToTop,
+    TopToBottom,
+}
+
+/// Text drawn centered on a `ProgressBar`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ProgressText {
+    #[default]
+    None,
+    /// `62`
+    Value,
+    /// `62%`
+    Percent,
+    /// `62/100`
+    Fraction,
+}
+
+/// Display-only bar showing `value` between `minimum` and `maximum`, e.g. HP or loading.
+pub struct ProgressBar {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub minimum: f32,
+    pub maximum: f32,
+    pub value: f32,
+    pub direction: ProgressDirection,
+    /// Number of equal segments separated by tick marks. `0` and `1` draw no ticks.
+    pub segments: u32,
+    pub text: ProgressText,
+    /// Fill color, `None` uses `Theme::progress_color`.
+    pub fill_color: Option<Color>,
+    /// Trail color, `None` uses `Theme::progress_trail_color`.
+    pub trail_color: Option<Color>,
+    pub background_image: Option<ImageStyle>,
+    /// Drawn over the whole bar and clipped to the filled part.
+    pub fill_image: Option<ImageStyle>,
+    pub trail_image: Option<ImageStyle>,
+    /// When the value drops, a trail bar stays at the old value and then catches up.
+    pub trail: bool,
+    /// Value the trail bar currently shows.
+    pub trail_value: f32,
+    /// Seconds the trail waits before catching up.
+    pub trail_delay: f32,
+    /// Catch-up speed in fractions of the full range per second.
+    pub trail_speed: f32,
+    /// Seconds since the value last dropped.
+    pub trail_timer: f32,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+}
+
+impl ProgressBar {
+    pub fn new(position: Vec2, size: Vec2, minimum: f32, maximum: f32, value: f32) -> Self {
+        let value = value.clamp(minimum.min(maximum), maximum.max(minimum));
+        Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            minimum,
+            maximum,
+            value,
+            direction: ProgressDirection::LeftToRight,
+            segments: 0,
+            text: ProgressText::None,
+            fill_color: None,
+            trail_color: None,
+            background_image: None,
+            fill_image: None,
+            trail_image: None,
+            trail: false,
+            trail_value: value,
+            trail_delay: 0.4,
+            trail_speed: 0.5,
+            trail_timer: 0.0,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+        }
+    }
+
+    pub fn set_direction(&mut self, direction: ProgressDirection) {
+        self.direction = direction;
+    }
+
+    pub fn set_segments(&mut self, segments: u32) {
+        self.segments = segments;
+    }
+
+    pub fn set_text(&mut self, text: ProgressText) {
+        self.text = text;
+    }
+
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = Some(color);
+    }
+
+    pub fn set_trail_color(&mut self, color: Color) {
+        self.trail_color = Some(color);
+    }
+
+    pub fn set_background_image(&mut self, image: ImageStyle) {
+        self.background_image = Some(image);
+    }
+
+    pub fn set_fill_image(&mut self, image: ImageStyle) {
+        self.fill_image = Some(image);
+    }
+
+    pub fn set_trail_image(&mut self, image: ImageStyle) {
+        self.trail_image = Some(image);
+    }
+
+    /// Turn the damage trail on or off. It starts at the current value.
+    pub fn set_trail(&mut self, trail: bool) {
+        self.trail = trail;
+        self.trail_value = self.value;
+        self.trail_timer = 0.0;
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// Set the value, clamped to the range. A drop leaves the trail behind; a rise
+    /// pulls the trail up with it.
+    pub fn set_value(&mut self, value: f32) {
+        let value = value.clamp(
+            self.minimum.min(self.maximum),
+            self.maximum.max(self.minimum),
+        );
+        if self.trail && value < self.value {
+            self.trail_value = self.trail_value.max(self.value);
+            self.trail_timer = 0.0;
+        }
+        self.value = value;
+        if !self.trail || self.trail_value < value {
+            self.trail_value = value;
+        }
+    }
+
+    /// Filled part of the range, `0..=1`.
+    pub fn fraction(&self) -> f32 {
+        self.value_fraction(self.value)
+    }
+
+    /// Part of the range the trail reaches, `0..=1`.
+    pub fn trail_fraction(&self) -> f32 {
+        self.value_fraction(self.trail_value)
+    }
+
+    /// Part of the bar rect covering fractions `from..to` of the range, following
+    /// `direction`.
+    pub fn part_rect(&self, from: f32, to: f32) -> Rect {
+        let (from, to) = (from.clamp(0.0, 1.0), to.clamp(0.0, 1.0));
+        let (position, size) = (self.position, self.size);
+        match self.direction {
+            ProgressDirection::LeftToRight => Rect::new(
+                position + Vec2::new(size.x * from, 0.0),
+                Vec2::new(size.x * (to - from), size.y),
+            ),
+            ProgressDirection::RightToLeft => Rect::new(
+                position + Vec2::new(size.x * (1.0 - to), 0.0),
+                Vec2::new(size.x * (to - from), size.y),
+            ),
+            ProgressDirection::TopToBottom => Rect::new(
+                position + Vec2::new(0.0, size.y * from),
+                Vec2::new(size.x, size.y * (to - from)),
+            ),
+            ProgressDirection::BottomToTop => Rect::new(
+                position + Vec2::new(0.0, size.y * (1.0 - to)),
+                Vec2::new(size.x, size.y * (to - from)),
+            ),
+        }
+    }
+
+    /// The value text to draw, if any.
+    pub fn value_text(&self) -> Option<String> {
+        match self.text {
+            ProgressText::None => None,
+            ProgressText::Value => Some(format!("{:.0}", self.value)),
+            ProgressText::Percent => Some(format!("{:.0}%", self.fraction() * 100.0)),
+            ProgressText::Fraction => Some(format!("{:.0}/{:.0}", self.value, self.maximum)),
+        }
+    }
+
+    /// Move the trail toward the value. Called by `Gui::update`.
+    pub fn update(&mut self, dt: f32) {
+        if self.trail_value <= self.value {
+            self.trail_value = self.value;
+            return;
+        }
+        self.trail_timer += dt;
+        if self.trail_timer < self.trail_delay {
+            return;
+        }
+        let range = (self.maximum - self.minimum).abs();
+        self.trail_value = (self.trail_value - self.trail_speed * range * dt).max(self.value);
+    }
+
+    fn value_fraction(&self, value: f32) -> f32 {
+        let range = self.maximum - self.minimum;
+        if range == 0.0 {
+            return 0.0;
+        }
+        ((value - self.minimum) / range).clamp(0.0, 1.0)
+    }
+}
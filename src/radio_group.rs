@@ -0,0 +1,159 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Button, Event, FontId, Key, StyleOverride, ELEMENT_NEXT_ID};
+
+/// Direction `RadioGroup` options are laid out in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RadioLayout {
+    #[default]
+    Vertical,
+    Horizontal,
+}
+
+/// A set of options where exactly one is selected.
+pub struct RadioGroup {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub options: Vec<String>,
+    pub selected_index: usize,
+    pub layout: RadioLayout,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    /// One per option, splitting the group rect evenly along `layout`.
+    pub option_buttons: Vec<Button>,
+}
+
+impl RadioGroup {
+    pub fn new(
+        position: Vec2,
+        size: Vec2,
+        options: Vec<String>,
+        selected_index: usize,
+        layout: RadioLayout,
+    ) -> Self {
+        let option_buttons = options
+            .iter()
+            .map(|_| Button::new(position, Vec2::ZERO, None))
+            .collect();
+        let mut group = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            selected_index: selected_index.min(options.len().saturating_sub(1)),
+            options,
+            layout,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            option_buttons,
+        };
+        group.sync_internal_buttons();
+        group
+    }
+
+    /// Replace the options, keeping the selection when it is still in range.
+    pub fn set_options(&mut self, options: Vec<String>) {
+        self.option_buttons = options
+            .iter()
+            .map(|_| Button::new(self.position, Vec2::ZERO, None))
+            .collect();
+        self.selected_index = self.selected_index.min(options.len().saturating_sub(1));
+        self.options = options;
+        self.sync_internal_buttons();
+    }
+
+    pub fn selected_option(&self) -> Option<&str> {
+        self.options.get(self.selected_index).map(String::as_str)
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.sync_internal_buttons();
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+        self.sync_internal_buttons();
+    }
+
+    pub fn set_layout(&mut self, layout: RadioLayout) {
+        self.layout = layout;
+        self.sync_internal_buttons();
+    }
+
+    pub fn cancel_press(&mut self) {
+        for button in self.option_buttons.iter_mut() {
+            button.cancel_press();
+        }
+    }
+
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        let mut released = None;
+        for (index, button) in self.option_buttons.iter_mut().enumerate() {
+            let event = button.step(mouse_position, mouse_pressed);
+            if matches!(event, Some(Event::ButtonReleased)) && button.hovered {
+                released = Some(index);
+            }
+        }
+        self.select(released?)
+    }
+
+    /// Arrow keys move the selection while focused.
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        let last = self.options.len().checked_sub(1)?;
+        let index = match key {
+            Key::Up | Key::Left => self.selected_index.saturating_sub(1),
+            Key::Down | Key::Right => (self.selected_index + 1).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            _ => return None,
+        };
+        self.select(index)
+    }
+
+    fn select(&mut self, index: usize) -> Option<Event> {
+        if index == self.selected_index {
+            return None;
+        }
+        self.selected_index = index;
+        Some(Event::RadioSelected { index })
+    }
+
+    fn sync_internal_buttons(&mut self) {
+        let count = self.option_buttons.len().max(1) as f32;
+        let step = match self.layout {
+            RadioLayout::Vertical => Vec2::new(0.0, self.size.y / count),
+            RadioLayout::Horizontal => Vec2::new(self.size.x / count, 0.0),
+        };
+        let option_size = match self.layout {
+            RadioLayout::Vertical => Vec2::new(self.size.x, step.y),
+            RadioLayout::Horizontal => Vec2::new(step.x, self.size.y),
+        };
+        for (index, button) in self.option_buttons.iter_mut().enumerate() {
+            button.position = self.position + step * index as f32;
+            button.size = option_size;
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::slider::snap_slider_value;
+use super::{Event, ImageStyle, Rect, StateImages, StyleOverride, ELEMENT_NEXT_ID};
+
+/// Part of a `RangeSlider` under the mouse or being dragged.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RangeSliderPart {
+    Low,
+    High,
+    /// The segment between the thumbs. Dragging it moves both.
+    Range,
+}
+
+/// A slider with two thumbs selecting `low..=high`, e.g. "level 10 to 25". The thumbs
+/// can't cross. Horizontal by default with the minimum at the left; vertical sliders
+/// have the minimum at the top like `VerticalSlider`. `step_size` and
+/// `snap_sensetivity_fraction` snap like `Slider`.
+pub struct RangeSlider {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    /// Thumb extent along the slider.
+    pub thumb_size: f32,
+    pub minimum: f32,
+    pub maximum: f32,
+    pub step_size: f32,
+    pub snap_sensetivity_fraction: f32,
+    pub low: f32,
+    pub high: f32,
+    pub vertical: bool,
+    pub track_image: Option<ImageStyle>,
+    /// Drawn over the segment between the thumbs instead of the selection color.
+    pub range_image: Option<ImageStyle>,
+    pub thumb_image: Option<ImageStyle>,
+    /// Per-state thumb skin drawn instead of the theme's bevel box.
+    pub thumb_state_images: Option<StateImages>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    pub hovered: bool,
+    pub hovered_part: Option<RangeSliderPart>,
+    pub dragging: Option<RangeSliderPart>,
+    /// Mouse position along the slider and `(low, high)` when the drag started.
+    pub drag_origin: (f32, f32, f32),
+    pub was_mouse_pressed: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+impl RangeSlider {
+    pub fn new(
+        position: Vec2,
+        size: Vec2,
+        thumb_size: f32,
+        minimum: f32,
+        maximum: f32,
+        step_size: f32,
+        low: f32,
+        high: f32,
+        snap_sensetivity_fraction: f32,
+    ) -> Self {
+        let (minimum, maximum) = (minimum.min(maximum), minimum.max(maximum));
+        let low = low.clamp(minimum, maximum);
+        Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            thumb_size,
+            minimum,
+            maximum,
+            step_size,
+            snap_sensetivity_fraction,
+            low,
+            high: high.clamp(low, maximum),
+            vertical: false,
+            track_image: None,
+            range_image: None,
+            thumb_image: None,
+            thumb_state_images: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            hovered: false,
+            hovered_part: None,
+            dragging: None,
+            drag_origin: (0.0, low, high),
+            was_mouse_pressed: false,
+        }
+    }
+
+    pub fn set_vertical(&mut self, vertical: bool) {
+        self.vertical = vertical;
+    }
+
+    pub fn set_track_image(&mut self, image: ImageStyle) {
+        self.track_image = Some(image);
+    }
+
+    pub fn set_range_image(&mut self, image: ImageStyle) {
+        self.range_image = Some(image);
+    }
+
+    pub fn set_thumb_image(&mut self, image: ImageStyle) {
+        self.thumb_image = Some(image);
+    }
+
+    pub fn set_thumb_state_images(&mut self, images: StateImages) {
+        self.thumb_state_images = Some(images);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// Set both ends without an event. `high` is raised to `low` if it's below it.
+    pub fn set_range(&mut self, low: f32, high: f32) {
+        let (minimum, maximum) = self.bounds();
+        self.low = low.clamp(minimum, maximum);
+        self.high = high.clamp(self.low, maximum);
+    }
+
+    /// Pixel position of `value` along the slider.
+    pub fn value_to_axis(&self, value: f32) -> f32 {
+        let range = self.maximum - self.minimum;
+        let fraction = if range.abs() <= f32::EPSILON {
+            0.0
+        } else {
+            ((value - self.minimum) / range).clamp(0.0, 1.0)
+        };
+        self.axis(self.position) + fraction * self.axis(self.size)
+    }
+
+    /// Rect of a thumb centered on `value`, `low` or `high`.
+    pub fn thumb_rect(&self, value: f32) -> Rect {
+        let start = self.value_to_axis(value) - self.thumb_size * 0.5;
+        self.span(start, self.thumb_size)
+    }
+
+    /// The segment between the thumb centers.
+    pub fn range_rect(&self) -> Rect {
+        let start = self.value_to_axis(self.low);
+        self.span(start, self.value_to_axis(self.high) - start)
+    }
+
+    /// Part under `point`. Thumbs win over the segment, and where the thumbs overlap the
+    /// side of their shared center picks one.
+    pub fn part_at(&self, point: Vec2) -> Option<RangeSliderPart> {
+        let on_low = self.thumb_rect(self.low).contains(point);
+        let on_high = self.thumb_rect(self.high).contains(point);
+        match (on_low, on_high) {
+            (true, true) if self.axis(point) < self.value_to_axis(self.low) => {
+                Some(RangeSliderPart::Low)
+            }
+            (true, true) => Some(RangeSliderPart::High),
+            (true, false) => Some(RangeSliderPart::Low),
+            (false, true) => Some(RangeSliderPart::High),
+            _ if self.range_rect().contains(point) => Some(RangeSliderPart::Range),
+            _ => None,
+        }
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.hovered = false;
+        self.hovered_part = None;
+        self.dragging = None;
+    }
+
+    /// Pressing a thumb or the segment drags it. Pressing the track elsewhere moves the
+    /// nearer thumb there and drags it.
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+        self.hovered = Rect::new(self.position, self.size).contains(mouse_position);
+        self.hovered_part = if self.hovered {
+            self.part_at(mouse_position)
+        } else {
+            None
+        };
+
+        if !mouse_pressed {
+            self.dragging = None;
+            return None;
+        }
+        if press_started && self.hovered {
+            let part = self.hovered_part.unwrap_or_else(|| {
+                let value = self.axis_to_value(self.axis(mouse_position));
+                if (value - self.low).abs() <= (value - self.high).abs() {
+                    RangeSliderPart::Low
+                } else {
+                    RangeSliderPart::High
+                }
+            });
+            self.dragging = Some(part);
+            self.drag_origin = (self.axis(mouse_position), self.low, self.high);
+        }
+        let part = self.dragging?;
+
+        let (old_low, old_high) = (self.low, self.high);
+        let value = self.snap(self.axis_to_value(self.axis(mouse_position)));
+        match part {
+            RangeSliderPart::Low => self.low = value.min(self.high),
+            RangeSliderPart::High => self.high = value.max(self.low),
+            RangeSliderPart::Range => {
+                let (origin, low, high) = self.drag_origin;
+                let width = high - low;
+                let delta =
+                    self.axis_to_value(self.axis(mouse_position)) - self.axis_to_value(origin);
+                // keep the width exact, so only the low end is snapped
+                let (minimum, maximum) = self.bounds();
+                self.low = self
+                    .snap(low + delta)
+                    .clamp(minimum, (maximum - width).max(minimum));
+                self.high = self.low + width;
+            }
+        }
+        if self.low == old_low && self.high == old_high {
+            return None;
+        }
+        Some(Event::RangeChanged {
+            low: self.low,
+            high: self.high,
+        })
+    }
+
+    fn snap(&self, value: f32) -> f32 {
+        let (minimum, maximum) = self.bounds();
+        snap_slider_value(
+            value,
+            minimum,
+            maximum,
+            self.step_size,
+            self.snap_sensetivity_fraction,
+        )
+        .clamp(minimum, maximum)
+    }
+
+    /// `(minimum, maximum)` in order. `new` orders them, but the fields are pub.
+    fn bounds(&self) -> (f32, f32) {
+        (
+            self.minimum.min(self.maximum),
+            self.minimum.max(self.maximum),
+        )
+    }
+
+    fn axis_to_value(&self, axis: f32) -> f32 {
+        let fraction = (axis - self.axis(self.position)) / self.axis(self.size).max(1.0);
+        self.minimum + fraction * (self.maximum - self.minimum)
+    }
+
+    /// The component of `v` along the slider.
+    fn axis(&self, v: Vec2) -> f32 {
+        if self.vertical {
+            v.y
+        } else {
+            v.x
+        }
+    }
+
+    /// Rect covering `start..start + length` along the slider and its full width across.
+    fn span(&self, start: f32, length: f32) -> Rect {
+        if self.vertical {
+            Rect::new(
+                Vec2::new(self.position.x, start),
+                Vec2::new(self.size.x, length),
+            )
+        } else {
+            Rect::new(
+                Vec2::new(start, self.position.y),
+                Vec2::new(length, self.size.y),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swapped_bounds_do_not_panic() {
+        let mut slider = RangeSlider::new(
+            Vec2::ZERO,
+            Vec2::new(100.0, 20.0),
+            10.0,
+            100.0,
+            0.0,
+            1.0,
+            10.0,
+            20.0,
+            0.0,
+        );
+        assert_eq!((slider.minimum, slider.maximum), (0.0, 100.0));
+        assert_eq!((slider.low, slider.high), (10.0, 20.0));
+        slider.minimum = 50.0;
+        slider.maximum = -50.0;
+        slider.set_range(-80.0, 80.0);
+        assert_eq!((slider.low, slider.high), (-50.0, 50.0));
+        slider.step(Vec2::new(0.0, 10.0), true);
+    }
+}
@@ -0,0 +1,502 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::{
+    draw_styled_image, theme::text_width, Color, DrawBackend, FontId, ImageStyle, Rect, Theme,
+};
+
+/// Style applied to one run of rich text. `None` fields use the widget's defaults.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RichStyle {
+    pub color: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    /// Index into `RichText::links` when this run is part of a link.
+    pub link: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RichContent {
+    Text(String),
+    /// Inline icon, resolved by name through `Gui::register_icon`.
+    Icon(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichRun {
+    pub content: RichContent,
+    pub style: RichStyle,
+}
+
+/// Parsed rich text markup.
+///
+/// Supported markup:
+/// - `[color=#f44]...[/color]` (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`)
+/// - `[b]...[/b]`, `[i]...[/i]` (mapped to `Theme` bold/italic fonts)
+/// - `[size=24]...[/size]`
+/// - `[font=2]...[/font]` (raw `FontId`)
+/// - `[link=target]...[/link]`
+/// - `{icon:name}`
+///
+/// Unknown or malformed tags are kept as literal text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichText {
+    pub runs: Vec<RichRun>,
+    /// Link targets, indexed by `RichStyle::link` and `Event::LinkClicked::link_index`.
+    pub links: Vec<String>,
+}
+
+impl RichText {
+    pub fn parse(markup: &str) -> Self {
+        let mut rich_text = Self::default();
+        let mut stack: Vec<(&str, RichStyle)> = vec![("", RichStyle::default())];
+        let mut text = String::new();
+        let mut rest = markup;
+
+        while let Some(ch) = rest.chars().next() {
+            let style = stack[stack.len() - 1].1;
+
+            if ch == '[' {
+                if let Some(end) = rest.find(']') {
+                    let tag = &rest[1..end];
+                    if let Some(name) = tag.strip_prefix('/') {
+                        if stack.len() > 1 && stack[stack.len() - 1].0 == name {
+                            rich_text.push_text(&mut text, style);
+                            stack.pop();
+                            rest = &rest[end + 1..];
+                            continue;
+                        }
+                    } else if let Some((name, new_style)) = rich_text.parse_open_tag(tag, style) {
+                        rich_text.push_text(&mut text, style);
+                        stack.push((name, new_style));
+                        rest = &rest[end + 1..];
+                        continue;
+                    }
+                }
+            }
+
+            if ch == '{' {
+                if let Some(end) = rest.find('}') {
+                    if let Some(name) = rest[1..end].strip_prefix("icon:") {
+                        rich_text.push_text(&mut text, style);
+                        rich_text.runs.push(RichRun {
+                            content: RichContent::Icon(name.trim().to_string()),
+                            style,
+                        });
+                        rest = &rest[end + 1..];
+                        continue;
+                    }
+                }
+            }
+
+            text.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+
+        let style = stack[stack.len() - 1].1;
+        rich_text.push_text(&mut text, style);
+        rich_text
+    }
+
+    /// Text with all markup removed and icons dropped.
+    pub fn plain_text(&self) -> String {
+        self.runs
+            .iter()
+            .filter_map(|run| match &run.content {
+                RichContent::Text(text) => Some(text.as_str()),
+                RichContent::Icon(_) => None,
+            })
+            .collect()
+    }
+
+    fn push_text(&mut self, text: &mut String, style: RichStyle) {
+        if text.is_empty() {
+            return;
+        }
+        self.runs.push(RichRun {
+            content: RichContent::Text(std::mem::take(text)),
+            style,
+        });
+    }
+
+    fn parse_open_tag<'a>(
+        &mut self,
+        tag: &'a str,
+        mut style: RichStyle,
+    ) -> Option<(&'a str, RichStyle)> {
+        let (name, value) = match tag.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim())),
+            None => (tag.trim(), None),
+        };
+        match (name, value) {
+            ("b", None) => style.bold = true,
+            ("i", None) => style.italic = true,
+            ("color", Some(value)) => style.color = Some(Color::from_hex(value)?),
+            ("size", Some(value)) => {
+                let size: f32 = value.parse().ok()?;
+                style.font_size_px = Some((size.is_finite() && size > 0.0).then_some(size)?);
+            }
+            ("font", Some(value)) => style.font = Some(FontId(value.parse().ok()?)),
+            ("link", Some(value)) => {
+                self.links.push(value.to_string());
+                style.link = Some(self.links.len() - 1);
+            }
+            _ => return None,
+        }
+        Some((name, style))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlacedRichContent {
+    Text {
+        text: String,
+        font: FontId,
+        font_size: f32,
+        color: Color,
+    },
+    Icon(ImageStyle),
+}
+
+/// One positioned piece of laid out rich text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlacedRichItem {
+    pub rect: Rect,
+    pub content: PlacedRichContent,
+    pub link: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichLayout {
+    pub items: Vec<PlacedRichItem>,
+    /// Total height of all lines, starting at the layout rect top.
+    pub height: f32,
+}
+
+impl RichLayout {
+    /// Link index under `point`, if any.
+    pub fn link_at(&self, point: Vec2) -> Option<usize> {
+        self.items
+            .iter()
+            .find(|item| item.link.is_some() && item.rect.contains(point))
+            .and_then(|item| item.link)
+    }
+}
+
+/// Lay out rich text runs inside `rect`, wrapping at word boundaries to `rect.size.x`.
+/// `font`/`font_size` are the defaults for runs without their own font or size.
+/// Icons are sized to the font size of their run.
+pub fn layout_rich_text<B: DrawBackend + ?Sized>(
+    backend: &B,
+    rich_text: &RichText,
+    rect: Rect,
+    font: FontId,
+    font_size: f32,
+    theme: &Theme,
+    icons: &HashMap<String, ImageStyle>,
+) -> RichLayout {
+    let mut layout = RichLayout::default();
+    let mut line: Vec<PlacedRichItem> = Vec::new();
+    let mut line_height = font_size;
+    let mut cursor = rect.position;
+    let max_x = rect.position.x + rect.size.x;
+
+    for run in &rich_text.runs {
+        let run_font = run
+            .style
+            .font
+            .unwrap_or_else(|| theme.styled_font(font, run.style.bold, run.style.italic));
+        let run_size = run.style.font_size_px.unwrap_or(font_size);
+
+        match &run.content {
+            RichContent::Icon(name) => {
+                let Some(image) = icons.get(name) else {
+                    continue;
+                };
+                if cursor.x + run_size > max_x && !line.is_empty() {
+                    finish_line(
+                        &mut layout,
+                        &mut line,
+                        &mut cursor,
+                        &mut line_height,
+                        rect,
+                        font_size,
+                    );
+                }
+                line_height = line_height.max(run_size);
+                line.push(PlacedRichItem {
+                    rect: Rect::new(cursor, Vec2::splat(run_size)),
+                    content: PlacedRichContent::Icon(*image),
+                    link: run.style.link,
+                });
+                cursor.x += run_size;
+            }
+            RichContent::Text(text) => {
+                let color = match run.style.link {
+                    Some(_) => run.style.color.unwrap_or(theme.link_color),
+                    None => run.style.color.unwrap_or(theme.text_color),
+                };
+                for (i, paragraph) in text.split('\n').enumerate() {
+                    if i > 0 {
+                        finish_line(
+                            &mut layout,
+                            &mut line,
+                            &mut cursor,
+                            &mut line_height,
+                            rect,
+                            font_size,
+                        );
+                    }
+                    for word in split_keeping_spaces(paragraph) {
+                        let at_line_start = line.is_empty();
+                        if at_line_start && word.trim().is_empty() {
+                            continue;
+                        }
+                        let width = text_width(backend, word, run_font, run_size);
+                        if cursor.x + width > max_x && !at_line_start {
+                            finish_line(
+                                &mut layout,
+                                &mut line,
+                                &mut cursor,
+                                &mut line_height,
+                                rect,
+                                font_size,
+                            );
+                            if word.trim().is_empty() {
+                                continue;
+                            }
+                        }
+                        line_height = line_height.max(run_size);
+                        push_text_piece(
+                            &mut line,
+                            Rect::new(cursor, Vec2::new(width, run_size)),
+                            word,
+                            run_font,
+                            run_size,
+                            color,
+                            run.style.link,
+                        );
+                        cursor.x += width;
+                    }
+                }
+            }
+        }
+    }
+
+    if !line.is_empty() {
+        finish_line(
+            &mut layout,
+            &mut line,
+            &mut cursor,
+            &mut line_height,
+            rect,
+            font_size,
+        );
+    }
+    layout
+}
+
+/// Draw a laid out rich text block. Link text is underlined.
+pub fn draw_rich_text<B: DrawBackend + ?Sized>(backend: &mut B, layout: &RichLayout) {
+    for item in &layout.items {
+        match &item.content {
+            PlacedRichContent::Text {
+                text,
+                font,
+                font_size,
+                color,
+            } => {
+                backend.draw_text_with_font(text, item.rect.position, *font, *font_size, *color);
+                if item.link.is_some() {
+                    let y = item.rect.position.y + item.rect.size.y;
+                    backend.draw_line(
+                        Vec2::new(item.rect.position.x, y),
+                        Vec2::new(item.rect.position.x + item.rect.size.x, y),
+                        *color,
+                        1.0,
+                    );
+                }
+            }
+            PlacedRichContent::Icon(image) => draw_styled_image(backend, *image, item.rect),
+        }
+    }
+}
+
+fn split_keeping_spaces(text: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+    for (i, ch) in text.char_indices() {
+        let is_space = ch == ' ';
+        if in_space.is_some_and(|was_space| was_space != is_space) {
+            pieces.push(&text[start..i]);
+            start = i;
+        }
+        in_space = Some(is_space);
+    }
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+    pieces
+}
+
+fn push_text_piece(
+    line: &mut Vec<PlacedRichItem>,
+    rect: Rect,
+    text: &str,
+    font: FontId,
+    font_size: f32,
+    color: Color,
+    link: Option<usize>,
+) {
+    // merge with the previous piece when it continues the same run
+    if let Some(last) = line.last_mut() {
+        if let PlacedRichContent::Text {
+            text: last_text,
+            font: last_font,
+            font_size: last_size,
+            color: last_color,
+        } = &mut last.content
+        {
+            if *last_font == font
+                && *last_size == font_size
+                && *last_color == color
+                && last.link == link
+            {
+                last_text.push_str(text);
+                last.rect.size.x += rect.size.x;
+                return;
+            }
+        }
+    }
+    line.push(PlacedRichItem {
+        rect,
+        content: PlacedRichContent::Text {
+            text: text.to_string(),
+            font,
+            font_size,
+            color,
+        },
+        link,
+    });
+}
+
+fn finish_line(
+    layout: &mut RichLayout,
+    line: &mut Vec<PlacedRichItem>,
+    cursor: &mut Vec2,
+    line_height: &mut f32,
+    rect: Rect,
+    min_line_height: f32,
+) {
+    // bottom-align items of different sizes on the line
+    for mut item in line.drain(..) {
+        item.rect.position.y += *line_height - item.rect.size.y;
+        layout.items.push(item);
+    }
+    cursor.x = rect.position.x;
+    cursor.y += *line_height;
+    layout.height = cursor.y - rect.position.y;
+    *line_height = min_line_height;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_runs(rich_text: &RichText) -> Vec<(&str, RichStyle)> {
+        rich_text
+            .runs
+            .iter()
+            .map(|run| match &run.content {
+                RichContent::Text(text) => (text.as_str(), run.style),
+                RichContent::Icon(name) => (name.as_str(), run.style),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plain_markup_is_one_default_run() {
+        let rich_text = RichText::parse("just text");
+        assert_eq!(text_runs(&rich_text), [("just text", RichStyle::default())]);
+        assert!(rich_text.links.is_empty());
+    }
+
+    #[test]
+    fn nested_tags_stack_styles() {
+        let rich_text = RichText::parse("a[b]b[i]bi[/i][/b]c");
+        let bold = RichStyle {
+            bold: true,
+            ..RichStyle::default()
+        };
+        let bold_italic = RichStyle {
+            italic: true,
+            ..bold
+        };
+        assert_eq!(
+            text_runs(&rich_text),
+            [
+                ("a", RichStyle::default()),
+                ("b", bold),
+                ("bi", bold_italic),
+                ("c", RichStyle::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn value_tags_set_color_size_font_and_links() {
+        let rich_text = RichText::parse(
+            "[color=#f00]red[/color][size=24]big[/size][font=3]f[/font][link=home]go[/link]",
+        );
+        let styles: Vec<RichStyle> = rich_text.runs.iter().map(|run| run.style).collect();
+        assert_eq!(styles[0].color, Some(Color::rgb(255, 0, 0)));
+        assert_eq!(styles[1].font_size_px, Some(24.0));
+        assert_eq!(styles[2].font, Some(FontId(3)));
+        assert_eq!(styles[3].link, Some(0));
+        assert_eq!(rich_text.links, ["home"]);
+        assert_eq!(rich_text.plain_text(), "redbigfgo");
+    }
+
+    #[test]
+    fn icons_become_their_own_runs() {
+        let rich_text = RichText::parse("hp {icon: heart } left");
+        assert_eq!(
+            rich_text.runs[1].content,
+            RichContent::Icon("heart".to_string())
+        );
+        assert_eq!(rich_text.plain_text(), "hp  left");
+    }
+
+    #[test]
+    fn unknown_and_malformed_tags_stay_literal() {
+        for markup in [
+            "[u]under[/u]",
+            "[color=#xyz]bad color[/color]",
+            "[size=big]bad size[/size]",
+            "[size=NaN]nan[/size]",
+            "[size=inf]inf[/size]",
+            "[size=0]zero[/size]",
+            "[size=-4]negative[/size]",
+            "[b unclosed",
+            "{notanicon} {icon:open",
+        ] {
+            let rich_text = RichText::parse(markup);
+            assert_eq!(rich_text.plain_text(), markup, "{markup}");
+            assert!(rich_text
+                .runs
+                .iter()
+                .all(|run| run.style == RichStyle::default()));
+        }
+    }
+
+    #[test]
+    fn mismatched_close_tags_stay_literal_and_unclosed_tags_run_to_the_end() {
+        let rich_text = RichText::parse("[b]a[/i]b");
+        assert_eq!(rich_text.plain_text(), "a[/i]b");
+        assert!(rich_text.runs.iter().all(|run| run.style.bold));
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::atomic::Ordering;
 
 use glam::Vec2;
 
-use super::{Event, ImageStyle, ELEMENT_NEXT_ID};
+use super::{Event, ImageStyle, StateImages, StyleOverride, ELEMENT_NEXT_ID};
 
 pub struct Slider {
     pub id: u32,
@@ -17,6 +17,10 @@ pub struct Slider {
     pub label: Option<String>,
     pub track_image: Option<ImageStyle>,
     pub thumb_image: Option<ImageStyle>,
+    /// Per-state thumb skin drawn instead of the theme's bevel box.
+    pub thumb_state_images: Option<StateImages>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
     pub hovered: bool,
     pub was_pressed: bool,
 }
@@ -47,6 +51,9 @@ impl Slider {
             label,
             track_image: None,
             thumb_image: None,
+            thumb_state_images: None,
+            style_class: None,
+            style: StyleOverride::default(),
             hovered: false,
             was_pressed: false,
         }
@@ -60,6 +67,23 @@ impl Slider {
         self.thumb_image = Some(image);
     }
 
+    pub fn set_thumb_state_images(&mut self, images: StateImages) {
+        self.thumb_state_images = Some(images);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.hovered = false;
+        self.was_pressed = false;
+    }
+
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
         let mut event: Option<Event> = None;
 
@@ -83,23 +107,13 @@ impl Slider {
                 let total = br.x - tl.x;
                 let local_p = mouse_position.x - tl.x;
                 let fraction = local_p / total;
-                self.value = self.minimum + fraction * (self.maximum - self.minimum);
-
-                // if value is within 5% of the minimum or maximum, snap to it
-                if self.snap_sensetivity_fraction > 0.0 {
-                    if self.value > self.maximum * (1.0 - self.snap_sensetivity_fraction) {
-                        self.value = self.maximum;
-                    }
-                    if self.value < (self.maximum - self.minimum) * self.snap_sensetivity_fraction {
-                        self.value = self.minimum;
-                    }
-                }
-
-                // round to nearest 100th, needs to work for negative and 0
-                self.value = (self.value * 100.0).round() / 100.0;
-
-                // round to nearest step size
-                self.value = (self.value / self.step_size).round() * self.step_size;
+                self.value = snap_slider_value(
+                    self.minimum + fraction * (self.maximum - self.minimum),
+                    self.minimum,
+                    self.maximum,
+                    self.step_size,
+                    self.snap_sensetivity_fraction,
+                );
 
                 // only emit event if value changed
                 if self.value != old_value {
@@ -115,3 +129,32 @@ impl Slider {
         event
     }
 }
+
+/// Snapping shared by the sliders: values within `snap_sensetivity_fraction` of the
+/// ends snap to them, then the value is rounded to hundredths and to the nearest
+/// multiple of `step_size`.
+pub(crate) fn snap_slider_value(
+    value: f32,
+    minimum: f32,
+    maximum: f32,
+    step_size: f32,
+    snap_sensetivity_fraction: f32,
+) -> f32 {
+    let mut value = value;
+
+    // if value is within 5% of the minimum or maximum, snap to it
+    if snap_sensetivity_fraction > 0.0 {
+        if value > maximum * (1.0 - snap_sensetivity_fraction) {
+            value = maximum;
+        }
+        if value < (maximum - minimum) * snap_sensetivity_fraction {
+            value = minimum;
+        }
+    }
+
+    // round to nearest 100th, needs to work for negative and 0
+    value = (value * 100.0).round() / 100.0;
+
+    // round to nearest step size
+    (value / step_size).round() * step_size
+}
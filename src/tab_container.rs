@@ -0,0 +1,290 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Button, Event, FontId, ImageStyle, Key, Rect, StyleOverride, ELEMENT_NEXT_ID};
+
+/// One tab of a `TabContainer`: its caption and the elements on its page.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tab {
+    pub label: Option<String>,
+    /// Drawn before the label, or alone for icon tabs.
+    pub icon: Option<ImageStyle>,
+    /// Elements shown only while this tab is selected.
+    pub page_ids: Vec<u32>,
+}
+
+impl Tab {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: Some(label.to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub fn icon(icon: ImageStyle) -> Self {
+        Self {
+            icon: Some(icon),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_icon(mut self, icon: ImageStyle) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn with_page_ids(mut self, page_ids: Vec<u32>) -> Self {
+        self.page_ids = page_ids;
+        self
+    }
+}
+
+/// A tab strip over a page area. The `Gui` shows the selected tab's page elements and
+/// hides the others.
+pub struct TabContainer {
+    pub id: u32,
+    pub position: Vec2,
+    /// Tab strip and page together.
+    pub size: Vec2,
+    pub tab_height: f32,
+    pub tab_width: f32,
+    pub tabs: Vec<Tab>,
+    pub selected_index: usize,
+    /// Pixels the tab strip is scrolled when the tabs don't fit.
+    pub scroll_offset: f32,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    /// Drawn behind unselected tabs instead of `Theme::tab_color`.
+    pub tab_image: Option<ImageStyle>,
+    /// Drawn behind the selected tab instead of `Theme::tab_selected_color`.
+    pub selected_tab_image: Option<ImageStyle>,
+    /// One per tab, laid out along the strip.
+    pub tab_buttons: Vec<Button>,
+    /// Strip scroll arrows, only shown when the tabs overflow.
+    pub scroll_left_button: Button,
+    pub scroll_right_button: Button,
+}
+
+impl TabContainer {
+    pub fn new(
+        position: Vec2,
+        size: Vec2,
+        tab_height: f32,
+        tab_width: f32,
+        tabs: Vec<Tab>,
+    ) -> Self {
+        let tab_buttons = tabs
+            .iter()
+            .map(|_| Button::new(position, Vec2::ZERO, None))
+            .collect();
+        let mut container = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            tab_height,
+            tab_width,
+            tabs,
+            selected_index: 0,
+            scroll_offset: 0.0,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            tab_image: None,
+            selected_tab_image: None,
+            tab_buttons,
+            scroll_left_button: Button::new(position, Vec2::ZERO, Some("<".to_string())),
+            scroll_right_button: Button::new(position, Vec2::ZERO, Some(">".to_string())),
+        };
+        container.sync_internal_buttons();
+        container
+    }
+
+    /// Add a tab at the end. Use `Gui::sync_tab_pages` afterwards if it has page ids.
+    pub fn add_tab(&mut self, tab: Tab) {
+        self.tabs.push(tab);
+        self.tab_buttons
+            .push(Button::new(self.position, Vec2::ZERO, None));
+        self.sync_internal_buttons();
+    }
+
+    pub fn selected_tab(&self) -> Option<&Tab> {
+        self.tabs.get(self.selected_index)
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn set_tab_image(&mut self, image: ImageStyle) {
+        self.tab_image = Some(image);
+    }
+
+    pub fn set_selected_tab_image(&mut self, image: ImageStyle) {
+        self.selected_tab_image = Some(image);
+    }
+
+    pub fn set_position(&mut self, position: Vec2) {
+        self.position = position;
+        self.sync_internal_buttons();
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+        self.sync_internal_buttons();
+    }
+
+    /// Area below the tab strip where page elements go.
+    pub fn page_rect(&self) -> Rect {
+        Rect::new(
+            self.position + Vec2::new(0.0, self.tab_height),
+            Vec2::new(self.size.x, (self.size.y - self.tab_height).max(0.0)),
+        )
+    }
+
+    /// Whether the tabs are wider than the container and the strip scrolls.
+    pub fn overflows(&self) -> bool {
+        self.tabs.len() as f32 * self.tab_width > self.size.x
+    }
+
+    /// Part of the strip tabs are visible in, left of the scroll arrows.
+    pub fn strip_rect(&self) -> Rect {
+        let arrows_width = if self.overflows() {
+            self.tab_height * 2.0
+        } else {
+            0.0
+        };
+        Rect::new(
+            self.position,
+            Vec2::new((self.size.x - arrows_width).max(0.0), self.tab_height),
+        )
+    }
+
+    /// Scroll the strip by `pixels`, positive scrolls right.
+    pub fn scroll_by(&mut self, pixels: f32) {
+        let max_scroll =
+            (self.tabs.len() as f32 * self.tab_width - self.strip_rect().size.x).max(0.0);
+        self.scroll_offset = (self.scroll_offset + pixels).clamp(0.0, max_scroll);
+        self.sync_internal_buttons();
+    }
+
+    /// Scroll the strip just enough to show tab `index`.
+    pub fn scroll_to(&mut self, index: usize) {
+        let left = index as f32 * self.tab_width;
+        let strip_width = self.strip_rect().size.x;
+        if left < self.scroll_offset {
+            self.scroll_offset = left;
+        } else if left + self.tab_width > self.scroll_offset + strip_width {
+            self.scroll_offset = left + self.tab_width - strip_width;
+        }
+        self.scroll_by(0.0);
+    }
+
+    /// Select a tab without emitting an event. The `Gui` updates page visibility on its
+    /// next `step`, or right away through `Gui::select_tab`.
+    pub fn set_selected_index(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.selected_index = index;
+            self.scroll_to(index);
+        }
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.scroll_left_button.cancel_press();
+        self.scroll_right_button.cancel_press();
+        for button in self.tab_buttons.iter_mut() {
+            button.cancel_press();
+        }
+    }
+
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        if self.overflows() {
+            if matches!(
+                self.scroll_left_button.step(mouse_position, mouse_pressed),
+                Some(Event::ButtonPressed)
+            ) {
+                self.scroll_by(-self.tab_width);
+            }
+            if matches!(
+                self.scroll_right_button.step(mouse_position, mouse_pressed),
+                Some(Event::ButtonPressed)
+            ) {
+                self.scroll_by(self.tab_width);
+            }
+        }
+
+        // tabs scrolled out of the strip can't be clicked
+        let tab_mouse = if self.strip_rect().contains(mouse_position) {
+            mouse_position
+        } else {
+            Vec2::new(-1.0, -1.0)
+        };
+        let mut pressed = None;
+        for (index, button) in self.tab_buttons.iter_mut().enumerate() {
+            if matches!(
+                button.step(tab_mouse, mouse_pressed),
+                Some(Event::ButtonPressed)
+            ) {
+                pressed = Some(index);
+            }
+        }
+        self.select(pressed?)
+    }
+
+    /// Left/Right and Home/End switch tabs while focused.
+    pub fn key_pressed(&mut self, key: Key) -> Option<Event> {
+        let last = self.tabs.len().checked_sub(1)?;
+        let index = match key {
+            Key::Left => self.selected_index.saturating_sub(1),
+            Key::Right => (self.selected_index + 1).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            _ => return None,
+        };
+        self.select(index)
+    }
+
+    fn select(&mut self, index: usize) -> Option<Event> {
+        self.scroll_to(index);
+        if index == self.selected_index {
+            return None;
+        }
+        self.selected_index = index;
+        Some(Event::TabChanged { index })
+    }
+
+    fn sync_internal_buttons(&mut self) {
+        let tab_size = Vec2::new(self.tab_width, self.tab_height);
+        for (index, button) in self.tab_buttons.iter_mut().enumerate() {
+            button.position =
+                self.position + Vec2::new(index as f32 * self.tab_width - self.scroll_offset, 0.0);
+            button.size = tab_size;
+        }
+
+        let arrow_size = if self.overflows() {
+            Vec2::splat(self.tab_height)
+        } else {
+            Vec2::ZERO
+        };
+        let right = self.position.x + self.size.x;
+        self.scroll_left_button.position = Vec2::new(right - arrow_size.x * 2.0, self.position.y);
+        self.scroll_left_button.size = arrow_size;
+        self.scroll_right_button.position = Vec2::new(right - arrow_size.x, self.position.y);
+        self.scroll_right_button.size = arrow_size;
+    }
+}
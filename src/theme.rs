@@ -1,21 +1,213 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
 use glam::Vec2;
 
+use crate::animation::VisualBackend;
 use crate::{
-    Button, ButtonToggle, Color, Draggable, DrawBackend, Gui, ImageStyle, Label, LeftRightSelector,
-    MoveAndResizeThumbs, Rect, Slider, VerticalSlider,
+    draw_rich_text, draw_styled_image, estimate_text_width, layout_rich_text, place_tooltip,
+    Button, ButtonToggle, Checkbox, Color, ColorPicker, ContextMenu, Draggable, DrawBackend,
+    Dropdown, FontId, Gui, ImageStyle, Label, LeftRightSelector, ListRow, ListView, Menu, MenuBar,
+    MenuPopup, MessageBox, MoveAndResizeThumbs, NumberField, ProgressBar, ProgressDirection,
+    RadioGroup, RangeSlider, RangeSliderPart, Rect, RichLayout, Slider, StateImages, TabContainer,
+    TooltipContent, TreeView, VerticalSlider, WidgetState, XYPad,
 };
 
+/// Which theme font a piece of text uses when its widget has no font override.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TextRole {
+    /// Window titles and drag handles.
+    Title,
+    /// Labels and selector values.
+    #[default]
+    Body,
+    /// Button captions.
+    Button,
+}
+
+/// Font for one theme role. `size_px` of `None` uses `Theme::font_size_px`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FontStyle {
+    pub font: FontId,
+    pub size_px: Option<f32>,
+}
+
+/// Partial `Theme` override used by style classes and per-widget styles.
+/// `None` fields keep the value from the level below.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StyleOverride {
+    pub control_color: Option<Color>,
+    pub text_color: Option<Color>,
+    pub link_color: Option<Color>,
+    pub shadow_color: Option<Color>,
+    pub highlight_color: Option<Color>,
+    pub track_color: Option<Color>,
+    pub hover_shade: Option<f32>,
+    pub pressed_shade: Option<f32>,
+    pub bevel_size_px: Option<f32>,
+    /// Replaces the font size of every text role.
+    pub font_size_px: Option<f32>,
+    pub padding_px: Option<Vec2>,
+}
+
+impl StyleOverride {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply_to(&self, theme: &mut Theme) {
+        theme.control_color = self.control_color.unwrap_or(theme.control_color);
+        theme.text_color = self.text_color.unwrap_or(theme.text_color);
+        theme.link_color = self.link_color.unwrap_or(theme.link_color);
+        theme.shadow_color = self.shadow_color.unwrap_or(theme.shadow_color);
+        theme.highlight_color = self.highlight_color.unwrap_or(theme.highlight_color);
+        theme.track_color = self.track_color.unwrap_or(theme.track_color);
+        theme.hover_shade = self.hover_shade.unwrap_or(theme.hover_shade);
+        theme.pressed_shade = self.pressed_shade.unwrap_or(theme.pressed_shade);
+        theme.bevel_size_px = self.bevel_size_px.unwrap_or(theme.bevel_size_px);
+        theme.padding_px = self.padding_px.unwrap_or(theme.padding_px);
+        if let Some(font_size_px) = self.font_size_px {
+            theme.font_size_px = font_size_px;
+            theme.title_font.size_px = None;
+            theme.body_font.size_px = None;
+            theme.button_font.size_px = None;
+        }
+    }
+}
+
+/// Named style classes that widgets reference through `style_class`. Kept apart from
+/// `Theme` so the theme stays `Copy`; `Gui::style_classes` holds the ones `draw_gui`
+/// uses.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleClasses {
+    pub classes: HashMap<String, StyleOverride>,
+}
+
+impl StyleClasses {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: &str, style: StyleOverride) {
+        self.classes.insert(name.to_string(), style);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&StyleOverride> {
+        self.classes.get(name)
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Theme {
     pub control_color: Color,
     pub text_color: Color,
+    /// Rich text link color, unless the link sets its own color.
+    pub link_color: Color,
     pub shadow_color: Color,
     pub highlight_color: Color,
     pub track_color: Color,
+    /// Fill of disabled controls.
+    pub disabled_color: Color,
+    pub disabled_text_color: Color,
+    /// Multiplier for disabled track colors and image tints.
+    pub disabled_shade: f32,
+    pub tooltip_color: Color,
+    pub tooltip_text_color: Color,
+    /// Fill of the highlighted row in popups and lists.
+    pub selection_color: Color,
+    pub selection_text_color: Color,
+    /// Fill of progress bars without their own `fill_color`.
+    pub progress_color: Color,
+    /// Damage trail of progress bars without their own `trail_color`.
+    pub progress_trail_color: Color,
+    /// Unselected `TabContainer` tabs.
+    pub tab_color: Color,
+    pub tab_selected_color: Color,
+    /// Drawn over everything beneath an open modal.
+    pub modal_dim_color: Color,
     pub hover_shade: f32,
     pub pressed_shade: f32,
     pub bevel_size_px: f32,
     pub font_size_px: f32,
+    pub title_font: FontStyle,
+    pub body_font: FontStyle,
+    pub button_font: FontStyle,
+    /// Fonts substituted for rich text `[b]`/`[i]` runs. `None` keeps the regular font.
+    pub bold_font: Option<FontId>,
+    pub italic_font: Option<FontId>,
+    pub bold_italic_font: Option<FontId>,
+    /// Space between a widget's edge and its text.
+    pub padding_px: Vec2,
+}
+
+impl Theme {
+    pub fn font_style(&self, role: TextRole) -> FontStyle {
+        match role {
+            TextRole::Title => self.title_font,
+            TextRole::Body => self.body_font,
+            TextRole::Button => self.button_font,
+        }
+    }
+
+    /// Theme for one widget: the widget's own `style` wins over its `style_class` in
+    /// `classes`, which wins over this theme.
+    pub fn resolve_style(
+        &self,
+        classes: &StyleClasses,
+        class: Option<&str>,
+        style: &StyleOverride,
+    ) -> Theme {
+        let mut resolved = *self;
+        if let Some(class_style) = class.and_then(|class| classes.get(class)) {
+            class_style.apply_to(&mut resolved);
+        }
+        style.apply_to(&mut resolved);
+        resolved
+    }
+
+    /// Theme for drawing a disabled widget: disabled colors replace control and text
+    /// colors and hovering no longer shades. Unchanged when `disabled` is false.
+    pub fn resolve_disabled(&self, disabled: bool) -> Theme {
+        let mut resolved = *self;
+        if !disabled {
+            return resolved;
+        }
+        resolved.control_color = self.disabled_color;
+        resolved.text_color = self.disabled_text_color;
+        resolved.link_color = self.disabled_text_color;
+        resolved.track_color = self.track_color.scaled(self.disabled_shade);
+        resolved.progress_color = self.progress_color.scaled(self.disabled_shade);
+        resolved.progress_trail_color = self.progress_trail_color.scaled(self.disabled_shade);
+        resolved.tab_color = self.tab_color.scaled(self.disabled_shade);
+        resolved.tab_selected_color = self.tab_selected_color.scaled(self.disabled_shade);
+        resolved.hover_shade = 1.0;
+        resolved
+    }
+
+    /// Bold/italic variant of `font` for rich text, falling back to `font` itself.
+    pub fn styled_font(&self, font: FontId, bold: bool, italic: bool) -> FontId {
+        let variant = match (bold, italic) {
+            (true, true) => self.bold_italic_font.or(self.bold_font),
+            (true, false) => self.bold_font,
+            (false, true) => self.italic_font,
+            (false, false) => None,
+        };
+        variant.unwrap_or(font)
+    }
+
+    /// Font and size for text in `role`, with per-widget overrides taking precedence.
+    pub fn resolve_font(
+        &self,
+        role: TextRole,
+        font: Option<FontId>,
+        font_size_px: Option<f32>,
+    ) -> (FontId, f32) {
+        let style = self.font_style(role);
+        (
+            font.unwrap_or(style.font),
+            font_size_px.or(style.size_px).unwrap_or(self.font_size_px),
+        )
+    }
 }
 
 impl Default for Theme {
@@ -23,256 +215,1556 @@ impl Default for Theme {
         Self {
             control_color: Color::rgb(200, 200, 200),
             text_color: Color::rgb(0, 0, 0),
+            link_color: Color::rgb(30, 60, 200),
             shadow_color: Color::rgb(0, 0, 0),
             highlight_color: Color::rgb(255, 255, 255),
             track_color: Color::rgb(100, 100, 100),
+            disabled_color: Color::rgb(160, 160, 160),
+            disabled_text_color: Color::rgb(110, 110, 110),
+            disabled_shade: 0.6,
+            tooltip_color: Color::rgb(250, 245, 215),
+            tooltip_text_color: Color::rgb(0, 0, 0),
+            selection_color: Color::rgb(60, 100, 190),
+            selection_text_color: Color::rgb(255, 255, 255),
+            progress_color: Color::rgb(70, 170, 80),
+            progress_trail_color: Color::rgb(230, 120, 100),
+            tab_color: Color::rgb(165, 165, 170),
+            tab_selected_color: Color::rgb(215, 215, 220),
+            modal_dim_color: Color::rgba(0, 0, 0, 128),
             hover_shade: 0.65,
             pressed_shade: 0.65,
             bevel_size_px: 1.0,
             font_size_px: 20.0,
+            title_font: FontStyle::default(),
+            body_font: FontStyle::default(),
+            button_font: FontStyle::default(),
+            bold_font: None,
+            italic_font: None,
+            bold_italic_font: None,
+            padding_px: Vec2::new(6.0, 4.0),
+        }
+    }
+}
+
+/// Gui-owned state the draw functions need besides the widget itself.
+struct DrawContext<'a> {
+    icons: &'a HashMap<String, ImageStyle>,
+    classes: &'a StyleClasses,
+    /// `None` when hover fading is off and hover highlights switch instantly.
+    hover_fades: Option<&'a HashMap<u32, f32>>,
+}
+
+impl DrawContext<'_> {
+    /// Hover highlight strength `0..=1` for a widget part.
+    fn hover(&self, id: u32, hovered: bool) -> f32 {
+        match self.hover_fades {
+            Some(fades) => fades.get(&id).copied().unwrap_or(0.0),
+            None if hovered => 1.0,
+            None => 0.0,
         }
     }
 }
 
 pub fn draw_gui<T: Clone + Copy, B: DrawBackend>(gui: &Gui<T>, backend: &mut B, theme: &Theme) {
+    let ctx = DrawContext {
+        icons: &gui.icons,
+        classes: &gui.style_classes,
+        hover_fades: (gui.hover_fade_seconds > 0.0).then_some(&gui.hover_fades),
+    };
+    draw_layer(gui, backend, theme, &ctx, 0);
+    // each modal dims everything beneath it
+    for layer in 1..=gui.modal_layers.len() {
+        backend.fill_rect(modal_dim_rect(gui), theme.modal_dim_color);
+        draw_layer(gui, backend, theme, &ctx, layer);
+    }
+
+    // overlay pass, above every widget
+    for dropdown in &gui.dropdowns {
+        if !dropdown.open || !gui.is_visible(dropdown.id) {
+            continue;
+        }
+        with_visual(gui, backend, dropdown.id, |backend| {
+            draw_dropdown_popup(backend, dropdown, theme, &ctx)
+        });
+    }
+    for context_menu in &gui.context_menus {
+        if !context_menu.open || !gui.is_visible(context_menu.id) {
+            continue;
+        }
+        with_visual(gui, backend, context_menu.id, |backend| {
+            draw_context_menu(backend, context_menu, theme, &ctx)
+        });
+    }
+    for menu_bar in &gui.menu_bars {
+        if !menu_bar.is_open() || !gui.is_visible(menu_bar.id) {
+            continue;
+        }
+        with_visual(gui, backend, menu_bar.id, |backend| {
+            let theme = &theme.resolve_style(
+                ctx.classes,
+                menu_bar.style_class.as_deref(),
+                &menu_bar.style,
+            );
+            let (font, font_size) =
+                theme.resolve_font(TextRole::Body, menu_bar.font, menu_bar.font_size_px);
+            if let Some(menu) = menu_bar.open_menu() {
+                draw_menu_popup(backend, menu, &menu_bar.popup, theme, font, font_size);
+            }
+        });
+    }
+    draw_tooltip(gui, backend, theme, &ctx);
+}
+
+/// Draw the elements of one modal layer, `0` being everything outside modals.
+fn draw_layer<T: Clone + Copy, B: DrawBackend>(
+    gui: &Gui<T>,
+    backend: &mut B,
+    theme: &Theme,
+    ctx: &DrawContext,
+    layer: usize,
+) {
     for label in &gui.labels {
-        if !gui.is_visible(label.id) {
+        if !gui.is_visible(label.id) || gui.modal_layer(label.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, label.id, |backend| {
+            draw_label(backend, label, theme, ctx, !gui.is_enabled(label.id))
+        });
+    }
+    for container in &gui.tab_containers {
+        if !gui.is_visible(container.id) || gui.modal_layer(container.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, container.id, |backend| {
+            draw_tab_container(
+                backend,
+                container,
+                theme,
+                ctx,
+                !gui.is_enabled(container.id),
+                gui.is_focused(container.id),
+            )
+        });
+    }
+    for menu_bar in &gui.menu_bars {
+        if !gui.is_visible(menu_bar.id) || gui.modal_layer(menu_bar.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, menu_bar.id, |backend| {
+            draw_menu_bar(
+                backend,
+                menu_bar,
+                theme,
+                ctx,
+                !gui.is_enabled(menu_bar.id),
+                gui.is_focused(menu_bar.id),
+            )
+        });
+    }
+    for bar in &gui.progress_bars {
+        if !gui.is_visible(bar.id) || gui.modal_layer(bar.id) != layer {
             continue;
         }
-        draw_label(backend, label, theme);
+        with_visual(gui, backend, bar.id, |backend| {
+            draw_progress_bar(backend, bar, theme, ctx, !gui.is_enabled(bar.id))
+        });
     }
     for button in &gui.buttons {
-        if !gui.is_visible(button.id) {
+        if !gui.is_visible(button.id) || gui.modal_layer(button.id) != layer {
             continue;
         }
-        draw_button(backend, button, theme);
+        with_visual(gui, backend, button.id, |backend| {
+            draw_button(
+                backend,
+                button,
+                theme,
+                ctx,
+                !gui.is_enabled(button.id),
+                gui.is_focused(button.id),
+            )
+        });
     }
     for slider in &gui.sliders {
-        if !gui.is_visible(slider.id) {
+        if !gui.is_visible(slider.id) || gui.modal_layer(slider.id) != layer {
             continue;
         }
-        draw_slider(backend, slider, theme);
+        with_visual(gui, backend, slider.id, |backend| {
+            draw_slider(
+                backend,
+                slider,
+                theme,
+                ctx,
+                !gui.is_enabled(slider.id),
+                gui.is_focused(slider.id),
+            )
+        });
     }
     for slider in &gui.vertical_sliders {
-        if !gui.is_visible(slider.id) {
+        if !gui.is_visible(slider.id) || gui.modal_layer(slider.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, slider.id, |backend| {
+            draw_vertical_slider(
+                backend,
+                slider,
+                theme,
+                ctx,
+                !gui.is_enabled(slider.id),
+                gui.is_focused(slider.id),
+            )
+        });
+    }
+    for range in &gui.range_sliders {
+        if !gui.is_visible(range.id) || gui.modal_layer(range.id) != layer {
             continue;
         }
-        draw_vertical_slider(backend, slider, theme);
+        with_visual(gui, backend, range.id, |backend| {
+            draw_range_slider(
+                backend,
+                range,
+                theme,
+                ctx,
+                !gui.is_enabled(range.id),
+                gui.is_focused(range.id),
+            )
+        });
+    }
+    for pad in &gui.xy_pads {
+        if !gui.is_visible(pad.id) || gui.modal_layer(pad.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, pad.id, |backend| {
+            draw_xy_pad(
+                backend,
+                pad,
+                theme,
+                ctx,
+                !gui.is_enabled(pad.id),
+                gui.is_focused(pad.id),
+            )
+        });
     }
     for draggable in &gui.draggables {
-        if !gui.is_visible(draggable.id) {
+        if !gui.is_visible(draggable.id) || gui.modal_layer(draggable.id) != layer {
             continue;
         }
-        draw_draggable(backend, draggable, theme);
+        with_visual(gui, backend, draggable.id, |backend| {
+            draw_draggable(
+                backend,
+                draggable,
+                theme,
+                ctx,
+                !gui.is_enabled(draggable.id),
+                gui.is_focused(draggable.id),
+            )
+        });
     }
     for selector in &gui.left_right_selectors {
-        if !gui.is_visible(selector.id) {
+        if !gui.is_visible(selector.id) || gui.modal_layer(selector.id) != layer {
             continue;
         }
-        draw_left_right_selector(backend, selector, theme);
+        with_visual(gui, backend, selector.id, |backend| {
+            draw_left_right_selector(
+                backend,
+                selector,
+                theme,
+                ctx,
+                !gui.is_enabled(selector.id),
+                gui.is_focused(selector.id),
+            )
+        });
     }
     for toggle in &gui.button_toggles {
-        if !gui.is_visible(toggle.id) {
+        if !gui.is_visible(toggle.id) || gui.modal_layer(toggle.id) != layer {
             continue;
         }
-        draw_button_toggle(backend, toggle, theme);
+        with_visual(gui, backend, toggle.id, |backend| {
+            draw_button_toggle(
+                backend,
+                toggle,
+                theme,
+                ctx,
+                !gui.is_enabled(toggle.id),
+                gui.is_focused(toggle.id),
+            )
+        });
     }
     for thumbs in &gui.move_and_resize_thumbs {
-        if !gui.is_visible(thumbs.id) {
+        if !gui.is_visible(thumbs.id) || gui.modal_layer(thumbs.id) != layer {
             continue;
         }
-        draw_move_and_resize_thumbs(backend, thumbs, theme);
+        with_visual(gui, backend, thumbs.id, |backend| {
+            draw_move_and_resize_thumbs(
+                backend,
+                thumbs,
+                theme,
+                ctx,
+                !gui.is_enabled(thumbs.id),
+                gui.is_focused(thumbs.id),
+            )
+        });
     }
-}
-
-fn draw_label<B: DrawBackend>(backend: &mut B, label: &Label, theme: &Theme) {
-    let rect = Rect::new(label.position, label.size);
-    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
-        return;
+    for list in &gui.list_views {
+        if !gui.is_visible(list.id) || gui.modal_layer(list.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, list.id, |backend| {
+            draw_list_view(
+                backend,
+                list,
+                theme,
+                ctx,
+                !gui.is_enabled(list.id),
+                gui.is_focused(list.id),
+            )
+        });
     }
-
-    match label.background_image {
-        Some(image) if !image.draw_over_content => backend.draw_image(image, rect),
-        _ => backend.fill_rect(rect, theme.control_color),
+    for tree in &gui.tree_views {
+        if !gui.is_visible(tree.id) || gui.modal_layer(tree.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, tree.id, |backend| {
+            draw_tree_view(
+                backend,
+                tree,
+                theme,
+                ctx,
+                !gui.is_enabled(tree.id),
+                gui.is_focused(tree.id),
+            )
+        });
     }
 
-    if let Some(text) = &label.text {
-        let text_pos = rect.position + Vec2::new(6.0, 4.0);
-        backend.draw_text(text, text_pos, theme.font_size_px, theme.text_color);
+    for checkbox in &gui.checkboxes {
+        if !gui.is_visible(checkbox.id) || gui.modal_layer(checkbox.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, checkbox.id, |backend| {
+            draw_checkbox(backend, checkbox, theme, ctx, !gui.is_enabled(checkbox.id))
+        });
     }
-
-    if let Some(image) = label.background_image {
-        if image.draw_over_content {
-            backend.draw_image(image, rect);
+    for group in &gui.radio_groups {
+        if !gui.is_visible(group.id) || gui.modal_layer(group.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, group.id, |backend| {
+            draw_radio_group(backend, group, theme, ctx, !gui.is_enabled(group.id))
+        });
+    }
+    for dropdown in &gui.dropdowns {
+        if !gui.is_visible(dropdown.id) || gui.modal_layer(dropdown.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, dropdown.id, |backend| {
+            draw_dropdown(backend, dropdown, theme, ctx, !gui.is_enabled(dropdown.id))
+        });
+    }
+    for picker in &gui.color_pickers {
+        if !gui.is_visible(picker.id) || gui.modal_layer(picker.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, picker.id, |backend| {
+            draw_color_picker(
+                backend,
+                picker,
+                theme,
+                ctx,
+                !gui.is_enabled(picker.id),
+                gui.is_focused(picker.id),
+            )
+        });
+    }
+    for field in &gui.number_fields {
+        if !gui.is_visible(field.id) || gui.modal_layer(field.id) != layer {
+            continue;
+        }
+        with_visual(gui, backend, field.id, |backend| {
+            draw_number_field(
+                backend,
+                field,
+                theme,
+                ctx,
+                !gui.is_enabled(field.id),
+                gui.is_focused(field.id),
+            )
+        });
+    }
+    for message_box in &gui.message_boxes {
+        if !gui.is_visible(message_box.id) || gui.modal_layer(message_box.id) != layer {
+            continue;
         }
+        with_visual(gui, backend, message_box.id, |backend| {
+            draw_message_box(
+                backend,
+                message_box,
+                theme,
+                ctx,
+                !gui.is_enabled(message_box.id),
+                gui.is_focused(message_box.id),
+            )
+        });
     }
 }
 
-fn draw_button<B: DrawBackend>(backend: &mut B, button: &Button, theme: &Theme) {
-    draw_button_visual(
-        backend,
-        theme,
-        button.position,
-        button.size,
-        button.label.as_deref(),
-        button.hovered,
-        button.pressed,
-        button.background_image,
-    );
+/// Area a modal dims: `Gui::bounds`, or without bounds a rect far larger than any
+/// render target, which the backend clips.
+fn modal_dim_rect<T: Clone + Copy>(gui: &Gui<T>) -> Rect {
+    gui.bounds
+        .unwrap_or(Rect::new(Vec2::splat(-100_000.0), Vec2::splat(200_000.0)))
 }
 
-fn draw_button_visual<B: DrawBackend>(
+/// Run `draw` with the element's tint/opacity/scale applied, if it has any.
+fn with_visual<T: Clone + Copy, B: DrawBackend>(
+    gui: &Gui<T>,
     backend: &mut B,
-    theme: &Theme,
-    position: Vec2,
-    size: Vec2,
-    label: Option<&str>,
-    hovered: bool,
-    pressed: bool,
-    background_image: Option<ImageStyle>,
+    id: u32,
+    draw: impl FnOnce(&mut dyn DrawBackend),
 ) {
-    let rect = Rect::new(position, size);
-    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+    let visual = gui.visual(id);
+    if visual.is_identity() {
+        draw(backend);
         return;
     }
-    draw_beveled_box(backend, rect, theme, hovered, pressed, theme.control_color);
+    let origin = gui
+        .element_rect(id)
+        .map(|rect| rect.position + rect.size * 0.5)
+        .unwrap_or(Vec2::ZERO);
+    draw(&mut VisualBackend {
+        inner: backend,
+        visual,
+        origin,
+    });
+}
 
-    if let Some(image) = background_image {
-        if !image.draw_over_content {
-            backend.draw_image(image, rect);
+/// Draw the tooltip of `Gui::active_tooltip`, placed next to its element.
+fn draw_tooltip<T: Clone + Copy, B: DrawBackend + ?Sized>(
+    gui: &Gui<T>,
+    backend: &mut B,
+    theme: &Theme,
+    ctx: &DrawContext,
+) {
+    let Some(id) = gui.active_tooltip() else {
+        return;
+    };
+    let (Some(tooltip), Some(anchor)) = (gui.tooltips.get(&id), gui.element_rect(id)) else {
+        return;
+    };
+
+    let padding = theme.padding_px;
+    let (font, font_size) = theme.resolve_font(TextRole::Body, None, None);
+    let max_content_width = (tooltip.max_width - padding.x * 2.0).max(0.0);
+    let mut text_layout = TextLayout::default();
+    let mut rich_layout = RichLayout::default();
+    let content_size = match &tooltip.content {
+        TooltipContent::Text(text) => {
+            text_layout = wrap_text(backend, text, Some(max_content_width), font, font_size, 0.0);
+            let width = text_layout
+                .lines
+                .iter()
+                .map(|line| text_width(backend, line, font, font_size))
+                .fold(0.0, f32::max);
+            Vec2::new(width, text_layout.height)
         }
-    }
+        TooltipContent::Rich(rich_text) => {
+            let mut rich_theme = *theme;
+            rich_theme.text_color = theme.tooltip_text_color;
+            let rect = Rect::new(Vec2::ZERO, Vec2::new(max_content_width, 0.0));
+            rich_layout = layout_rich_text(
+                backend,
+                rich_text,
+                rect,
+                font,
+                font_size,
+                &rich_theme,
+                ctx.icons,
+            );
+            let width = rich_layout
+                .items
+                .iter()
+                .map(|item| item.rect.position.x + item.rect.size.x)
+                .fold(0.0, f32::max);
+            Vec2::new(width, rich_layout.height)
+        }
+        TooltipContent::Image { size, .. } => *size,
+    };
 
-    if let Some(label) = label {
-        let text_pos = rect.position + Vec2::new(6.0, 4.0);
-        let text_offset = if pressed {
-            Vec2::new(0.0, theme.bevel_size_px)
-        } else {
-            Vec2::ZERO
-        };
-        backend.draw_text(
-            label,
-            text_pos + text_offset,
-            theme.font_size_px,
-            theme.text_color,
-        );
-    }
+    let rect = place_tooltip(
+        anchor,
+        content_size + padding * 2.0,
+        tooltip.placement,
+        padding.y,
+        gui.bounds,
+    );
+    backend.fill_rect(rect, theme.shadow_color);
+    let bevel = Vec2::splat(theme.bevel_size_px);
+    backend.fill_rect(
+        Rect::new(rect.position + bevel, rect.size - bevel * 2.0),
+        theme.tooltip_color,
+    );
 
-    if let Some(image) = background_image {
-        if image.draw_over_content {
-            backend.draw_image(image, rect);
+    let content_position = rect.position + padding;
+    match &tooltip.content {
+        TooltipContent::Text(_) => {
+            let mut text_pos = content_position;
+            for line in &text_layout.lines {
+                backend.draw_text_with_font(
+                    line,
+                    text_pos,
+                    font,
+                    font_size,
+                    theme.tooltip_text_color,
+                );
+                text_pos.y += text_layout.line_height;
+            }
+        }
+        TooltipContent::Rich(_) => {
+            for item in &mut rich_layout.items {
+                item.rect.position += content_position;
+            }
+            draw_rich_text(backend, &rich_layout);
+        }
+        TooltipContent::Image { image, size } => {
+            draw_styled_image(backend, *image, Rect::new(content_position, *size));
         }
     }
 }
 
-fn draw_slider<B: DrawBackend>(backend: &mut B, slider: &Slider, theme: &Theme) {
-    let body = Rect::new(slider.position, slider.size);
-    if body.size.x <= 0.0 || body.size.y <= 0.0 {
-        return;
-    }
+/// Text of a label broken into drawable lines.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextLayout {
+    pub lines: Vec<String>,
+    pub line_height: f32,
+    /// Total height of all lines including line spacing, without label padding.
+    pub height: f32,
+}
 
-    match slider.track_image {
-        Some(image) if !image.draw_over_content => backend.draw_image(image, body),
-        _ => backend.fill_rect(body, theme.track_color),
+/// Break `text` into lines at explicit newlines and, when `max_width` is given, at word
+/// boundaries so that no line is wider than `max_width`. Words wider than `max_width`
+/// are split between characters.
+pub fn wrap_text<B: DrawBackend + ?Sized>(
+    backend: &B,
+    text: &str,
+    max_width: Option<f32>,
+    font: FontId,
+    font_size: f32,
+    line_spacing_px: f32,
+) -> TextLayout {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        match max_width {
+            Some(max_width) => {
+                wrap_paragraph(backend, paragraph, max_width, font, font_size, &mut lines)
+            }
+            None => lines.push(paragraph.to_string()),
+        }
     }
 
-    let range = (slider.maximum - slider.minimum).abs();
-    let value_fraction = if range <= f32::EPSILON {
+    let line_height = font_size;
+    let height = if lines.is_empty() {
         0.0
     } else {
-        ((slider.value - slider.minimum) / (slider.maximum - slider.minimum)).clamp(0.0, 1.0)
+        lines.len() as f32 * line_height + (lines.len() - 1) as f32 * line_spacing_px
     };
+    TextLayout {
+        lines,
+        line_height,
+        height,
+    }
+}
 
-    let thumb_x = body.position.x + value_fraction * body.size.x;
-    let thumb_width = slider.thumb_width.max(1.0);
-    let thumb_rect = Rect::new(
-        Vec2::new(thumb_x - thumb_width / 2.0, body.position.y),
-        Vec2::new(thumb_width, body.size.y),
-    );
+/// Lines and total height of a label's text as `draw_gui` renders it.
+/// Layout code can use `height` to size panels to fit.
+pub fn layout_label_text<B: DrawBackend + ?Sized>(
+    backend: &B,
+    label: &Label,
+    theme: &Theme,
+    classes: &StyleClasses,
+) -> TextLayout {
+    let theme = &theme.resolve_style(classes, label.style_class.as_deref(), &label.style);
+    label_text_layout(backend, label, theme)
+}
 
-    draw_beveled_box(
+fn label_text_layout<B: DrawBackend + ?Sized>(
+    backend: &B,
+    label: &Label,
+    theme: &Theme,
+) -> TextLayout {
+    let Some(text) = &label.text else {
+        return TextLayout::default();
+    };
+    let max_width = if label.wrap {
+        Some((label.size.x - theme.padding_px.x * 2.0).max(0.0))
+    } else {
+        None
+    };
+    let (font, font_size) = theme.resolve_font(label.text_role, label.font, label.font_size_px);
+    wrap_text(
         backend,
-        thumb_rect,
-        theme,
-        slider.hovered,
-        false,
-        theme.control_color,
-    );
+        text,
+        max_width,
+        font,
+        font_size,
+        label.line_spacing_px,
+    )
+}
 
-    if let Some(image) = slider.thumb_image {
-        backend.draw_image(image, thumb_rect);
-    }
+pub(crate) fn text_width<B: DrawBackend + ?Sized>(
+    backend: &B,
+    text: &str,
+    font: FontId,
+    font_size: f32,
+) -> f32 {
+    backend
+        .measure_text_with_font(text, font, font_size)
+        .unwrap_or_else(|| estimate_text_width(text, font_size))
+}
 
-    if let Some(image) = slider.track_image {
-        if image.draw_over_content {
-            backend.draw_image(image, body);
+fn wrap_paragraph<B: DrawBackend + ?Sized>(
+    backend: &B,
+    paragraph: &str,
+    max_width: f32,
+    font: FontId,
+    font_size: f32,
+    lines: &mut Vec<String>,
+) {
+    let mut line = String::new();
+    // runs of spaces collapse to one, so no line starts with a space
+    for word in paragraph.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{line} {word}")
+        };
+        if text_width(backend, &candidate, font, font_size) <= max_width {
+            line = candidate;
+            continue;
+        }
+
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        // word alone is too wide, break it between characters
+        for ch in word.chars() {
+            line.push(ch);
+            if line.chars().count() > 1 && text_width(backend, &line, font, font_size) > max_width {
+                line.pop();
+                lines.push(std::mem::take(&mut line));
+                line.push(ch);
+            }
         }
     }
+    lines.push(line);
 }
 
-fn draw_vertical_slider<B: DrawBackend>(backend: &mut B, slider: &VerticalSlider, theme: &Theme) {
-    let body = Rect::new(slider.position, slider.size);
-    if body.size.x <= 0.0 || body.size.y <= 0.0 {
+/// Rich text of a label laid out as `draw_gui` renders it, or `None` for plain labels.
+pub fn layout_label_rich_text<B: DrawBackend + ?Sized>(
+    backend: &B,
+    label: &Label,
+    theme: &Theme,
+    classes: &StyleClasses,
+    icons: &HashMap<String, ImageStyle>,
+) -> Option<RichLayout> {
+    let theme = &theme.resolve_style(classes, label.style_class.as_deref(), &label.style);
+    label_rich_text_layout(backend, label, theme, icons)
+}
+
+fn label_rich_text_layout<B: DrawBackend + ?Sized>(
+    backend: &B,
+    label: &Label,
+    theme: &Theme,
+    icons: &HashMap<String, ImageStyle>,
+) -> Option<RichLayout> {
+    let rich_text = label.rich_text.as_ref()?;
+    let (font, font_size) = theme.resolve_font(label.text_role, label.font, label.font_size_px);
+    let text_rect = Rect::new(
+        label.position + theme.padding_px,
+        Vec2::new(
+            (label.size.x - theme.padding_px.x * 2.0).max(0.0),
+            label.size.y,
+        ),
+    );
+    Some(layout_rich_text(
+        backend, rich_text, text_rect, font, font_size, theme, icons,
+    ))
+}
+
+fn draw_label<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    label: &Label,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, label.style_class.as_deref(), &label.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let mut rect = Rect::new(label.position, label.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
         return;
     }
 
-    match slider.track_image {
-        Some(image) if !image.draw_over_content => backend.draw_image(image, body),
-        _ => backend.fill_rect(body, theme.track_color),
+    let layout = label_text_layout(backend, label, theme);
+    let rich_layout = label_rich_text_layout(backend, label, theme, ctx.icons);
+    if label.auto_height {
+        let text_height = match &rich_layout {
+            Some(rich_layout) => rich_layout.height,
+            None => layout.height,
+        };
+        rect.size.y = rect.size.y.max(text_height + theme.padding_px.y * 2.0);
     }
 
-    let range = (slider.maximum - slider.minimum).abs();
-    let value_fraction = if range <= f32::EPSILON {
-        0.0
+    match label.background_image {
+        Some(image) if !image.draw_over_content => {
+            draw_styled_image(backend, widget_image(image, theme, disabled), rect)
+        }
+        _ => backend.fill_rect(rect, theme.control_color),
+    }
+
+    if let Some(rich_layout) = rich_layout {
+        draw_rich_text(backend, &rich_layout);
     } else {
-        ((slider.value - slider.minimum) / (slider.maximum - slider.minimum)).clamp(0.0, 1.0)
-    };
+        let (font, font_size) = theme.resolve_font(label.text_role, label.font, label.font_size_px);
+        let mut text_pos = rect.position + theme.padding_px;
+        for line in &layout.lines {
+            backend.draw_text_with_font(line, text_pos, font, font_size, theme.text_color);
+            text_pos.y += layout.line_height + label.line_spacing_px;
+        }
+    }
 
-    let thumb_y = body.position.y + value_fraction * body.size.y;
-    let thumb_height = slider.thumb_height.max(1.0);
-    let thumb_rect = Rect::new(
-        Vec2::new(body.position.x, thumb_y - thumb_height / 2.0),
-        Vec2::new(body.size.x, thumb_height),
-    );
+    if let Some(image) = label.background_image {
+        if image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), rect);
+        }
+    }
+}
 
-    draw_beveled_box(
+fn draw_tab_container(
+    backend: &mut dyn DrawBackend,
+    container: &TabContainer,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        container.style_class.as_deref(),
+        &container.style,
+    );
+    let theme = &theme.resolve_disabled(disabled);
+    if container.size.x <= 0.0 || container.size.y <= 0.0 {
+        return;
+    }
+    let thickness = theme.bevel_size_px.max(1.0);
+    draw_outline(
         backend,
-        thumb_rect,
-        theme,
-        slider.hovered,
-        false,
-        theme.control_color,
+        container.page_rect(),
+        theme.shadow_color,
+        thickness,
     );
 
-    if let Some(image) = slider.thumb_image {
-        backend.draw_image(image, thumb_rect);
+    let (font, font_size) =
+        theme.resolve_font(TextRole::Body, container.font, container.font_size_px);
+    backend.push_clip_rect(container.strip_rect());
+    for (index, (tab, button)) in container
+        .tabs
+        .iter()
+        .zip(&container.tab_buttons)
+        .enumerate()
+    {
+        let selected = index == container.selected_index;
+        let mut rect = Rect::new(button.position, button.size);
+        // the selected tab stands taller and covers the page frame below it
+        if selected {
+            rect.size.y += thickness;
+        } else {
+            rect.position.y += thickness * 2.0;
+            rect.size.y -= thickness * 2.0;
+        }
+        let (image, color) = if selected {
+            (container.selected_tab_image, theme.tab_selected_color)
+        } else {
+            (container.tab_image, theme.tab_color)
+        };
+        match image {
+            Some(image) => draw_styled_image(backend, widget_image(image, theme, disabled), rect),
+            None => draw_beveled_box(
+                backend,
+                rect,
+                theme,
+                ctx.hover(button.id, button.hovered && !selected),
+                false,
+                color,
+            ),
+        }
+
+        let mut content_x = rect.position.x + theme.padding_px.x;
+        let middle = rect.position.y + rect.size.y * 0.5;
+        if let Some(icon) = tab.icon {
+            let icon_size = (rect.size.y - theme.padding_px.y * 2.0).max(0.0);
+            let icon_rect = Rect::new(
+                Vec2::new(content_x, middle - icon_size * 0.5),
+                Vec2::splat(icon_size),
+            );
+            draw_styled_image(backend, widget_image(icon, theme, disabled), icon_rect);
+            content_x += icon_size + theme.padding_px.x * 0.5;
+        }
+        if let Some(label) = &tab.label {
+            backend.draw_text_with_font(
+                label,
+                Vec2::new(content_x, middle - font_size * 0.5),
+                font,
+                font_size,
+                theme.text_color,
+            );
+        }
+        if selected && focused {
+            draw_outline(backend, rect, theme.text_color, thickness);
+        }
     }
+    backend.pop_clip_rect();
 
-    if let Some(image) = slider.track_image {
-        if image.draw_over_content {
-            backend.draw_image(image, body);
+    if container.overflows() {
+        for button in [
+            &container.scroll_left_button,
+            &container.scroll_right_button,
+        ] {
+            let state = WidgetState {
+                hovered: button.hovered,
+                pressed: button.pressed,
+                focused: false,
+                disabled,
+            };
+            draw_button_visual(backend, theme, ctx, button, state);
         }
     }
 }
 
-fn draw_draggable<B: DrawBackend>(backend: &mut B, draggable: &Draggable, theme: &Theme) {
-    let rect = Rect::new(draggable.position, draggable.size);
+fn draw_message_box(
+    backend: &mut dyn DrawBackend,
+    message_box: &MessageBox,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        message_box.style_class.as_deref(),
+        &message_box.style,
+    );
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(message_box.position, message_box.size);
     if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
         return;
     }
+    draw_beveled_box(backend, rect, theme, 0.0, false, theme.control_color);
 
-    draw_beveled_box(
+    let (title_font, title_size) = theme.resolve_font(TextRole::Title, None, None);
+    let title_position = rect.position + Vec2::splat(message_box.spacing);
+    backend.draw_text_with_font(
+        &message_box.title,
+        title_position,
+        title_font,
+        title_size,
+        theme.text_color,
+    );
+    let text_rect = message_box.text_rect(title_size);
+    let separator_y = text_rect.position.y - message_box.spacing * 0.5;
+    backend.draw_line(
+        Vec2::new(text_rect.position.x, separator_y),
+        Vec2::new(text_rect.position.x + text_rect.size.x, separator_y),
+        theme.shadow_color,
+        theme.bevel_size_px.max(1.0),
+    );
+
+    let (font, font_size) =
+        theme.resolve_font(TextRole::Body, message_box.font, message_box.font_size_px);
+    let layout = wrap_text(
         backend,
-        rect,
-        theme,
-        draggable.hovered,
-        false,
-        theme.control_color,
+        &message_box.text,
+        Some(text_rect.size.x),
+        font,
+        font_size,
+        0.0,
     );
+    backend.push_clip_rect(text_rect);
+    let mut text_position = text_rect.position;
+    for line in &layout.lines {
+        backend.draw_text_with_font(line, text_position, font, font_size, theme.text_color);
+        text_position.y += layout.line_height;
+    }
+    backend.pop_clip_rect();
 
-    if let Some(image) = draggable.background_image {
-        if !image.draw_over_content {
-            backend.draw_image(image, rect);
+    for (index, button) in message_box.buttons.iter().enumerate() {
+        let button_focused = focused && index == message_box.focused_button;
+        let state = WidgetState {
+            hovered: button.hovered,
+            pressed: button.pressed,
+            focused: button_focused,
+            disabled,
+        };
+        draw_button_visual(backend, theme, ctx, button, state);
+        if button_focused {
+            draw_outline(
+                backend,
+                Rect::new(button.position, button.size),
+                theme.text_color,
+                theme.bevel_size_px.max(1.0),
+            );
+        }
+    }
+}
+
+fn draw_menu_bar(
+    backend: &mut dyn DrawBackend,
+    menu_bar: &MenuBar,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        menu_bar.style_class.as_deref(),
+        &menu_bar.style,
+    );
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(menu_bar.position, menu_bar.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+    draw_beveled_box(backend, rect, theme, 0.0, false, theme.control_color);
+
+    let (font, font_size) =
+        theme.resolve_font(TextRole::Body, menu_bar.font, menu_bar.font_size_px);
+    backend.push_clip_rect(rect);
+    for (index, (entry, button)) in menu_bar
+        .entries
+        .iter()
+        .zip(&menu_bar.title_buttons)
+        .enumerate()
+    {
+        let title_rect = Rect::new(button.position, button.size);
+        let text_color = if !entry.enabled {
+            theme.disabled_text_color
+        } else if menu_bar.open_index == Some(index) {
+            backend.fill_rect(title_rect, theme.selection_color);
+            theme.selection_text_color
+        } else {
+            let hover = ctx.hover(button.id, button.hovered);
+            if hover > 0.0 {
+                backend.fill_rect(
+                    title_rect,
+                    theme
+                        .control_color
+                        .scaled(1.0 + (theme.hover_shade - 1.0) * hover),
+                );
+            }
+            theme.text_color
+        };
+        let text_width = text_width(backend, &entry.label, font, font_size);
+        backend.draw_text_with_font(
+            &entry.label,
+            title_rect.position + (title_rect.size - Vec2::new(text_width, font_size)) * 0.5,
+            font,
+            font_size,
+            text_color,
+        );
+    }
+    backend.pop_clip_rect();
+    if focused {
+        draw_outline(
+            backend,
+            rect,
+            theme.text_color,
+            theme.bevel_size_px.max(1.0),
+        );
+    }
+}
+
+fn draw_context_menu(
+    backend: &mut dyn DrawBackend,
+    context_menu: &ContextMenu,
+    theme: &Theme,
+    ctx: &DrawContext,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        context_menu.style_class.as_deref(),
+        &context_menu.style,
+    );
+    let (font, font_size) =
+        theme.resolve_font(TextRole::Body, context_menu.font, context_menu.font_size_px);
+    draw_menu_popup(
+        backend,
+        &context_menu.menu,
+        &context_menu.popup,
+        theme,
+        font,
+        font_size,
+    );
+}
+
+/// Draw the open panels of a menu popup, top level first so submenus overlap their
+/// parent. Items have a check mark column on the left and a submenu arrow on the right.
+fn draw_menu_popup(
+    backend: &mut dyn DrawBackend,
+    menu: &Menu,
+    popup: &MenuPopup,
+    theme: &Theme,
+    font: FontId,
+    font_size: f32,
+) {
+    let bevel = Vec2::splat(theme.bevel_size_px);
+    let menus = popup.open_menus(menu);
+    for (level, (menu, panel)) in menus.iter().zip(popup.panel_rects(menu)).enumerate() {
+        backend.fill_rect(
+            Rect::new(panel.position, panel.size + bevel),
+            theme.shadow_color,
+        );
+        backend.fill_rect(panel, theme.control_color);
+
+        let item_rects = menu.item_rects(panel.position, panel.size.x, popup.item_height);
+        for (index, (item, rect)) in menu.items.iter().zip(item_rects).enumerate() {
+            if item.separator {
+                let y = rect.position.y + rect.size.y * 0.5;
+                backend.draw_line(
+                    Vec2::new(rect.position.x + theme.padding_px.x, y),
+                    Vec2::new(rect.position.x + rect.size.x - theme.padding_px.x, y),
+                    theme.shadow_color,
+                    1.0,
+                );
+                continue;
+            }
+            let highlighted =
+                popup.hovered == Some((level, index)) || popup.open_path.get(level) == Some(&index);
+            let text_color = if !item.enabled {
+                theme.disabled_text_color
+            } else if highlighted {
+                backend.fill_rect(rect, theme.selection_color);
+                theme.selection_text_color
+            } else {
+                theme.text_color
+            };
+
+            let column = rect.size.y;
+            let at = |x: f32, y: f32| rect.position + Vec2::splat(column) * Vec2::new(x, y);
+            if item.checked == Some(true) {
+                let thickness = (column * 0.1).max(1.5);
+                backend.draw_line(at(0.28, 0.52), at(0.44, 0.68), text_color, thickness);
+                backend.draw_line(at(0.44, 0.68), at(0.74, 0.34), text_color, thickness);
+            }
+            backend.draw_text_with_font(
+                &item.label,
+                Vec2::new(
+                    rect.position.x + column,
+                    rect.position.y + (rect.size.y - font_size) * 0.5,
+                ),
+                font,
+                font_size,
+                text_color,
+            );
+            if let Some(shortcut) = &item.shortcut {
+                let width = text_width(backend, shortcut, font, font_size);
+                backend.draw_text_with_font(
+                    shortcut,
+                    Vec2::new(
+                        rect.position.x + rect.size.x - theme.padding_px.x - width,
+                        rect.position.y + (rect.size.y - font_size) * 0.5,
+                    ),
+                    font,
+                    font_size,
+                    text_color,
+                );
+            }
+            if item.submenu.is_some() {
+                let tip = Vec2::new(
+                    rect.position.x + rect.size.x - theme.padding_px.x,
+                    rect.position.y + rect.size.y * 0.5,
+                );
+                let arm = column * 0.18;
+                backend.draw_line(tip - Vec2::new(arm, arm), tip, text_color, 1.5);
+                backend.draw_line(tip - Vec2::new(arm, -arm), tip, text_color, 1.5);
+            }
+        }
+    }
+}
+
+fn draw_progress_bar<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    bar: &ProgressBar,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, bar.style_class.as_deref(), &bar.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(bar.position, bar.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+
+    match bar.background_image {
+        Some(image) => draw_styled_image(backend, widget_image(image, theme, disabled), rect),
+        None => backend.fill_rect(rect, theme.track_color),
+    }
+
+    // images span the whole bar and are clipped to their part, so they don't stretch
+    let mut draw_part = |part: Rect, image: Option<ImageStyle>, color: Color| {
+        if part.size.x <= 0.0 || part.size.y <= 0.0 {
+            return;
+        }
+        match image {
+            Some(image) => {
+                backend.push_clip_rect(part);
+                draw_styled_image(backend, widget_image(image, theme, disabled), rect);
+                backend.pop_clip_rect();
+            }
+            None => backend.fill_rect(part, color),
+        }
+    };
+    let fraction = bar.fraction();
+    let trail_fraction = bar.trail_fraction();
+    if bar.trail && trail_fraction > fraction {
+        let trail_color = match (bar.trail_color, disabled) {
+            (Some(color), false) => color,
+            (Some(color), true) => color.scaled(theme.disabled_shade),
+            (None, _) => theme.progress_trail_color,
+        };
+        draw_part(
+            bar.part_rect(fraction, trail_fraction),
+            bar.trail_image,
+            trail_color,
+        );
+    }
+    let fill_color = match (bar.fill_color, disabled) {
+        (Some(color), false) => color,
+        (Some(color), true) => color.scaled(theme.disabled_shade),
+        (None, _) => theme.progress_color,
+    };
+    draw_part(bar.part_rect(0.0, fraction), bar.fill_image, fill_color);
+
+    if bar.segments > 1 {
+        let thickness = theme.bevel_size_px.max(1.0);
+        for segment in 1..bar.segments {
+            let t = segment as f32 / bar.segments as f32;
+            let (start, end) = match bar.direction {
+                ProgressDirection::LeftToRight | ProgressDirection::RightToLeft => {
+                    let x = rect.position.x + rect.size.x * t;
+                    (
+                        Vec2::new(x, rect.position.y),
+                        Vec2::new(x, rect.position.y + rect.size.y),
+                    )
+                }
+                ProgressDirection::TopToBottom | ProgressDirection::BottomToTop => {
+                    let y = rect.position.y + rect.size.y * t;
+                    (
+                        Vec2::new(rect.position.x, y),
+                        Vec2::new(rect.position.x + rect.size.x, y),
+                    )
+                }
+            };
+            backend.draw_line(start, end, theme.shadow_color, thickness);
+        }
+    }
+
+    if let Some(text) = bar.value_text() {
+        let (font, font_size) = theme.resolve_font(TextRole::Body, bar.font, bar.font_size_px);
+        let width = text_width(backend, &text, font, font_size);
+        let position = rect.position + (rect.size - Vec2::new(width, font_size)) * 0.5;
+        backend.draw_text_with_font(&text, position, font, font_size, theme.text_color);
+    }
+}
+
+fn draw_button<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    button: &Button,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let state = WidgetState {
+        hovered: button.hovered,
+        pressed: button.pressed,
+        focused,
+        disabled,
+    };
+    draw_button_visual(backend, theme, ctx, button, state);
+}
+
+fn draw_button_visual<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    theme: &Theme,
+    ctx: &DrawContext,
+    button: &Button,
+    state: WidgetState,
+) {
+    let (pressed, disabled) = (state.pressed, state.disabled);
+    let theme = &theme.resolve_style(ctx.classes, button.style_class.as_deref(), &button.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(button.position, button.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+    draw_state_box(
+        backend,
+        rect,
+        theme,
+        button.state_images.as_ref(),
+        state,
+        ctx.hover(button.id, button.hovered),
+    );
+
+    if let Some(image) = button.background_image {
+        if !image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), rect);
+        }
+    }
+
+    let text_pos = rect.position + theme.padding_px;
+    let text_offset = if pressed {
+        Vec2::new(0.0, theme.bevel_size_px)
+    } else {
+        Vec2::ZERO
+    };
+    let (font, font_size) = theme.resolve_font(TextRole::Button, button.font, button.font_size_px);
+    if let Some(rich_label) = &button.rich_label {
+        let text_rect = Rect::new(
+            text_pos + text_offset,
+            Vec2::new(
+                (rect.size.x - theme.padding_px.x * 2.0).max(0.0),
+                rect.size.y,
+            ),
+        );
+        let layout = layout_rich_text(
+            backend, rich_label, text_rect, font, font_size, theme, ctx.icons,
+        );
+        draw_rich_text(backend, &layout);
+    } else if let Some(label) = &button.label {
+        backend.draw_text_with_font(
+            label,
+            text_pos + text_offset,
+            font,
+            font_size,
+            theme.text_color,
+        );
+    }
+
+    if let Some(image) = button.background_image {
+        if image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), rect);
+        }
+    }
+}
+
+fn draw_slider<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    slider: &Slider,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, slider.style_class.as_deref(), &slider.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let body = Rect::new(slider.position, slider.size);
+    if body.size.x <= 0.0 || body.size.y <= 0.0 {
+        return;
+    }
+
+    match slider.track_image {
+        Some(image) if !image.draw_over_content => {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body)
+        }
+        _ => backend.fill_rect(body, theme.track_color),
+    }
+
+    let range = (slider.maximum - slider.minimum).abs();
+    let value_fraction = if range <= f32::EPSILON {
+        0.0
+    } else {
+        ((slider.value - slider.minimum) / (slider.maximum - slider.minimum)).clamp(0.0, 1.0)
+    };
+
+    let thumb_x = body.position.x + value_fraction * body.size.x;
+    let thumb_width = slider.thumb_width.max(1.0);
+    let thumb_rect = Rect::new(
+        Vec2::new(thumb_x - thumb_width / 2.0, body.position.y),
+        Vec2::new(thumb_width, body.size.y),
+    );
+
+    draw_state_box(
+        backend,
+        thumb_rect,
+        theme,
+        slider.thumb_state_images.as_ref(),
+        WidgetState {
+            hovered: slider.hovered,
+            pressed: slider.was_pressed,
+            focused,
+            disabled,
+        },
+        ctx.hover(slider.id, slider.hovered),
+    );
+
+    if let Some(image) = slider.thumb_image {
+        draw_styled_image(backend, widget_image(image, theme, disabled), thumb_rect);
+    }
+
+    if let Some(image) = slider.track_image {
+        if image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body);
+        }
+    }
+}
+
+fn draw_range_slider<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    slider: &RangeSlider,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, slider.style_class.as_deref(), &slider.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let body = Rect::new(slider.position, slider.size);
+    if body.size.x <= 0.0 || body.size.y <= 0.0 {
+        return;
+    }
+
+    match slider.track_image {
+        Some(image) if !image.draw_over_content => {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body)
+        }
+        _ => backend.fill_rect(body, theme.track_color),
+    }
+    let range_rect = slider.range_rect();
+    match slider.range_image {
+        Some(image) => draw_styled_image(backend, widget_image(image, theme, disabled), range_rect),
+        None => {
+            let hover = if slider.hovered_part == Some(RangeSliderPart::Range) {
+                ctx.hover(slider.id, true)
+            } else {
+                0.0
+            };
+            let color = theme
+                .selection_color
+                .scaled(1.0 + (theme.hover_shade - 1.0) * hover);
+            backend.fill_rect(range_rect, color);
+        }
+    }
+
+    // the high thumb last, so it's on top when the thumbs meet
+    for (part, value) in [
+        (RangeSliderPart::Low, slider.low),
+        (RangeSliderPart::High, slider.high),
+    ] {
+        let thumb_rect = slider.thumb_rect(value);
+        let hovered = slider.hovered_part == Some(part);
+        draw_state_box(
+            backend,
+            thumb_rect,
+            theme,
+            slider.thumb_state_images.as_ref(),
+            WidgetState {
+                hovered,
+                pressed: slider.dragging == Some(part),
+                focused,
+                disabled,
+            },
+            if hovered {
+                ctx.hover(slider.id, true)
+            } else {
+                0.0
+            },
+        );
+        if let Some(image) = slider.thumb_image {
+            draw_styled_image(backend, widget_image(image, theme, disabled), thumb_rect);
+        }
+    }
+
+    if let Some(image) = slider.track_image {
+        if image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body);
+        }
+    }
+}
+
+fn draw_xy_pad<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    pad: &XYPad,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, pad.style_class.as_deref(), &pad.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let body = Rect::new(pad.position, pad.size);
+    if body.size.x <= 0.0 || body.size.y <= 0.0 {
+        return;
+    }
+
+    match pad.track_image {
+        Some(image) if !image.draw_over_content => {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body)
+        }
+        _ => backend.fill_rect(body, theme.track_color),
+    }
+    if pad.circular {
+        // the inscribed circle as line segments
+        const SEGMENTS: usize = 32;
+        let center = body.position + body.size * 0.5;
+        let point = |index: usize| {
+            let angle = index as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+            center + Vec2::new(angle.cos(), angle.sin()) * body.size * 0.5
+        };
+        for index in 0..SEGMENTS {
+            backend.draw_line(point(index), point(index + 1), theme.shadow_color, 1.0);
+        }
+    }
+
+    // crosshair through the thumb, across the whole pad
+    let center = pad.value_to_point(pad.value);
+    let end = body.position + body.size;
+    backend.draw_line(
+        Vec2::new(body.position.x, center.y),
+        Vec2::new(end.x, center.y),
+        theme.shadow_color,
+        1.0,
+    );
+    backend.draw_line(
+        Vec2::new(center.x, body.position.y),
+        Vec2::new(center.x, end.y),
+        theme.shadow_color,
+        1.0,
+    );
+
+    let thumb_rect = pad.thumb_rect();
+    draw_state_box(
+        backend,
+        thumb_rect,
+        theme,
+        pad.thumb_state_images.as_ref(),
+        WidgetState {
+            hovered: pad.hovered,
+            pressed: pad.was_pressed,
+            focused,
+            disabled,
+        },
+        ctx.hover(pad.id, pad.hovered),
+    );
+    if let Some(image) = pad.thumb_image {
+        draw_styled_image(backend, widget_image(image, theme, disabled), thumb_rect);
+    }
+
+    if let Some(image) = pad.track_image {
+        if image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body);
+        }
+    }
+}
+
+fn draw_vertical_slider<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    slider: &VerticalSlider,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, slider.style_class.as_deref(), &slider.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let body = Rect::new(slider.position, slider.size);
+    if body.size.x <= 0.0 || body.size.y <= 0.0 {
+        return;
+    }
+
+    match slider.track_image {
+        Some(image) if !image.draw_over_content => {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body)
+        }
+        _ => backend.fill_rect(body, theme.track_color),
+    }
+
+    let range = (slider.maximum - slider.minimum).abs();
+    let value_fraction = if range <= f32::EPSILON {
+        0.0
+    } else {
+        ((slider.value - slider.minimum) / (slider.maximum - slider.minimum)).clamp(0.0, 1.0)
+    };
+
+    let thumb_y = body.position.y + value_fraction * body.size.y;
+    let thumb_height = slider.thumb_height.max(1.0);
+    let thumb_rect = Rect::new(
+        Vec2::new(body.position.x, thumb_y - thumb_height / 2.0),
+        Vec2::new(body.size.x, thumb_height),
+    );
+
+    draw_state_box(
+        backend,
+        thumb_rect,
+        theme,
+        slider.thumb_state_images.as_ref(),
+        WidgetState {
+            hovered: slider.hovered,
+            pressed: slider.was_pressed,
+            focused,
+            disabled,
+        },
+        ctx.hover(slider.id, slider.hovered),
+    );
+
+    if let Some(image) = slider.thumb_image {
+        draw_styled_image(backend, widget_image(image, theme, disabled), thumb_rect);
+    }
+
+    if let Some(image) = slider.track_image {
+        if image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), body);
+        }
+    }
+}
+
+fn draw_draggable<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    draggable: &Draggable,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        draggable.style_class.as_deref(),
+        &draggable.style,
+    );
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(draggable.position, draggable.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+
+    draw_state_box(
+        backend,
+        rect,
+        theme,
+        draggable.state_images.as_ref(),
+        WidgetState {
+            hovered: draggable.hovered,
+            pressed: draggable.being_dragged,
+            focused,
+            disabled,
+        },
+        ctx.hover(draggable.id, draggable.hovered),
+    );
+
+    if let Some(image) = draggable.background_image {
+        if !image.draw_over_content {
+            draw_styled_image(backend, widget_image(image, theme, disabled), rect);
         }
     }
 
@@ -296,22 +1788,33 @@ fn draw_draggable<B: DrawBackend>(backend: &mut B, draggable: &Draggable, theme:
     );
 
     if let Some(label) = &draggable.label {
-        let text_pos = rect.position + Vec2::new(6.0, 4.0);
-        backend.draw_text(label, text_pos, theme.font_size_px, theme.text_color);
+        let text_pos = rect.position + theme.padding_px;
+        let (font, font_size) =
+            theme.resolve_font(TextRole::Title, draggable.font, draggable.font_size_px);
+        backend.draw_text_with_font(label, text_pos, font, font_size, theme.text_color);
     }
 
     if let Some(image) = draggable.background_image {
         if image.draw_over_content {
-            backend.draw_image(image, rect);
+            draw_styled_image(backend, widget_image(image, theme, disabled), rect);
         }
     }
 }
 
-fn draw_left_right_selector<B: DrawBackend>(
+fn draw_left_right_selector<B: DrawBackend + ?Sized>(
     backend: &mut B,
     selector: &LeftRightSelector,
     theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
 ) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        selector.style_class.as_deref(),
+        &selector.style,
+    );
+    let theme = &theme.resolve_disabled(disabled);
     let rect = Rect::new(selector.position, selector.size);
     let center_position = rect.position + Vec2::new(selector.button_width, 0.0);
     let center_size = Vec2::new(rect.size.x - selector.button_width * 2.0, rect.size.y);
@@ -319,52 +1822,881 @@ fn draw_left_right_selector<B: DrawBackend>(
         backend.fill_rect(Rect::new(center_position, center_size), theme.track_color);
     }
 
-    draw_button(backend, &selector.left_button, theme);
-    draw_button(backend, &selector.right_button, theme);
+    draw_button(
+        backend,
+        &selector.left_button,
+        theme,
+        ctx,
+        disabled,
+        focused,
+    );
+    draw_button(
+        backend,
+        &selector.right_button,
+        theme,
+        ctx,
+        disabled,
+        focused,
+    );
 
     if let Some(selected) = selector.selected_option() {
-        let text_pos = center_position + Vec2::new(6.0, 4.0);
-        backend.draw_text(selected, text_pos, theme.font_size_px, theme.text_color);
+        let text_pos = center_position + theme.padding_px;
+        let (font, font_size) =
+            theme.resolve_font(TextRole::Body, selector.font, selector.font_size_px);
+        backend.draw_text_with_font(selected, text_pos, font, font_size, theme.text_color);
+    }
+}
+
+fn draw_dropdown<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    dropdown: &Dropdown,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        dropdown.style_class.as_deref(),
+        &dropdown.style,
+    );
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(dropdown.position, dropdown.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+    let head = &dropdown.head_button;
+    draw_beveled_box(
+        backend,
+        rect,
+        theme,
+        ctx.hover(head.id, head.hovered),
+        head.pressed || dropdown.open,
+        theme.control_color,
+    );
+
+    let (font, font_size) =
+        theme.resolve_font(TextRole::Body, dropdown.font, dropdown.font_size_px);
+    if let Some(selected) = dropdown.selected_option() {
+        let text_pos = rect.position + theme.padding_px;
+        backend.draw_text_with_font(selected, text_pos, font, font_size, theme.text_color);
+    }
+
+    // down arrow at the right edge
+    let arrow_half_width = font_size * 0.25;
+    let center = Vec2::new(
+        rect.position.x + rect.size.x - theme.padding_px.x - arrow_half_width,
+        rect.position.y + rect.size.y * 0.5,
+    );
+    let tip = center + Vec2::new(0.0, arrow_half_width * 0.5);
+    let thickness = theme.bevel_size_px.max(2.0);
+    backend.draw_line(
+        center - Vec2::new(arrow_half_width, arrow_half_width * 0.5),
+        tip,
+        theme.text_color,
+        thickness,
+    );
+    backend.draw_line(
+        tip,
+        center + Vec2::new(arrow_half_width, -arrow_half_width * 0.5),
+        theme.text_color,
+        thickness,
+    );
+}
+
+fn draw_number_field(
+    backend: &mut dyn DrawBackend,
+    field: &NumberField,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, field.style_class.as_deref(), &field.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = field.field_rect();
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+    let text = match &field.edit_text {
+        Some(edit_text) => Cow::Borrowed(edit_text.as_str()),
+        None => Cow::Owned(field.display_text()),
+    };
+    let font = theme.resolve_font(TextRole::Body, field.font, field.font_size_px);
+    let input = TextInputState {
+        editing: field.is_editing(),
+        selected: field.replace_on_type,
+    };
+    let hover = ctx.hover(field.id, field.hovered);
+    draw_text_input(backend, rect, theme, hover, &text, input, font);
+    if focused {
+        draw_outline(
+            backend,
+            rect,
+            theme.text_color,
+            theme.bevel_size_px.max(1.0),
+        );
+    }
+
+    for (button, up) in [
+        (&field.increment_button, true),
+        (&field.decrement_button, false),
+    ] {
+        if button.size.x <= 0.0 || button.size.y <= 0.0 {
+            continue;
+        }
+        let state = WidgetState {
+            hovered: button.hovered,
+            pressed: button.pressed,
+            focused: false,
+            disabled,
+        };
+        draw_button_visual(backend, theme, ctx, button, state);
+        let center = button.position + button.size * 0.5;
+        let half_width = button.size.x.min(button.size.y * 2.0) * 0.2;
+        let rise = if up {
+            -half_width * 0.5
+        } else {
+            half_width * 0.5
+        };
+        let thickness = theme.bevel_size_px.max(2.0);
+        backend.draw_line(
+            center + Vec2::new(-half_width, -rise),
+            center + Vec2::new(0.0, rise),
+            theme.text_color,
+            thickness,
+        );
+        backend.draw_line(
+            center + Vec2::new(0.0, rise),
+            center + Vec2::new(half_width, -rise),
+            theme.text_color,
+            thickness,
+        );
+    }
+}
+
+/// Whether a text input is being typed into, and whether its text is all selected.
+#[derive(Clone, Copy)]
+struct TextInputState {
+    editing: bool,
+    selected: bool,
+}
+
+/// Sunken box with single-line text, and a caret at the end while editing.
+fn draw_text_input(
+    backend: &mut dyn DrawBackend,
+    rect: Rect,
+    theme: &Theme,
+    hover: f32,
+    text: &str,
+    state: TextInputState,
+    (font, font_size): (FontId, f32),
+) {
+    // bevel lit from the bottom-right so it reads as sunken, but without the darker
+    // pressed fill that would hide the text
+    let offset = Vec2::splat(theme.bevel_size_px);
+    backend.fill_rect(
+        Rect::new(rect.position, rect.size + offset),
+        theme.highlight_color,
+    );
+    backend.fill_rect(rect, theme.shadow_color);
+    backend.fill_rect(
+        Rect::new(rect.position + offset, (rect.size - offset).max(Vec2::ONE)),
+        theme
+            .control_color
+            .scaled(1.0 + (theme.hover_shade - 1.0) * hover),
+    );
+    let text_pos = Vec2::new(
+        rect.position.x + theme.padding_px.x,
+        rect.position.y + (rect.size.y - font_size) * 0.5,
+    );
+    let width = text_width(backend, text, font, font_size);
+    backend.push_clip_rect(rect);
+    if state.editing && state.selected {
+        backend.fill_rect(
+            Rect::new(text_pos, Vec2::new(width, font_size)),
+            theme.selection_color,
+        );
+    }
+    backend.draw_text_with_font(text, text_pos, font, font_size, theme.text_color);
+    if state.editing {
+        let caret_x = text_pos.x + width + 1.0;
+        backend.draw_line(
+            Vec2::new(caret_x, text_pos.y),
+            Vec2::new(caret_x, text_pos.y + font_size),
+            theme.text_color,
+            1.0,
+        );
+    }
+    backend.pop_clip_rect();
+}
+
+fn draw_color_picker(
+    backend: &mut dyn DrawBackend,
+    picker: &ColorPicker,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, picker.style_class.as_deref(), &picker.style);
+    let theme = &theme.resolve_disabled(disabled);
+    if picker.size.x <= 0.0 || picker.size.y <= 0.0 {
+        return;
+    }
+    let thickness = theme.bevel_size_px.max(1.0);
+    let white = Color::rgb(255, 255, 255);
+    let black = Color::rgb(0, 0, 0);
+
+    // white to the hue across, then darkened toward black down
+    let square = picker.saturation_value_rect();
+    let hue = picker.hue_color();
+    backend.fill_rect_gradient(square, [white, hue, hue, white]);
+    let clear_black = Color::rgba(0, 0, 0, 0);
+    backend.fill_rect_gradient(square, [clear_black, clear_black, black, black]);
+    draw_outline(backend, square, theme.shadow_color, 1.0);
+    let cursor = square.position + square.size * Vec2::new(picker.saturation, 1.0 - picker.value);
+    let cursor_color = if picker.value > 0.5 { black } else { white };
+    draw_outline(
+        backend,
+        Rect::new(cursor - Vec2::splat(3.0), Vec2::splat(6.0)),
+        cursor_color,
+        1.0,
+    );
+    if focused {
+        draw_outline(backend, square, theme.text_color, thickness);
+    }
+
+    // one gradient per sixth of the wheel
+    let strip = picker.hue_rect();
+    let sixth = strip.size.y / 6.0;
+    for index in 0..6 {
+        let top = Color::from_hsv(index as f32 * 60.0, 1.0, 1.0);
+        let bottom = Color::from_hsv((index + 1) as f32 * 60.0, 1.0, 1.0);
+        backend.fill_rect_gradient(
+            Rect::new(
+                strip.position + Vec2::new(0.0, index as f32 * sixth),
+                Vec2::new(strip.size.x, sixth),
+            ),
+            [top, top, bottom, bottom],
+        );
+    }
+    draw_strip_marker(backend, strip, picker.hue / 360.0, theme);
+
+    let color = picker.color();
+    if let Some(strip) = picker.alpha_rect() {
+        draw_checkerboard(backend, strip, strip.size.x * 0.5);
+        let opaque = Color::rgba(color.r, color.g, color.b, 255);
+        let clear = Color::rgba(color.r, color.g, color.b, 0);
+        backend.fill_rect_gradient(strip, [opaque, opaque, clear, clear]);
+        draw_strip_marker(backend, strip, 1.0 - picker.alpha, theme);
+    }
+
+    let preview = picker.preview_rect();
+    draw_checkerboard(backend, preview, preview.size.y * 0.5);
+    backend.fill_rect(preview, color);
+    draw_outline(backend, preview, theme.shadow_color, 1.0);
+
+    let text = match &picker.hex_text {
+        Some(hex_text) => Cow::Borrowed(hex_text.as_str()),
+        None => Cow::Owned(color.to_hex()),
+    };
+    let font = theme.resolve_font(TextRole::Body, picker.font, picker.font_size_px);
+    let input = TextInputState {
+        editing: picker.is_editing(),
+        selected: picker.replace_on_type,
+    };
+    let hex = picker.hex_rect();
+    let hover = ctx.hover(picker.id, picker.hex_hovered);
+    draw_text_input(backend, hex, theme, hover, &text, input, font);
+
+    for (index, (rect, swatch)) in picker
+        .swatch_rects()
+        .iter()
+        .zip(&picker.swatches)
+        .enumerate()
+    {
+        draw_checkerboard(backend, *rect, rect.size.x * 0.5);
+        backend.fill_rect(*rect, *swatch);
+        let outline = if picker.hovered_swatch == Some(index) {
+            theme.text_color
+        } else {
+            theme.shadow_color
+        };
+        draw_outline(backend, *rect, outline, 1.0);
+    }
+}
+
+/// Two-tone squares behind translucent colors.
+fn draw_checkerboard(backend: &mut dyn DrawBackend, rect: Rect, cell: f32) {
+    backend.fill_rect(rect, Color::rgb(204, 204, 204));
+    if cell <= 0.0 {
+        return;
+    }
+    let cells = (rect.size / cell).ceil();
+    backend.push_clip_rect(rect);
+    for row in 0..cells.y as usize {
+        for column in (row % 2..cells.x as usize).step_by(2) {
+            backend.fill_rect(
+                Rect::new(
+                    rect.position + Vec2::new(column as f32, row as f32) * cell,
+                    Vec2::splat(cell),
+                ),
+                Color::rgb(153, 153, 153),
+            );
+        }
+    }
+    backend.pop_clip_rect();
+}
+
+/// Outline of a vertical strip and a notch across it `fraction` of the way down.
+fn draw_strip_marker(backend: &mut dyn DrawBackend, strip: Rect, fraction: f32, theme: &Theme) {
+    draw_outline(backend, strip, theme.shadow_color, 1.0);
+    let y = strip.position.y + strip.size.y * fraction.clamp(0.0, 1.0);
+    draw_outline(
+        backend,
+        Rect::new(
+            Vec2::new(strip.position.x - 1.0, y - 2.0),
+            Vec2::new(strip.size.x + 2.0, 4.0),
+        ),
+        theme.text_color,
+        1.0,
+    );
+}
+
+fn draw_checkbox<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    checkbox: &Checkbox,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        checkbox.style_class.as_deref(),
+        &checkbox.style,
+    );
+    let theme = &theme.resolve_disabled(disabled);
+    if checkbox.size.x <= 0.0 || checkbox.size.y <= 0.0 {
+        return;
+    }
+    let button = &checkbox.button;
+    let box_rect = draw_choice_box(
+        backend,
+        Rect::new(checkbox.position, checkbox.size),
+        theme,
+        ctx.hover(button.id, button.hovered),
+        button.pressed,
+    );
+
+    let color = theme.text_color;
+    let thickness = (box_rect.size.x * 0.12).max(2.0);
+    let at = |x: f32, y: f32| box_rect.position + box_rect.size * Vec2::new(x, y);
+    if checkbox.indeterminate {
+        backend.draw_line(at(0.25, 0.5), at(0.75, 0.5), color, thickness);
+    } else if checkbox.checked {
+        backend.draw_line(at(0.22, 0.52), at(0.42, 0.72), color, thickness);
+        backend.draw_line(at(0.42, 0.72), at(0.8, 0.28), color, thickness);
+    }
+
+    if let Some(label) = &checkbox.label {
+        draw_choice_label(
+            backend,
+            label,
+            box_rect,
+            theme,
+            checkbox.font,
+            checkbox.font_size_px,
+        );
+    }
+}
+
+fn draw_radio_group<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    group: &RadioGroup,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, group.style_class.as_deref(), &group.style);
+    let theme = &theme.resolve_disabled(disabled);
+    for (index, (button, option)) in group.option_buttons.iter().zip(&group.options).enumerate() {
+        if button.size.x <= 0.0 || button.size.y <= 0.0 {
+            continue;
+        }
+        let box_rect = draw_choice_box(
+            backend,
+            Rect::new(button.position, button.size),
+            theme,
+            ctx.hover(button.id, button.hovered),
+            button.pressed,
+        );
+        if index == group.selected_index {
+            // filled dot in the middle of the box
+            let inset = box_rect.size * 0.3;
+            backend.fill_rect(
+                Rect::new(box_rect.position + inset, box_rect.size - inset * 2.0),
+                theme.text_color,
+            );
+        }
+        draw_choice_label(
+            backend,
+            option,
+            box_rect,
+            theme,
+            group.font,
+            group.font_size_px,
+        );
     }
 }
 
-fn draw_button_toggle<B: DrawBackend>(backend: &mut B, toggle: &ButtonToggle, theme: &Theme) {
-    draw_button_visual(
+/// Square box at the left of `rect` for checkboxes and radio options. Returns the box
+/// rect.
+fn draw_choice_box<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    rect: Rect,
+    theme: &Theme,
+    hover: f32,
+    pressed: bool,
+) -> Rect {
+    let (_, font_size) = theme.resolve_font(TextRole::Body, None, None);
+    let side = font_size.min(rect.size.y).min(rect.size.x);
+    let box_rect = Rect::new(
+        rect.position + Vec2::new(0.0, (rect.size.y - side) * 0.5),
+        Vec2::splat(side),
+    );
+    draw_beveled_box(
         backend,
+        box_rect,
         theme,
-        toggle.left_button.position,
-        toggle.left_button.size,
-        toggle.left_button.label.as_deref(),
-        toggle.left_button.hovered,
-        toggle.toggled_left,
-        toggle.left_button.background_image,
-    );
-    draw_button_visual(
+        hover,
+        pressed,
+        theme.control_color,
+    );
+    box_rect
+}
+
+fn draw_choice_label<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    label: &str,
+    box_rect: Rect,
+    theme: &Theme,
+    font: Option<FontId>,
+    font_size_px: Option<f32>,
+) {
+    let (font, font_size) = theme.resolve_font(TextRole::Body, font, font_size_px);
+    let text_pos = Vec2::new(
+        box_rect.position.x + box_rect.size.x + theme.padding_px.x,
+        box_rect.position.y + (box_rect.size.y - font_size) * 0.5,
+    );
+    backend.draw_text_with_font(label, text_pos, font, font_size, theme.text_color);
+}
+
+/// Option list of an open dropdown, drawn in the overlay pass.
+fn draw_dropdown_popup<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    dropdown: &Dropdown,
+    theme: &Theme,
+    ctx: &DrawContext,
+) {
+    let theme = &theme.resolve_style(
+        ctx.classes,
+        dropdown.style_class.as_deref(),
+        &dropdown.style,
+    );
+    let popup = dropdown.popup_rect();
+    let bevel = Vec2::splat(theme.bevel_size_px);
+    backend.fill_rect(
+        Rect::new(popup.position, popup.size + bevel),
+        theme.shadow_color,
+    );
+    backend.fill_rect(popup, theme.control_color);
+
+    let (font, font_size) =
+        theme.resolve_font(TextRole::Body, dropdown.font, dropdown.font_size_px);
+    let visible_rows = dropdown.visible_rows();
+    backend.push_clip_rect(popup);
+    for row in 0..visible_rows {
+        let index = dropdown.scroll_offset + row;
+        let Some(option) = dropdown.options.get(index) else {
+            break;
+        };
+        let row_rect = Rect::new(
+            popup.position + Vec2::new(0.0, row as f32 * dropdown.row_height),
+            Vec2::new(popup.size.x, dropdown.row_height),
+        );
+        let text_color = if index == dropdown.highlighted_index {
+            backend.fill_rect(row_rect, theme.selection_color);
+            theme.selection_text_color
+        } else {
+            theme.text_color
+        };
+        backend.draw_text_with_font(
+            option,
+            row_rect.position + theme.padding_px,
+            font,
+            font_size,
+            text_color,
+        );
+    }
+
+    // scroll position indicator
+    let option_count = dropdown.options.len();
+    if option_count > visible_rows {
+        let bar_width = (theme.padding_px.x * 0.5).max(3.0);
+        let track = Rect::new(
+            Vec2::new(
+                popup.position.x + popup.size.x - bar_width,
+                popup.position.y,
+            ),
+            Vec2::new(bar_width, popup.size.y),
+        );
+        backend.fill_rect(track, theme.track_color);
+        let thumb = Rect::new(
+            track.position
+                + Vec2::new(
+                    0.0,
+                    track.size.y * dropdown.scroll_offset as f32 / option_count as f32,
+                ),
+            Vec2::new(
+                bar_width,
+                track.size.y * visible_rows as f32 / option_count as f32,
+            ),
+        );
+        backend.fill_rect(thumb, theme.selection_color);
+    }
+    backend.pop_clip_rect();
+}
+
+fn draw_list_view(
+    backend: &mut dyn DrawBackend,
+    list: &ListView,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, list.style_class.as_deref(), &list.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(list.position, list.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+    backend.fill_rect(rect, theme.control_color);
+
+    backend.push_clip_rect(rect);
+    for index in list.visible_range() {
+        let row = list.row(index);
+        if row.selected {
+            backend.fill_rect(row.rect, theme.selection_color);
+        } else if row.hovered && !disabled {
+            backend.fill_rect(row.rect, theme.control_color.scaled(theme.hover_shade));
+        }
+        if let Some(draw_row) = &list.draw_row {
+            draw_row(backend, theme, &row);
+        }
+        if row.cursor && focused {
+            draw_outline(
+                backend,
+                row.rect,
+                theme.text_color,
+                theme.bevel_size_px.max(1.0),
+            );
+        }
+    }
+
+    if let Some(y) = list.drop_indicator_y() {
+        let thickness = (theme.bevel_size_px * 2.0).max(2.0);
+        backend.fill_rect(
+            Rect::new(
+                Vec2::new(rect.position.x, y - thickness * 0.5),
+                Vec2::new(rect.size.x, thickness),
+            ),
+            theme.text_color,
+        );
+    }
+
+    draw_scroll_indicator(
         backend,
+        rect,
+        list.content_height(),
+        list.scroll_offset,
         theme,
-        toggle.right_button.position,
-        toggle.right_button.size,
-        toggle.right_button.label.as_deref(),
-        toggle.right_button.hovered,
-        !toggle.toggled_left,
-        toggle.right_button.background_image,
     );
+    backend.pop_clip_rect();
 }
 
-fn draw_move_and_resize_thumbs<B: DrawBackend>(
+/// Thin scroll position bar along the right edge of a scrolled `rect`, if the content
+/// is taller than it.
+fn draw_scroll_indicator(
+    backend: &mut dyn DrawBackend,
+    rect: Rect,
+    content_height: f32,
+    scroll_offset: f32,
+    theme: &Theme,
+) {
+    if content_height <= rect.size.y {
+        return;
+    }
+    let bar_width = (theme.padding_px.x * 0.5).max(3.0);
+    let track = Rect::new(
+        Vec2::new(rect.position.x + rect.size.x - bar_width, rect.position.y),
+        Vec2::new(bar_width, rect.size.y),
+    );
+    backend.fill_rect(track, theme.track_color);
+    let thumb = Rect::new(
+        track.position + Vec2::new(0.0, track.size.y * scroll_offset / content_height),
+        Vec2::new(bar_width, track.size.y * rect.size.y / content_height),
+    );
+    backend.fill_rect(thumb, theme.selection_color);
+}
+
+fn draw_tree_view(
+    backend: &mut dyn DrawBackend,
+    tree: &TreeView,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, tree.style_class.as_deref(), &tree.style);
+    let theme = &theme.resolve_disabled(disabled);
+    let rect = Rect::new(tree.position, tree.size);
+    if rect.size.x <= 0.0 || rect.size.y <= 0.0 {
+        return;
+    }
+    backend.fill_rect(rect, theme.control_color);
+
+    let (font, font_size) = theme.resolve_font(TextRole::Body, tree.font, tree.font_size_px);
+    let guide_color = theme.track_color;
+    let guide_thickness = theme.bevel_size_px.max(1.0);
+    let column_x = |level: usize| rect.position.x + (level as f32 + 0.5) * tree.indent;
+    backend.push_clip_rect(rect);
+    for index in tree.visible_range() {
+        let (Some(row), Some(node)) = (tree.rows.get(index), tree.row_node(index)) else {
+            continue;
+        };
+        let row_rect = tree.row_rect(index);
+        let (top, bottom) = (row_rect.position.y, row_rect.position.y + row_rect.size.y);
+        let middle = row_rect.position.y + row_rect.size.y * 0.5;
+        let selected = tree.selected == Some(row.key);
+        if selected {
+            backend.fill_rect(row_rect, theme.selection_color);
+        } else if tree.hovered_row == Some(index) && !disabled {
+            backend.fill_rect(row_rect, theme.control_color.scaled(theme.hover_shade));
+        }
+
+        // guides of ancestors that have more children below this row
+        for level in 0..row.depth.saturating_sub(1) {
+            if row.guides[level + 1] {
+                let x = column_x(level);
+                backend.draw_line(
+                    Vec2::new(x, top),
+                    Vec2::new(x, bottom),
+                    guide_color,
+                    guide_thickness,
+                );
+            }
+        }
+        // elbow from the parent's guide to this node
+        if row.depth > 0 {
+            let x = column_x(row.depth - 1);
+            let end_y = if row.last_child { middle } else { bottom };
+            backend.draw_line(
+                Vec2::new(x, top),
+                Vec2::new(x, end_y),
+                guide_color,
+                guide_thickness,
+            );
+            let end_x = rect.position.x + (row.depth as f32 + 0.25) * tree.indent;
+            backend.draw_line(
+                Vec2::new(x, middle),
+                Vec2::new(end_x, middle),
+                guide_color,
+                guide_thickness,
+            );
+        }
+
+        let text_color = if selected {
+            theme.selection_text_color
+        } else {
+            theme.text_color
+        };
+        if row.has_children {
+            let center = Vec2::new(column_x(row.depth), middle);
+            let half = tree.indent * 0.2;
+            let thickness = (half * 0.4).max(1.0);
+            let (a, tip, b) = if row.expanded {
+                (
+                    center + Vec2::new(-half, -half * 0.5),
+                    center + Vec2::new(0.0, half * 0.5),
+                    center + Vec2::new(half, -half * 0.5),
+                )
+            } else {
+                (
+                    center + Vec2::new(-half * 0.5, -half),
+                    center + Vec2::new(half * 0.5, 0.0),
+                    center + Vec2::new(-half * 0.5, half),
+                )
+            };
+            backend.draw_line(a, tip, text_color, thickness);
+            backend.draw_line(tip, b, text_color, thickness);
+            if row.expanded {
+                // guide down to the first child
+                backend.draw_line(
+                    Vec2::new(center.x, center.y + half),
+                    Vec2::new(center.x, bottom),
+                    guide_color,
+                    guide_thickness,
+                );
+            }
+        }
+
+        let mut text_x = rect.position.x + (row.depth as f32 + 1.0) * tree.indent;
+        if let Some(icon) = node.icon {
+            let icon_size = (row_rect.size.y - theme.padding_px.y).max(0.0);
+            let icon_rect = Rect::new(
+                Vec2::new(text_x, middle - icon_size * 0.5),
+                Vec2::splat(icon_size),
+            );
+            draw_styled_image(backend, widget_image(icon, theme, disabled), icon_rect);
+            text_x += icon_size;
+        }
+        backend.draw_text_with_font(
+            &node.text,
+            Vec2::new(text_x + theme.padding_px.x * 0.5, middle - font_size * 0.5),
+            font,
+            font_size,
+            text_color,
+        );
+
+        if selected && focused {
+            draw_outline(
+                backend,
+                row_rect,
+                theme.text_color,
+                theme.bevel_size_px.max(1.0),
+            );
+        }
+    }
+    draw_scroll_indicator(
+        backend,
+        rect,
+        tree.content_height(),
+        tree.scroll_offset,
+        theme,
+    );
+    backend.pop_clip_rect();
+}
+
+/// Draw `text` in a `ListView` row the way the theme draws list text: the list's font or
+/// the body font, vertically centered, in the selection text color when the row is
+/// selected. For use in `ListView::draw_row` callbacks.
+pub fn draw_list_row_text(backend: &mut dyn DrawBackend, theme: &Theme, row: &ListRow, text: &str) {
+    let (font, font_size) = theme.resolve_font(TextRole::Body, row.font, row.font_size_px);
+    let color = if row.selected {
+        theme.selection_text_color
+    } else {
+        theme.text_color
+    };
+    let position = Vec2::new(
+        row.rect.position.x + theme.padding_px.x,
+        row.rect.position.y + (row.rect.size.y - font_size) * 0.5,
+    );
+    backend.draw_text_with_font(text, position, font, font_size, color);
+}
+
+fn draw_outline(backend: &mut dyn DrawBackend, rect: Rect, color: Color, thickness: f32) {
+    let inset = thickness * 0.5;
+    let (min, max) = (
+        rect.position + Vec2::splat(inset),
+        rect.position + rect.size - Vec2::splat(inset),
+    );
+    let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    for i in 0..4 {
+        backend.draw_line(corners[i], corners[(i + 1) % 4], color, thickness);
+    }
+}
+
+fn draw_button_toggle<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    toggle: &ButtonToggle,
+    theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, toggle.style_class.as_deref(), &toggle.style);
+    let theme = &theme.resolve_disabled(disabled);
+    // the selected half is drawn pressed
+    for (button, selected) in [
+        (&toggle.left_button, toggle.toggled_left),
+        (&toggle.right_button, !toggle.toggled_left),
+    ] {
+        let state = WidgetState {
+            hovered: button.hovered,
+            pressed: selected,
+            focused,
+            disabled,
+        };
+        draw_button_visual(backend, theme, ctx, button, state);
+    }
+}
+
+fn draw_move_and_resize_thumbs<B: DrawBackend + ?Sized>(
     backend: &mut B,
     thumbs: &MoveAndResizeThumbs,
     theme: &Theme,
+    ctx: &DrawContext,
+    disabled: bool,
+    focused: bool,
+) {
+    let theme = &theme.resolve_style(ctx.classes, thumbs.style_class.as_deref(), &thumbs.style);
+    let theme = &theme.resolve_disabled(disabled);
+    draw_draggable(backend, &thumbs.move_thumb, theme, ctx, disabled, focused);
+    draw_draggable(backend, &thumbs.resize_thumb, theme, ctx, disabled, focused);
+}
+
+/// Draw the widget's state skin, or the theme's bevel box when it has none.
+fn draw_state_box<B: DrawBackend + ?Sized>(
+    backend: &mut B,
+    rect: Rect,
+    theme: &Theme,
+    images: Option<&StateImages>,
+    state: WidgetState,
+    hover: f32,
 ) {
-    draw_draggable(backend, &thumbs.move_thumb, theme);
-    draw_draggable(backend, &thumbs.resize_thumb, theme);
+    let Some(images) = images else {
+        draw_beveled_box(
+            backend,
+            rect,
+            theme,
+            hover,
+            state.pressed,
+            theme.control_color,
+        );
+        return;
+    };
+    let image = images.pick(state);
+    // an explicit disabled skin is drawn as-is, the fallback gets the disabled tint
+    let dim = state.disabled && images.disabled.is_none();
+    draw_styled_image(backend, widget_image(image, theme, dim), rect);
+}
+
+fn widget_image(image: ImageStyle, theme: &Theme, disabled: bool) -> ImageStyle {
+    if !disabled {
+        return image;
+    }
+    ImageStyle {
+        tint: image.tint.scaled(theme.disabled_shade),
+        ..image
+    }
 }
 
-fn draw_beveled_box<B: DrawBackend>(
+fn draw_beveled_box<B: DrawBackend + ?Sized>(
     backend: &mut B,
     rect: Rect,
     theme: &Theme,
-    hovered: bool,
+    hover: f32,
     pressed: bool,
     color: Color,
 ) {
@@ -382,8 +2714,8 @@ fn draw_beveled_box<B: DrawBackend>(
         backend.fill_rect(Rect::new(rect.position, rect.size), theme.highlight_color);
 
         let mut fill = color;
-        if hovered {
-            fill = fill.scaled(theme.hover_shade);
+        if hover > 0.0 {
+            fill = fill.scaled(1.0 + (theme.hover_shade - 1.0) * hover);
         }
         backend.fill_rect(Rect::new(rect.position + offset, inner_size), fill);
     } else {
@@ -0,0 +1,451 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use glam::Vec2;
+
+use crate::{Color, FontId, FontStyle, StyleClasses, StyleOverride, Theme};
+
+/// Error in theme file text, with the 1-based line it was found on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ThemeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ThemeParseError {}
+
+#[derive(Debug)]
+pub enum ThemeFileError {
+    Io(io::Error),
+    Parse(ThemeParseError),
+}
+
+impl fmt::Display for ThemeFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeFileError::Io(err) => write!(f, "theme file io error: {err}"),
+            ThemeFileError::Parse(err) => write!(f, "theme file parse error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeFileError {}
+
+impl From<io::Error> for ThemeFileError {
+    fn from(err: io::Error) -> Self {
+        ThemeFileError::Io(err)
+    }
+}
+
+impl From<ThemeParseError> for ThemeFileError {
+    fn from(err: ThemeParseError) -> Self {
+        ThemeFileError::Parse(err)
+    }
+}
+
+/// Parse theme file text into a theme and its style classes.
+///
+/// The format is `key = value` lines, with `#` comments. Keys not in the file keep their
+/// `Theme::default()` value. `[class name]` starts a style class section whose keys
+/// fill a `StyleOverride`.
+///
+/// ```text
+/// control_color = #c8c8c8
+/// hover_shade = 0.65
+/// padding_px = 6 4
+/// title_font = 2 28      # font id, optional size
+/// bold_font = none
+///
+/// [class danger]
+/// control_color = #c82828
+/// ```
+pub fn parse_theme(text: &str) -> Result<(Theme, StyleClasses), ThemeParseError> {
+    let mut theme = Theme::default();
+    let mut classes = StyleClasses::new();
+    let mut class: Option<(String, StyleOverride)> = None;
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |message: String| ThemeParseError {
+            line: line_number,
+            message,
+        };
+
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header
+                .strip_suffix(']')
+                .ok_or_else(|| error("section header is missing ']'".to_string()))?;
+            let name = header
+                .trim()
+                .split_once(char::is_whitespace)
+                .filter(|(kind, _)| *kind == "class")
+                .map(|(_, name)| name.trim())
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| error(format!("unknown section '[{header}]'")))?;
+            if let Some((name, style)) = class.take() {
+                classes.classes.insert(name, style);
+            }
+            class = Some((name.to_string(), StyleOverride::default()));
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error(format!("expected 'key = value', found '{line}'")))?;
+        let (key, value) = (key.trim(), value.trim());
+        let result = match &mut class {
+            Some((_, style)) => set_style_key(style, key, value),
+            None => set_theme_key(&mut theme, key, value),
+        };
+        result.map_err(error)?;
+    }
+
+    if let Some((name, style)) = class.take() {
+        classes.classes.insert(name, style);
+    }
+    Ok((theme, classes))
+}
+
+/// Theme file text for `theme` and `classes`, readable by `parse_theme`.
+pub fn theme_to_string(theme: &Theme, classes: &StyleClasses) -> String {
+    let mut out = String::new();
+    let mut line = |key: &str, value: String| out.push_str(&format!("{key} = {value}\n"));
+
+    line("control_color", theme.control_color.to_hex());
+    line("text_color", theme.text_color.to_hex());
+    line("link_color", theme.link_color.to_hex());
+    line("shadow_color", theme.shadow_color.to_hex());
+    line("highlight_color", theme.highlight_color.to_hex());
+    line("track_color", theme.track_color.to_hex());
+    line("disabled_color", theme.disabled_color.to_hex());
+    line("disabled_text_color", theme.disabled_text_color.to_hex());
+    line("disabled_shade", theme.disabled_shade.to_string());
+    line("tooltip_color", theme.tooltip_color.to_hex());
+    line("tooltip_text_color", theme.tooltip_text_color.to_hex());
+    line("selection_color", theme.selection_color.to_hex());
+    line("selection_text_color", theme.selection_text_color.to_hex());
+    line("progress_color", theme.progress_color.to_hex());
+    line("progress_trail_color", theme.progress_trail_color.to_hex());
+    line("tab_color", theme.tab_color.to_hex());
+    line("tab_selected_color", theme.tab_selected_color.to_hex());
+    line("modal_dim_color", theme.modal_dim_color.to_hex());
+    line("hover_shade", theme.hover_shade.to_string());
+    line("pressed_shade", theme.pressed_shade.to_string());
+    line("bevel_size_px", theme.bevel_size_px.to_string());
+    line("font_size_px", theme.font_size_px.to_string());
+    line("padding_px", format_vec2(theme.padding_px));
+    line("title_font", format_font_style(theme.title_font));
+    line("body_font", format_font_style(theme.body_font));
+    line("button_font", format_font_style(theme.button_font));
+    line("bold_font", format_optional_font(theme.bold_font));
+    line("italic_font", format_optional_font(theme.italic_font));
+    line(
+        "bold_italic_font",
+        format_optional_font(theme.bold_italic_font),
+    );
+
+    let mut names: Vec<&String> = classes.classes.keys().collect();
+    names.sort();
+    for name in names {
+        let style = &classes.classes[name];
+        out.push_str(&format!("\n[class {name}]\n"));
+        let mut line = |key: &str, value: Option<String>| {
+            if let Some(value) = value {
+                out.push_str(&format!("{key} = {value}\n"));
+            }
+        };
+        line("control_color", style.control_color.map(Color::to_hex));
+        line("text_color", style.text_color.map(Color::to_hex));
+        line("link_color", style.link_color.map(Color::to_hex));
+        line("shadow_color", style.shadow_color.map(Color::to_hex));
+        line("highlight_color", style.highlight_color.map(Color::to_hex));
+        line("track_color", style.track_color.map(Color::to_hex));
+        line("hover_shade", style.hover_shade.map(|v| v.to_string()));
+        line("pressed_shade", style.pressed_shade.map(|v| v.to_string()));
+        line("bevel_size_px", style.bevel_size_px.map(|v| v.to_string()));
+        line("font_size_px", style.font_size_px.map(|v| v.to_string()));
+        line("padding_px", style.padding_px.map(format_vec2));
+    }
+    out
+}
+
+pub fn load_theme(path: impl AsRef<Path>) -> Result<(Theme, StyleClasses), ThemeFileError> {
+    let text = fs::read_to_string(path)?;
+    Ok(parse_theme(&text)?)
+}
+
+pub fn save_theme(path: impl AsRef<Path>, theme: &Theme, classes: &StyleClasses) -> io::Result<()> {
+    fs::write(path, theme_to_string(theme, classes))
+}
+
+/// Polls a theme file's modification time and reloads it when it changes.
+pub struct ThemeWatcher {
+    pub path: PathBuf,
+    pub last_modified: Option<SystemTime>,
+}
+
+impl ThemeWatcher {
+    /// Watch `path`. The first `poll` loads the file if it exists.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            last_modified: None,
+        }
+    }
+
+    /// Reload into `theme` and `classes` if the file changed since the last poll.
+    ///
+    /// Returns `Ok(true)` when they were replaced. On a read or parse error both are
+    /// left untouched and the same version of the file is not retried until it changes
+    /// again.
+    pub fn poll(
+        &mut self,
+        theme: &mut Theme,
+        classes: &mut StyleClasses,
+    ) -> Result<bool, ThemeFileError> {
+        let modified = fs::metadata(&self.path)?.modified()?;
+        if self.last_modified == Some(modified) {
+            return Ok(false);
+        }
+        self.last_modified = Some(modified);
+        (*theme, *classes) = load_theme(&self.path)?;
+        Ok(true)
+    }
+}
+
+/// Drop a `#` comment. Hex colors also start with `#`, so a `#` only starts a comment at
+/// the beginning of the line or after whitespace, and never as the first char of a value.
+fn strip_comment(line: &str) -> &str {
+    let value_start = line.find('=').map(|eq| {
+        let value = &line[eq + 1..];
+        eq + 1 + value.len() - value.trim_start().len()
+    });
+    for (i, ch) in line.char_indices() {
+        if ch == '#'
+            && Some(i) != value_start
+            && (i == 0 || line[..i].ends_with(char::is_whitespace))
+        {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+fn set_theme_key(theme: &mut Theme, key: &str, value: &str) -> Result<(), String> {
+    match key {
+        "control_color" => theme.control_color = parse_color(value)?,
+        "text_color" => theme.text_color = parse_color(value)?,
+        "link_color" => theme.link_color = parse_color(value)?,
+        "shadow_color" => theme.shadow_color = parse_color(value)?,
+        "highlight_color" => theme.highlight_color = parse_color(value)?,
+        "track_color" => theme.track_color = parse_color(value)?,
+        "disabled_color" => theme.disabled_color = parse_color(value)?,
+        "disabled_text_color" => theme.disabled_text_color = parse_color(value)?,
+        "disabled_shade" => theme.disabled_shade = parse_f32(value)?,
+        "tooltip_color" => theme.tooltip_color = parse_color(value)?,
+        "tooltip_text_color" => theme.tooltip_text_color = parse_color(value)?,
+        "selection_color" => theme.selection_color = parse_color(value)?,
+        "selection_text_color" => theme.selection_text_color = parse_color(value)?,
+        "progress_color" => theme.progress_color = parse_color(value)?,
+        "progress_trail_color" => theme.progress_trail_color = parse_color(value)?,
+        "tab_color" => theme.tab_color = parse_color(value)?,
+        "tab_selected_color" => theme.tab_selected_color = parse_color(value)?,
+        "modal_dim_color" => theme.modal_dim_color = parse_color(value)?,
+        "hover_shade" => theme.hover_shade = parse_f32(value)?,
+        "pressed_shade" => theme.pressed_shade = parse_f32(value)?,
+        "bevel_size_px" => theme.bevel_size_px = parse_f32(value)?,
+        "font_size_px" => theme.font_size_px = parse_f32(value)?,
+        "padding_px" => theme.padding_px = parse_vec2(value)?,
+        "title_font" => theme.title_font = parse_font_style(value)?,
+        "body_font" => theme.body_font = parse_font_style(value)?,
+        "button_font" => theme.button_font = parse_font_style(value)?,
+        "bold_font" => theme.bold_font = parse_optional_font(value)?,
+        "italic_font" => theme.italic_font = parse_optional_font(value)?,
+        "bold_italic_font" => theme.bold_italic_font = parse_optional_font(value)?,
+        _ => return Err(format!("unknown theme key '{key}'")),
+    }
+    Ok(())
+}
+
+fn set_style_key(style: &mut StyleOverride, key: &str, value: &str) -> Result<(), String> {
+    match key {
+        "control_color" => style.control_color = Some(parse_color(value)?),
+        "text_color" => style.text_color = Some(parse_color(value)?),
+        "link_color" => style.link_color = Some(parse_color(value)?),
+        "shadow_color" => style.shadow_color = Some(parse_color(value)?),
+        "highlight_color" => style.highlight_color = Some(parse_color(value)?),
+        "track_color" => style.track_color = Some(parse_color(value)?),
+        "hover_shade" => style.hover_shade = Some(parse_f32(value)?),
+        "pressed_shade" => style.pressed_shade = Some(parse_f32(value)?),
+        "bevel_size_px" => style.bevel_size_px = Some(parse_f32(value)?),
+        "font_size_px" => style.font_size_px = Some(parse_f32(value)?),
+        "padding_px" => style.padding_px = Some(parse_vec2(value)?),
+        _ => return Err(format!("unknown style class key '{key}'")),
+    }
+    Ok(())
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    Color::from_hex(value).ok_or_else(|| format!("invalid color '{value}', expected #rrggbb"))
+}
+
+fn parse_f32(value: &str) -> Result<f32, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid number '{value}'"))
+}
+
+fn parse_vec2(value: &str) -> Result<Vec2, String> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    match parts.as_slice() {
+        [x, y] => Ok(Vec2::new(parse_f32(x)?, parse_f32(y)?)),
+        [both] => Ok(Vec2::splat(parse_f32(both)?)),
+        _ => Err(format!("invalid size '{value}', expected 'x y'")),
+    }
+}
+
+fn parse_font_id(value: &str) -> Result<FontId, String> {
+    value
+        .parse()
+        .map(FontId)
+        .map_err(|_| format!("invalid font id '{value}'"))
+}
+
+fn parse_font_style(value: &str) -> Result<FontStyle, String> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    match parts.as_slice() {
+        [font] => Ok(FontStyle {
+            font: parse_font_id(font)?,
+            size_px: None,
+        }),
+        [font, size] => Ok(FontStyle {
+            font: parse_font_id(font)?,
+            size_px: Some(parse_f32(size)?),
+        }),
+        _ => Err(format!("invalid font '{value}', expected 'id [size]'")),
+    }
+}
+
+fn parse_optional_font(value: &str) -> Result<Option<FontId>, String> {
+    if value == "none" {
+        return Ok(None);
+    }
+    parse_font_id(value).map(Some)
+}
+
+fn format_vec2(value: Vec2) -> String {
+    format!("{} {}", value.x, value.y)
+}
+
+fn format_font_style(style: FontStyle) -> String {
+    match style.size_px {
+        Some(size_px) => format!("{} {}", style.font.0, size_px),
+        None => style.font.0.to_string(),
+    }
+}
+
+fn format_optional_font(font: Option<FontId>) -> String {
+    match font {
+        Some(font) => font.0.to_string(),
+        None => "none".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (Theme, StyleClasses) {
+        let theme = Theme {
+            control_color: Color::rgba(82, 98, 148, 190),
+            hover_shade: 0.92,
+            padding_px: Vec2::new(6.5, 3.0),
+            title_font: FontStyle {
+                font: FontId(2),
+                size_px: Some(28.0),
+            },
+            bold_font: Some(FontId(4)),
+            ..Theme::default()
+        };
+        let mut classes = StyleClasses::new();
+        classes.add(
+            "danger",
+            StyleOverride {
+                control_color: Some(Color::rgb(200, 40, 40)),
+                padding_px: Some(Vec2::splat(2.0)),
+                ..StyleOverride::default()
+            },
+        );
+        classes.add("empty", StyleOverride::default());
+        (theme, classes)
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let (theme, classes) = sample();
+        let text = theme_to_string(&theme, &classes);
+        let (parsed, parsed_classes) = parse_theme(&text).unwrap();
+        assert_eq!(parsed_classes, classes);
+        assert_eq!(theme_to_string(&parsed, &parsed_classes), text);
+        assert_eq!(parsed.control_color, theme.control_color);
+        assert_eq!(parsed.padding_px, theme.padding_px);
+        assert_eq!(parsed.title_font, theme.title_font);
+        assert_eq!(parsed.bold_font, Some(FontId(4)));
+        assert_eq!(parsed.italic_font, None);
+    }
+
+    #[test]
+    fn missing_keys_keep_defaults_and_comments_are_ignored() {
+        let text = "# a theme\n\ncontrol_color = #abc  # trailing comment\npadding_px = 3\n";
+        let (theme, classes) = parse_theme(text).unwrap();
+        assert_eq!(theme.control_color, Color::rgb(0xaa, 0xbb, 0xcc));
+        assert_eq!(theme.padding_px, Vec2::splat(3.0));
+        assert_eq!(theme.text_color, Theme::default().text_color);
+        assert!(classes.classes.is_empty());
+    }
+
+    #[test]
+    fn errors_report_their_line() {
+        let cases = [
+            ("hover_shade = 0.5\nnot a pair", 2),
+            ("wobble = 1", 1),
+            ("\n\ncontrol_color = #abcde", 3),
+            ("control_color = red", 1),
+            ("padding_px = 1 2 3", 1),
+            ("title_font = x", 1),
+            ("[class danger\ncontrol_color = #fff", 1),
+            ("[style danger]", 1),
+            ("[class ]", 1),
+            ("[classy]", 1),
+            ("[classfoo bar]", 1),
+            ("[class a]\nfont_size_px = big", 2),
+            ("[class a]\nbold_font = 1", 2),
+        ];
+        for (text, line) in cases {
+            let err = parse_theme(text).unwrap_err();
+            assert_eq!(err.line, line, "{text:?}: {err}");
+        }
+        let err = parse_theme("[classy]").unwrap_err();
+        assert_eq!(err.message, "unknown section '[classy]'");
+    }
+
+    #[test]
+    fn hex_values_are_not_comments() {
+        let (theme, _) = parse_theme("text_color = #102030 # comment").unwrap();
+        assert_eq!(theme.text_color, Color::rgb(0x10, 0x20, 0x30));
+    }
+}
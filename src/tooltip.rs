@@ -0,0 +1,134 @@
+use glam::Vec2;
+
+use crate::{ImageStyle, Rect, RichText};
+
+/// What a tooltip shows.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TooltipContent {
+    /// Plain text, wrapped to `Tooltip::max_width`.
+    Text(String),
+    /// Rich text markup, laid out like a rich `Label`.
+    Rich(RichText),
+    /// An image drawn at a fixed size, e.g. an item preview.
+    Image { image: ImageStyle, size: Vec2 },
+}
+
+/// Side of the element a tooltip prefers. It flips to the opposite side when it would
+/// leave `Gui::bounds`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TooltipPlacement {
+    #[default]
+    Below,
+    Above,
+    Right,
+    Left,
+}
+
+impl TooltipPlacement {
+    fn opposite(self) -> Self {
+        match self {
+            TooltipPlacement::Below => TooltipPlacement::Above,
+            TooltipPlacement::Above => TooltipPlacement::Below,
+            TooltipPlacement::Right => TooltipPlacement::Left,
+            TooltipPlacement::Left => TooltipPlacement::Right,
+        }
+    }
+}
+
+/// Hover tooltip attached to an element with `Gui::set_tooltip`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tooltip {
+    pub content: TooltipContent,
+    pub placement: TooltipPlacement,
+    /// Widest the tooltip gets before text wraps, including padding.
+    pub max_width: f32,
+}
+
+impl Tooltip {
+    pub fn text(text: &str) -> Self {
+        Self::new(TooltipContent::Text(text.to_string()))
+    }
+
+    /// Tooltip with rich text markup, see `RichText`.
+    pub fn rich(markup: &str) -> Self {
+        Self::new(TooltipContent::Rich(RichText::parse(markup)))
+    }
+
+    pub fn image(image: ImageStyle, size: Vec2) -> Self {
+        Self::new(TooltipContent::Image { image, size })
+    }
+
+    pub fn new(content: TooltipContent) -> Self {
+        Self {
+            content,
+            placement: TooltipPlacement::Below,
+            max_width: 260.0,
+        }
+    }
+
+    pub fn with_placement(mut self, placement: TooltipPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = max_width;
+        self
+    }
+}
+
+/// Rect for a tooltip of `size` next to `anchor`, `gap` pixels away on the `placement`
+/// side. If that side doesn't fit inside `bounds` and the opposite side does, it flips.
+/// The tooltip is then shifted along the other axis to stay inside `bounds`.
+pub fn place_tooltip(
+    anchor: Rect,
+    size: Vec2,
+    placement: TooltipPlacement,
+    gap: f32,
+    bounds: Option<Rect>,
+) -> Rect {
+    let at = |placement: TooltipPlacement| {
+        let position = match placement {
+            TooltipPlacement::Below => {
+                Vec2::new(anchor.position.x, anchor.position.y + anchor.size.y + gap)
+            }
+            TooltipPlacement::Above => {
+                Vec2::new(anchor.position.x, anchor.position.y - gap - size.y)
+            }
+            TooltipPlacement::Right => {
+                Vec2::new(anchor.position.x + anchor.size.x + gap, anchor.position.y)
+            }
+            TooltipPlacement::Left => {
+                Vec2::new(anchor.position.x - gap - size.x, anchor.position.y)
+            }
+        };
+        Rect::new(position, size)
+    };
+
+    let Some(bounds) = bounds else {
+        return at(placement);
+    };
+    let fits = |rect: Rect| match placement {
+        TooltipPlacement::Below | TooltipPlacement::Above => {
+            rect.position.y >= bounds.position.y
+                && rect.position.y + rect.size.y <= bounds.position.y + bounds.size.y
+        }
+        TooltipPlacement::Right | TooltipPlacement::Left => {
+            rect.position.x >= bounds.position.x
+                && rect.position.x + rect.size.x <= bounds.position.x + bounds.size.x
+        }
+    };
+
+    let mut rect = at(placement);
+    if !fits(rect) {
+        let flipped = at(placement.opposite());
+        if fits(flipped) {
+            rect = flipped;
+        }
+    }
+
+    // keep inside bounds, preferring the top-left edge when the tooltip is too big
+    let max = bounds.position + bounds.size - rect.size;
+    rect.position = rect.position.min(max).max(bounds.position);
+    rect
+}
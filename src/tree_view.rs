@@ -0,0 +1,441 @@
+use std::ops::Range;
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Event, FontId, ImageStyle, Key, Rect, StyleOverride, ELEMENT_NEXT_ID};
+
+/// A node of a `TreeView`. `key` is chosen by the app and identifies the node in events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode {
+    pub key: u64,
+    pub text: String,
+    pub icon: Option<ImageStyle>,
+    pub children: Vec<TreeNode>,
+    /// Children are loaded through `TreeView::load_children` the first time the node
+    /// expands.
+    pub lazy: bool,
+    pub expanded: bool,
+}
+
+impl TreeNode {
+    pub fn new(key: u64, text: &str) -> Self {
+        Self {
+            key,
+            text: text.to_string(),
+            icon: None,
+            children: Vec::new(),
+            lazy: false,
+            expanded: false,
+        }
+    }
+
+    /// Node whose children are loaded when it is first expanded.
+    pub fn lazy(key: u64, text: &str) -> Self {
+        Self {
+            lazy: true,
+            ..Self::new(key, text)
+        }
+    }
+
+    pub fn with_children(mut self, children: Vec<TreeNode>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn with_icon(mut self, icon: ImageStyle) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Whether the node shows an expander.
+    pub fn has_children(&self) -> bool {
+        self.lazy || !self.children.is_empty()
+    }
+}
+
+/// Loads the children of the lazy node with the given key.
+pub type TreeLoadChildren = Box<dyn FnMut(u64) -> Vec<TreeNode>>;
+
+/// One visible row of a `TreeView`, rebuilt whenever nodes expand or collapse.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeRow {
+    pub key: u64,
+    pub depth: usize,
+    /// Child indices from the roots down to the node.
+    pub path: Vec<usize>,
+    /// For each ancestor level, whether its guide line runs through this row.
+    pub guides: Vec<bool>,
+    pub last_child: bool,
+    pub has_children: bool,
+    pub expanded: bool,
+}
+
+/// Scrollable tree of expandable nodes, drawn with indentation guides.
+pub struct TreeView {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub roots: Vec<TreeNode>,
+    pub row_height: f32,
+    /// Horizontal step per depth level, also the width of the expander.
+    pub indent: f32,
+    /// Pixels scrolled from the top.
+    pub scroll_offset: f32,
+    pub selected: Option<u64>,
+    /// Index into `rows` of the row under the mouse.
+    pub hovered_row: Option<usize>,
+    /// Visible rows, see `refresh`.
+    pub rows: Vec<TreeRow>,
+    pub load_children: Option<TreeLoadChildren>,
+    pub font: Option<FontId>,
+    pub font_size_px: Option<f32>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    pub was_mouse_pressed: bool,
+}
+
+impl TreeView {
+    pub fn new(position: Vec2, size: Vec2, roots: Vec<TreeNode>, row_height: f32) -> Self {
+        let mut tree = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            roots,
+            row_height,
+            indent: row_height * 0.8,
+            scroll_offset: 0.0,
+            selected: None,
+            hovered_row: None,
+            rows: Vec::new(),
+            load_children: None,
+            font: None,
+            font_size_px: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            was_mouse_pressed: false,
+        };
+        tree.refresh();
+        tree
+    }
+
+    pub fn set_load_children(&mut self, load_children: impl FnMut(u64) -> Vec<TreeNode> + 'static) {
+        self.load_children = Some(Box::new(load_children));
+    }
+
+    pub fn set_roots(&mut self, roots: Vec<TreeNode>) {
+        self.roots = roots;
+        self.refresh();
+    }
+
+    pub fn set_indent(&mut self, indent: f32) {
+        self.indent = indent;
+    }
+
+    pub fn set_font(&mut self, font: FontId) {
+        self.font = Some(font);
+    }
+
+    pub fn set_font_size(&mut self, font_size_px: f32) {
+        self.font_size_px = Some(font_size_px);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// Rebuild `rows` from `roots`. Call it after changing nodes directly.
+    pub fn refresh(&mut self) {
+        self.rows.clear();
+        flatten(
+            &self.roots,
+            &mut Vec::new(),
+            &mut Vec::new(),
+            &mut self.rows,
+        );
+        if self.hovered_row.is_some_and(|row| row >= self.rows.len()) {
+            self.hovered_row = None;
+        }
+        self.scroll_by(0.0);
+    }
+
+    pub fn node(&self, key: u64) -> Option<&TreeNode> {
+        find_node(&self.roots, key)
+    }
+
+    /// Call `refresh` after changing `children` or `expanded`.
+    pub fn node_mut(&mut self, key: u64) -> Option<&mut TreeNode> {
+        find_node_mut(&mut self.roots, key)
+    }
+
+    /// The selected node's text.
+    pub fn selected_text(&self) -> Option<&str> {
+        self.node(self.selected?).map(|node| node.text.as_str())
+    }
+
+    /// Select a node without emitting an event.
+    pub fn select(&mut self, key: Option<u64>) {
+        self.selected = key;
+        if let Some(row) = self.selected_row() {
+            self.scroll_to(row);
+        }
+    }
+
+    /// Expand or collapse a node without emitting events, loading lazy children first.
+    pub fn set_expanded(&mut self, key: u64, expanded: bool) {
+        if expanded {
+            self.load_if_lazy(key);
+        }
+        if let Some(node) = self.node_mut(key) {
+            node.expanded = expanded && !node.children.is_empty();
+        }
+        self.refresh();
+    }
+
+    pub fn content_height(&self) -> f32 {
+        self.rows.len() as f32 * self.row_height
+    }
+
+    pub fn max_scroll(&self) -> f32 {
+        (self.content_height() - self.size.y).max(0.0)
+    }
+
+    /// Scroll by `pixels`, positive scrolls down.
+    pub fn scroll_by(&mut self, pixels: f32) {
+        self.scroll_offset = (self.scroll_offset + pixels).clamp(0.0, self.max_scroll());
+    }
+
+    /// Scroll just enough to show all of row `row`.
+    pub fn scroll_to(&mut self, row: usize) {
+        let top = row as f32 * self.row_height;
+        if top < self.scroll_offset {
+            self.scroll_offset = top;
+        } else if top + self.row_height > self.scroll_offset + self.size.y {
+            self.scroll_offset = top + self.row_height - self.size.y;
+        }
+        self.scroll_by(0.0);
+    }
+
+    /// Range of `rows` at least partly inside the tree.
+    pub fn visible_range(&self) -> Range<usize> {
+        if self.row_height <= 0.0 {
+            return 0..0;
+        }
+        let first = (self.scroll_offset / self.row_height) as usize;
+        let last = ((self.scroll_offset + self.size.y) / self.row_height).ceil() as usize;
+        first.min(self.rows.len())..last.min(self.rows.len())
+    }
+
+    pub fn row_rect(&self, row: usize) -> Rect {
+        Rect::new(
+            self.position + Vec2::new(0.0, row as f32 * self.row_height - self.scroll_offset),
+            Vec2::new(self.size.x, self.row_height),
+        )
+    }
+
+    /// Square the expander of `row` is drawn in.
+    pub fn expander_rect(&self, row: usize) -> Rect {
+        let rect = self.row_rect(row);
+        let depth = self.rows.get(row).map_or(0, |row| row.depth);
+        Rect::new(
+            rect.position + Vec2::new(depth as f32 * self.indent, 0.0),
+            Vec2::new(self.indent, rect.size.y),
+        )
+    }
+
+    pub fn row_at(&self, position: Vec2) -> Option<usize> {
+        if !Rect::new(self.position, self.size).contains(position) || self.row_height <= 0.0 {
+            return None;
+        }
+        let row = ((position.y - self.position.y + self.scroll_offset) / self.row_height) as usize;
+        (row < self.rows.len()).then_some(row)
+    }
+
+    /// The node shown in `rows[row]`.
+    pub fn row_node(&self, row: usize) -> Option<&TreeNode> {
+        let (first, rest) = self.rows.get(row)?.path.split_first()?;
+        rest.iter()
+            .try_fold(self.roots.get(*first)?, |node, index| {
+                node.children.get(*index)
+            })
+    }
+
+    /// Index into `rows` of the selected node, if it is visible.
+    pub fn selected_row(&self) -> Option<usize> {
+        let selected = self.selected?;
+        self.rows.iter().position(|row| row.key == selected)
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.hovered_row = None;
+    }
+
+    /// Pressing a node's expander toggles it, pressing the rest of the row selects it.
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Vec<Event> {
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+        self.hovered_row = self.row_at(mouse_position);
+
+        let Some(row) = self.hovered_row.filter(|_| press_started) else {
+            return Vec::new();
+        };
+        if self.rows[row].has_children && self.expander_rect(row).contains(mouse_position) {
+            let (key, expanded) = (self.rows[row].key, self.rows[row].expanded);
+            return self.toggle(key, !expanded);
+        }
+        self.select_key(self.rows[row].key).into_iter().collect()
+    }
+
+    /// Keyboard input while focused. Up/Down, Page Up/Down and Home/End move the
+    /// selection, Right expands or moves to the first child, Left collapses or moves to
+    /// the parent, and Enter/Space toggle the selected node.
+    pub fn key_pressed(&mut self, key: Key) -> Vec<Event> {
+        if self.rows.is_empty() {
+            return Vec::new();
+        }
+        let last = self.rows.len() - 1;
+        let page = ((self.size.y / self.row_height.max(1.0)) as usize).max(1);
+        let Some(current) = self.selected_row() else {
+            return match key {
+                Key::Up | Key::Down | Key::Home | Key::End | Key::PageUp | Key::PageDown => {
+                    let row = if matches!(key, Key::End) { last } else { 0 };
+                    self.select_key(self.rows[row].key).into_iter().collect()
+                }
+                _ => Vec::new(),
+            };
+        };
+        let row = &self.rows[current];
+        let (node_key, expanded, has_children) = (row.key, row.expanded, row.has_children);
+        let target = match key {
+            Key::Up => current.saturating_sub(1),
+            Key::Down => (current + 1).min(last),
+            Key::PageUp => current.saturating_sub(page),
+            Key::PageDown => (current + page).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            Key::Right if has_children && !expanded => return self.toggle(node_key, true),
+            Key::Right if expanded => (current + 1).min(last),
+            Key::Left if expanded => return self.toggle(node_key, false),
+            Key::Left => {
+                let depth = self.rows[current].depth;
+                let Some(parent) = self.rows[..current]
+                    .iter()
+                    .rposition(|row| row.depth + 1 == depth)
+                else {
+                    return Vec::new();
+                };
+                parent
+            }
+            Key::Enter | Key::Space if has_children => return self.toggle(node_key, !expanded),
+            _ => return Vec::new(),
+        };
+        self.select_key(self.rows[target].key).into_iter().collect()
+    }
+
+    fn select_key(&mut self, key: u64) -> Option<Event> {
+        if let Some(row) = self.rows.iter().position(|row| row.key == key) {
+            self.scroll_to(row);
+        }
+        if self.selected == Some(key) {
+            return None;
+        }
+        self.selected = Some(key);
+        Some(Event::TreeSelectionChanged { node: key })
+    }
+
+    /// Expand or collapse `key` and report it. Collapsing a node that hides the
+    /// selection selects the node.
+    fn toggle(&mut self, key: u64, expand: bool) -> Vec<Event> {
+        let mut events = Vec::new();
+        if expand {
+            self.set_expanded(key, true);
+            if self.node(key).is_some_and(|node| node.expanded) {
+                events.push(Event::TreeNodeExpanded { node: key });
+            }
+            return events;
+        }
+
+        let hides_selection = self.selected.is_some_and(|selected| {
+            selected != key
+                && self
+                    .node(key)
+                    .is_some_and(|node| find_node(&node.children, selected).is_some())
+        });
+        self.set_expanded(key, false);
+        events.push(Event::TreeNodeCollapsed { node: key });
+        if hides_selection {
+            events.extend(self.select_key(key));
+        }
+        events
+    }
+
+    fn load_if_lazy(&mut self, key: u64) {
+        if !self.node(key).is_some_and(|node| node.lazy) {
+            return;
+        }
+        let children = match self.load_children.as_mut() {
+            Some(load_children) => load_children(key),
+            None => Vec::new(),
+        };
+        if let Some(node) = self.node_mut(key) {
+            node.children = children;
+            node.lazy = false;
+        }
+    }
+}
+
+fn flatten(
+    nodes: &[TreeNode],
+    path: &mut Vec<usize>,
+    guides: &mut Vec<bool>,
+    rows: &mut Vec<TreeRow>,
+) {
+    for (index, node) in nodes.iter().enumerate() {
+        let last_child = index + 1 == nodes.len();
+        path.push(index);
+        rows.push(TreeRow {
+            key: node.key,
+            depth: guides.len(),
+            path: path.clone(),
+            guides: guides.clone(),
+            last_child,
+            has_children: node.has_children(),
+            expanded: node.expanded,
+        });
+        if node.expanded {
+            guides.push(!last_child);
+            flatten(&node.children, path, guides, rows);
+            guides.pop();
+        }
+        path.pop();
+    }
+}
+
+fn find_node(nodes: &[TreeNode], key: u64) -> Option<&TreeNode> {
+    nodes.iter().find_map(|node| {
+        if node.key == key {
+            Some(node)
+        } else {
+            find_node(&node.children, key)
+        }
+    })
+}
+
+fn find_node_mut(nodes: &mut [TreeNode], key: u64) -> Option<&mut TreeNode> {
+    nodes.iter_mut().find_map(|node| {
+        if node.key == key {
+            Some(node)
+        } else {
+            find_node_mut(&mut node.children, key)
+        }
+    })
+}
@@ -2,7 +2,8 @@ use std::sync::atomic::Ordering;
 
 use glam::Vec2;
 
-use super::{Event, ImageStyle, ELEMENT_NEXT_ID};
+use super::slider::snap_slider_value;
+use super::{Event, ImageStyle, StateImages, StyleOverride, ELEMENT_NEXT_ID};
 
 pub struct VerticalSlider {
     pub id: u32,
@@ -17,6 +18,10 @@ pub struct VerticalSlider {
     pub label: Option<String>,
     pub track_image: Option<ImageStyle>,
     pub thumb_image: Option<ImageStyle>,
+    /// Per-state thumb skin drawn instead of the theme's bevel box.
+    pub thumb_state_images: Option<StateImages>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
     pub hovered: bool,
     pub was_pressed: bool,
 }
@@ -47,6 +52,9 @@ impl VerticalSlider {
             label,
             track_image: None,
             thumb_image: None,
+            thumb_state_images: None,
+            style_class: None,
+            style: StyleOverride::default(),
             hovered: false,
             was_pressed: false,
         }
@@ -60,6 +68,23 @@ impl VerticalSlider {
         self.thumb_image = Some(image);
     }
 
+    pub fn set_thumb_state_images(&mut self, images: StateImages) {
+        self.thumb_state_images = Some(images);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    pub fn cancel_press(&mut self) {
+        self.hovered = false;
+        self.was_pressed = false;
+    }
+
     pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
         let mut event: Option<Event> = None;
 
@@ -83,23 +108,13 @@ impl VerticalSlider {
                 let total = br.y - tl.y;
                 let local_p = mouse_position.y - tl.y;
                 let fraction = local_p / total;
-                self.value = self.minimum + fraction * (self.maximum - self.minimum);
-
-                // if value is within 5% of the minimum or maximum, snap to it
-                if self.snap_sensetivity_fraction > 0.0 {
-                    if self.value > self.maximum * (1.0 - self.snap_sensetivity_fraction) {
-                        self.value = self.maximum;
-                    }
-                    if self.value < (self.maximum - self.minimum) * self.snap_sensetivity_fraction {
-                        self.value = self.minimum;
-                    }
-                }
-
-                // round to nearest 100th, needs to work for negative and 0
-                self.value = (self.value * 100.0).round() / 100.0;
-
-                // round to nearest step size
-                self.value = (self.value / self.step_size).round() * self.step_size;
+                self.value = snap_slider_value(
+                    self.minimum + fraction * (self.maximum - self.minimum),
+                    self.minimum,
+                    self.maximum,
+                    self.step_size,
+                    self.snap_sensetivity_fraction,
+                );
 
                 // only emit event if value changed
                 if self.value != old_value {
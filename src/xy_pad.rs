@@ -0,0 +1,212 @@
+use std::sync::atomic::Ordering;
+
+use glam::Vec2;
+
+use super::{Event, ImageStyle, Rect, StateImages, StyleOverride, ELEMENT_NEXT_ID};
+
+/// A two-dimensional slider. The pointer position inside the pad maps to a `Vec2`
+/// between `minimum` and `maximum`, with x growing to the right and y growing down
+/// like `VerticalSlider`. Swap `minimum.y` and `maximum.y` to put the minimum at the
+/// bottom.
+pub struct XYPad {
+    pub id: u32,
+    pub position: Vec2,
+    pub size: Vec2,
+    pub minimum: Vec2,
+    pub maximum: Vec2,
+    /// Per-axis snapping to multiples of the step, like `Slider::step_size`. `0.0`
+    /// turns snapping off for that axis.
+    pub step_size: Vec2,
+    pub value: Vec2,
+    /// Keep the value inside the circle inscribed in the pad (an ellipse if the pad
+    /// isn't square), e.g. for a stick.
+    pub circular: bool,
+    /// Side of the square thumb at the crosshair center.
+    pub thumb_size: f32,
+    pub track_image: Option<ImageStyle>,
+    pub thumb_image: Option<ImageStyle>,
+    /// Per-state thumb skin drawn instead of the theme's bevel box.
+    pub thumb_state_images: Option<StateImages>,
+    pub style_class: Option<String>,
+    pub style: StyleOverride,
+    pub hovered: bool,
+    /// Whether the thumb is being dragged.
+    pub was_pressed: bool,
+    pub was_mouse_pressed: bool,
+}
+
+impl XYPad {
+    pub fn new(
+        position: Vec2,
+        size: Vec2,
+        minimum: Vec2,
+        maximum: Vec2,
+        step_size: Vec2,
+        default_value: Vec2,
+    ) -> Self {
+        let mut pad = Self {
+            id: ELEMENT_NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            position,
+            size,
+            minimum,
+            maximum,
+            step_size,
+            value: default_value,
+            circular: false,
+            thumb_size: 10.0,
+            track_image: None,
+            thumb_image: None,
+            thumb_state_images: None,
+            style_class: None,
+            style: StyleOverride::default(),
+            hovered: false,
+            was_pressed: false,
+            was_mouse_pressed: false,
+        };
+        pad.value = pad.constrain(default_value);
+        pad
+    }
+
+    pub fn set_circular(&mut self, circular: bool) {
+        self.circular = circular;
+        self.value = self.constrain(self.value);
+    }
+
+    pub fn set_thumb_size(&mut self, thumb_size: f32) {
+        self.thumb_size = thumb_size;
+    }
+
+    pub fn set_track_image(&mut self, image: ImageStyle) {
+        self.track_image = Some(image);
+    }
+
+    pub fn set_thumb_image(&mut self, image: ImageStyle) {
+        self.thumb_image = Some(image);
+    }
+
+    pub fn set_thumb_state_images(&mut self, images: StateImages) {
+        self.thumb_state_images = Some(images);
+    }
+
+    pub fn set_style_class(&mut self, class: &str) {
+        self.style_class = Some(class.to_string());
+    }
+
+    pub fn set_style(&mut self, style: StyleOverride) {
+        self.style = style;
+    }
+
+    /// Set the value without an event, kept inside the bounds and snapped.
+    pub fn set_value(&mut self, value: Vec2) {
+        self.value = self.constrain(value);
+    }
+
+    /// Pixel position of `value` inside the pad.
+    pub fn value_to_point(&self, value: Vec2) -> Vec2 {
+        self.value_to_point_unclamped(value)
+            .clamp(self.position, self.position + self.size)
+    }
+
+    /// Value under a pixel position, clamped to the pad (or its circle) and snapped.
+    pub fn point_to_value(&self, point: Vec2) -> Vec2 {
+        let fraction = (point - self.position) / self.size.max(Vec2::ONE);
+        self.constrain(self.minimum + fraction * (self.maximum - self.minimum))
+    }
+
+    /// Rect of the thumb drawn at the current value.
+    pub fn thumb_rect(&self) -> Rect {
+        let center = self.value_to_point(self.value);
+        Rect::new(
+            center - Vec2::splat(self.thumb_size * 0.5),
+            Vec2::splat(self.thumb_size),
+        )
+    }
+
+    /// Drop hover and drag state without an `XYPadReleased` event.
+    pub fn cancel_press(&mut self) {
+        self.hovered = false;
+        self.was_pressed = false;
+    }
+
+    pub fn step(&mut self, mouse_position: Vec2, mouse_pressed: bool) -> Option<Event> {
+        let press_started = mouse_pressed && !self.was_mouse_pressed;
+        self.was_mouse_pressed = mouse_pressed;
+        self.hovered = self.contains(mouse_position);
+
+        if self.was_pressed && !mouse_pressed {
+            self.was_pressed = false;
+            return Some(Event::XYPadReleased { value: self.value });
+        }
+        if press_started && self.hovered {
+            self.was_pressed = true;
+        }
+        if !self.was_pressed {
+            return None;
+        }
+
+        // keeps following the pointer outside the pad until release
+        let value = self.point_to_value(mouse_position);
+        if value == self.value {
+            return None;
+        }
+        self.value = value;
+        Some(Event::XYPadMoved { value })
+    }
+
+    /// Whether `point` is over the pad, or over its circle when `circular`.
+    pub fn contains(&self, point: Vec2) -> bool {
+        if !Rect::new(self.position, self.size).contains(point) {
+            return false;
+        }
+        !self.circular || self.circle_offset(point).length() <= 1.0
+    }
+
+    /// `point` relative to the pad center, scaled so the inscribed circle has radius 1.
+    fn circle_offset(&self, point: Vec2) -> Vec2 {
+        let half = (self.size * 0.5).max(Vec2::splat(0.5));
+        (point - self.position - half) / half
+    }
+
+    fn constrain(&self, value: Vec2) -> Vec2 {
+        let mut value = value;
+        if self.circular {
+            let offset = self.circle_offset(self.value_to_point_unclamped(value));
+            if offset.length() > 1.0 {
+                let half = self.size * 0.5;
+                let point = self.position + half + offset.normalize() * half;
+                let fraction = (point - self.position) / self.size.max(Vec2::ONE);
+                value = self.minimum + fraction * (self.maximum - self.minimum);
+            }
+        }
+        Vec2::new(
+            snap_and_clamp(value.x, self.step_size.x, self.minimum.x, self.maximum.x),
+            snap_and_clamp(value.y, self.step_size.y, self.minimum.y, self.maximum.y),
+        )
+    }
+
+    fn value_to_point_unclamped(&self, value: Vec2) -> Vec2 {
+        let range = self.maximum - self.minimum;
+        let fraction = Vec2::new(
+            fraction_of(value.x - self.minimum.x, range.x),
+            fraction_of(value.y - self.minimum.y, range.y),
+        );
+        self.position + fraction * self.size
+    }
+}
+
+fn fraction_of(offset: f32, range: f32) -> f32 {
+    if range.abs() <= f32::EPSILON {
+        0.0
+    } else {
+        offset / range
+    }
+}
+
+fn snap_and_clamp(value: f32, step_size: f32, minimum: f32, maximum: f32) -> f32 {
+    let value = if step_size > 0.0 {
+        (value / step_size).round() * step_size
+    } else {
+        value
+    };
+    value.clamp(minimum.min(maximum), minimum.max(maximum))
+}